@@ -22,6 +22,7 @@ fn main() {
         initial_state: BoardState::Turn, // must match `card_config`
         starting_pot: 200,
         effective_stack: 900,
+        big_blind: 0,
         rake_rate: 0.0,
         rake_cap: 0.0,
         flop_bet_sizes: [bet_sizes.clone(), bet_sizes.clone()], // [OOP, IP]
@@ -32,6 +33,11 @@ fn main() {
         add_allin_threshold: 1.5, // add all-in if (maximum bet size) <= 1.5x pot
         force_allin_threshold: 0.15, // force all-in if (SPR after the opponent's call) <= 0.15
         merging_threshold: 0.1,
+        depth_limit: None,
+        board_bet_sizes: Vec::new(),
+        max_bets_per_street: None,
+        min_raise: MinRaise::FullRaise,
+        incomplete_raise: IncompleteRaise::Allow,
     };
 
     // build the game tree
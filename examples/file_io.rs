@@ -19,6 +19,7 @@ fn main() {
         initial_state: BoardState::Turn,
         starting_pot: 200,
         effective_stack: 900,
+        big_blind: 0,
         rake_rate: 0.0,
         rake_cap: 0.0,
         flop_bet_sizes: [bet_sizes.clone(), bet_sizes.clone()],
@@ -29,6 +30,11 @@ fn main() {
         add_allin_threshold: 1.5,
         force_allin_threshold: 0.15,
         merging_threshold: 0.1,
+        depth_limit: None,
+        board_bet_sizes: Vec::new(),
+        max_bets_per_street: None,
+        min_raise: MinRaise::FullRaise,
+        incomplete_raise: IncompleteRaise::Allow,
     };
 
     let action_tree = ActionTree::new(tree_config).unwrap();
@@ -1,6 +1,7 @@
 use crate::bet_size::*;
 use crate::card::*;
 use crate::mutex_like::*;
+use crate::range::*;
 
 #[cfg(feature = "bincode")]
 use bincode::{Decode, Encode};
@@ -12,6 +13,7 @@ pub(crate) const PLAYER_MASK: u8 = 3;
 pub(crate) const PLAYER_CHANCE_FLAG: u8 = 4; // chance_player = PLAYER_CHANCE_FLAG | prev_player
 pub(crate) const PLAYER_TERMINAL_FLAG: u8 = 8;
 pub(crate) const PLAYER_FOLD_FLAG: u8 = 24;
+pub(crate) const PLAYER_LEAF_FLAG: u8 = 40; // depth-limited leaf (terminal, valued by evaluator)
 
 /// Available actions of the postflop game.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
@@ -54,6 +56,54 @@ pub enum BoardState {
     River = 2,
 }
 
+/// An enum representing where a depth-limited game tree is cut.
+///
+/// The nodes at which the tree is cut become *leaf nodes*, which are treated as terminal nodes and
+/// valued by a [`LeafEvaluator`].
+///
+/// [`LeafEvaluator`]: crate::LeafEvaluator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "bincode", derive(Decode, Encode))]
+pub enum DepthLimit {
+    /// Cuts the tree at the end of the specified street, i.e., the dealing of the next card
+    /// becomes a leaf node. `BoardState::River` means no limit.
+    Street(BoardState),
+
+    /// Cuts the tree after the specified number of actions (chance actions are not counted).
+    /// If a bet is not yet called at the leaf node, the uncalled amount is returned to the bettor.
+    Actions(u32),
+}
+
+/// An enum representing the minimum raise rule.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "bincode", derive(Decode, Encode))]
+pub enum MinRaise {
+    /// The raise increment must be at least the previous bet or raise increment (the standard
+    /// no-limit rule).
+    #[default]
+    FullRaise,
+
+    /// The raise increment must be at least the specified ratio of the previous bet or raise
+    /// increment (at least one chip). The ratio must be positive.
+    Ratio(f64),
+}
+
+/// An enum representing how an all-in raise smaller than the minimum raise is handled.
+///
+/// Since both players have the same effective stack, the opponent of an all-in player can only
+/// fold or call, so an incomplete raise never reopens the betting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "bincode", derive(Decode, Encode))]
+pub enum IncompleteRaise {
+    /// An all-in raise is allowed even if it is smaller than the minimum raise.
+    #[default]
+    Allow,
+
+    /// An all-in raise smaller than the minimum raise is not allowed, i.e., a player who cannot
+    /// make a full raise can only fold or call.
+    Disallow,
+}
+
 /// A struct containing the game tree configuration.
 ///
 /// # Examples
@@ -67,6 +117,7 @@ pub enum BoardState {
 ///     initial_state: BoardState::Turn,
 ///     starting_pot: 200,
 ///     effective_stack: 900,
+///     big_blind: 0,
 ///     rake_rate: 0.05,
 ///     rake_cap: 30.0,
 ///     flop_bet_sizes: Default::default(),
@@ -77,6 +128,11 @@ pub enum BoardState {
 ///     add_allin_threshold: 1.5,
 ///     force_allin_threshold: 0.15,
 ///     merging_threshold: 0.1,
+///     depth_limit: None,
+///     board_bet_sizes: Vec::new(),
+///     max_bets_per_street: None,
+///     min_raise: MinRaise::FullRaise,
+///     incomplete_raise: IncompleteRaise::Allow,
 /// };
 /// ```
 #[derive(Debug, Clone, Default)]
@@ -91,6 +147,10 @@ pub struct TreeConfig {
     /// Initial effective stack. Must be greater than `0`.
     pub effective_stack: i32,
 
+    /// Big blind size used by [`BetSize::BigBlinds`]. Must be non-negative, and must be greater
+    /// than `0` if [`BetSize::BigBlinds`] is used.
+    pub big_blind: i32,
+
     /// Rake rate. Must be between `0.0` and `1.0`, inclusive.
     pub rake_rate: f64,
 
@@ -131,12 +191,73 @@ pub struct TreeConfig {
     ///
     /// Personal recommendation: around `0.1`
     pub merging_threshold: f64,
+
+    /// Depth limit of the game tree (set `None` to build the full tree).
+    pub depth_limit: Option<DepthLimit>,
+
+    /// Board-dependent bet size rules. When a turn or river card is dealt, the first rule of the
+    /// street whose textures all match the board replaces the bet and donk size options of that
+    /// street. If no rule matches, the above options are used.
+    pub board_bet_sizes: Vec<BoardBetSizes>,
+
+    /// Maximum number of bets and raises per street, including the first bet (set `None` for no
+    /// limit). Must be positive.
+    ///
+    /// This is checked independently of [`BetSizeOptions::max_raises`], so the stricter limit
+    /// takes effect: `Some(n)` is equivalent to setting `max_raises` to `n - 1` in every bet size
+    /// option, including those of [`board_bet_sizes`](Self::board_bet_sizes).
+    pub max_bets_per_street: Option<u32>,
+
+    /// Minimum raise rule.
+    pub min_raise: MinRaise,
+
+    /// Handling of all-in raises smaller than the minimum raise.
+    pub incomplete_raise: IncompleteRaise,
+}
+
+/// A struct representing a board-dependent bet size rule of [`TreeConfig`].
+///
+/// Since every [`BoardTexture`] is invariant under the permutation of suits, isomorphic turn and
+/// river cards always select the same rule, so the rules do not break the suit isomorphism.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// // use smaller sizes on paired rivers
+/// let rule = BoardBetSizes {
+///     street: BoardState::River,
+///     textures: vec![BoardTexture::Paired],
+///     bet_sizes: [("33%", "2x").try_into().unwrap(), ("33%", "2x").try_into().unwrap()],
+///     donk_sizes: None,
+/// };
+///
+/// let board = ["Td", "9d", "6h", "2c", "9c"].map(|card| card_from_str(card).unwrap());
+/// assert!(rule.matches(&board));
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "bincode", derive(Decode, Encode))]
+pub struct BoardBetSizes {
+    /// The street to which the rule applies (turn or river).
+    pub street: BoardState,
+
+    /// The textures that the board (including the dealt card) must have. An empty list matches
+    /// any board.
+    pub textures: Vec<BoardTexture>,
+
+    /// Bet size options of each player used when the rule matches.
+    pub bet_sizes: [BetSizeOptions; 2],
+
+    /// Donk size options used when the rule matches (set `None` to use default sizes).
+    pub donk_sizes: Option<DonkSizeOptions>,
 }
 
 /// A struct representing an abstract game tree.
 ///
 /// An [`ActionTree`] does not distinguish between possible chance events (i.e., the dealing of turn
-/// and river cards) and treats them as the same action.
+/// and river cards) and treats them as the same action. If [`TreeConfig::board_bet_sizes`] is not
+/// empty, the tree internally keeps a subtree for each rule after a chance event, and the
+/// navigation methods follow the subtree where no rule matches.
 #[derive(Default)]
 pub struct ActionTree {
     config: TreeConfig,
@@ -163,6 +284,8 @@ struct BuildTreeInfo {
     oop_call_flag: bool,
     stack: [i32; 2],
     prev_amount: i32,
+    depth: u32,
+    size_rule: Option<usize>,
 }
 
 type EjectedActionTree = (
@@ -383,6 +506,12 @@ impl ActionTree {
         (self.config, self.added_lines, self.removed_lines, self.root)
     }
 
+    /// Returns the reference to the root node.
+    #[inline]
+    pub(crate) fn root(&self) -> MutexGuardLike<'_, ActionTreeNode> {
+        self.root.lock()
+    }
+
     /// Returns the reference to the current node.
     #[inline]
     fn current_node(&self) -> &ActionTreeNode {
@@ -428,6 +557,17 @@ impl ActionTree {
             ));
         }
 
+        if config.big_blind < 0 {
+            return Err(format!(
+                "Big blind must be non-negative: {}",
+                config.big_blind
+            ));
+        }
+
+        if config.big_blind == 0 && Self::uses_big_blinds(config) {
+            return Err("Big blind must be positive to use big blind bet sizes".to_string());
+        }
+
         if config.rake_rate < 0.0 {
             return Err(format!(
                 "Rake rate must be non-negative: {}",
@@ -470,6 +610,44 @@ impl ActionTree {
             ));
         }
 
+        if config.max_bets_per_street == Some(0) {
+            return Err("Maximum number of bets per street must be positive: 0".to_string());
+        }
+
+        if let MinRaise::Ratio(ratio) = config.min_raise {
+            if !(ratio > 0.0 && ratio.is_finite()) {
+                return Err(format!("Minimum raise ratio must be positive: {ratio}"));
+            }
+        }
+
+        match config.depth_limit {
+            Some(DepthLimit::Street(street)) if street < config.initial_state => {
+                return Err(format!(
+                    "Depth limit must not be before the initial state: {street:?}"
+                ));
+            }
+            Some(DepthLimit::Actions(0)) => {
+                return Err("Depth limit must be positive: 0".to_string());
+            }
+            _ => {}
+        }
+
+        if config.board_bet_sizes.len() >= u8::MAX as usize {
+            return Err(format!(
+                "Too many board bet size rules: {}",
+                config.board_bet_sizes.len()
+            ));
+        }
+
+        for rule in &config.board_bet_sizes {
+            if rule.street <= config.initial_state {
+                return Err(format!(
+                    "Board bet size rule must be after the initial state: {:?}",
+                    rule.street
+                ));
+            }
+        }
+
         Ok(())
     }
 
@@ -484,6 +662,10 @@ impl ActionTree {
 
     /// Recursively builds the action tree.
     fn build_tree_recursive(&self, node: &mut ActionTreeNode, info: BuildTreeInfo) {
+        if !node.is_terminal() && self.is_depth_limited(node, &info) {
+            node.player = PLAYER_LEAF_FLAG;
+        }
+
         if node.is_terminal() {
             // do nothing
         } else if node.is_chance() {
@@ -499,18 +681,32 @@ impl ActionTree {
                 (true, _) => PLAYER_TERMINAL_FLAG,
             };
 
+            // `Chance(0)` is the default subtree and `Chance(i + 1)` is the subtree of the `i`-th
+            // board bet size rule
             node.actions.push(Action::Chance(0));
-            node.children.push(MutexLike::new(ActionTreeNode {
-                player: next_player,
-                board_state: next_state,
-                amount: node.amount,
-                ..Default::default()
-            }));
+            if next_player == PLAYER_OOP {
+                for (i, rule) in self.config.board_bet_sizes.iter().enumerate() {
+                    if rule.street == next_state {
+                        node.actions.push(Action::Chance(i as Card + 1));
+                    }
+                }
+            }
 
-            self.build_tree_recursive(
-                &mut node.children[0].lock(),
-                info.create_next(0, Action::Chance(0)),
-            );
+            for &action in &node.actions {
+                node.children.push(MutexLike::new(ActionTreeNode {
+                    player: next_player,
+                    board_state: next_state,
+                    amount: node.amount,
+                    ..Default::default()
+                }));
+                self.build_tree_recursive(
+                    &mut node.children.last().unwrap().lock(),
+                    info.create_next(0, action),
+                );
+            }
+
+            node.actions.shrink_to_fit();
+            node.children.shrink_to_fit();
         } else {
             self.push_actions(node, &info);
             for (action, child) in node.actions.iter().zip(node.children.iter()) {
@@ -522,6 +718,59 @@ impl ActionTree {
         }
     }
 
+    /// Returns whether the given node should be a leaf node due to the depth limit.
+    #[inline]
+    fn is_depth_limited(&self, node: &ActionTreeNode, info: &BuildTreeInfo) -> bool {
+        match self.config.depth_limit {
+            Some(DepthLimit::Street(street)) => node.is_chance() && node.board_state >= street,
+            Some(DepthLimit::Actions(depth)) => info.depth >= depth,
+            None => false,
+        }
+    }
+
+    /// Returns whether any bet size option of the configuration is in big blinds.
+    fn uses_big_blinds(config: &TreeConfig) -> bool {
+        let is_big_blinds = |size: &BetSize| matches!(size, BetSize::BigBlinds(_));
+        let uses_in_options = |options: &BetSizeOptions| {
+            let raise_sizes = options.reraise.iter().flatten();
+            options
+                .bet
+                .iter()
+                .chain(&options.raise)
+                .chain(raise_sizes)
+                .any(is_big_blinds)
+        };
+        let uses_in_donk = |donk: &Option<DonkSizeOptions>| match donk {
+            Some(donk) => donk.donk.iter().any(is_big_blinds),
+            None => false,
+        };
+
+        let bet_sizes = [
+            &config.flop_bet_sizes,
+            &config.turn_bet_sizes,
+            &config.river_bet_sizes,
+        ];
+
+        bet_sizes.into_iter().flatten().any(uses_in_options)
+            || uses_in_donk(&config.turn_donk_sizes)
+            || uses_in_donk(&config.river_donk_sizes)
+            || config.board_bet_sizes.iter().any(|rule| {
+                rule.bet_sizes.iter().any(uses_in_options) || uses_in_donk(&rule.donk_sizes)
+            })
+    }
+
+    /// Returns the minimum bet or raise amount (before clamping to the stack), where `to_call` is
+    /// the previous bet or raise increment.
+    #[inline]
+    fn min_amount(&self, prev_amount: i32, to_call: i32) -> i32 {
+        match self.config.min_raise {
+            MinRaise::FullRaise => prev_amount + to_call,
+            MinRaise::Ratio(ratio) => {
+                prev_amount + ((to_call as f64 * ratio).round() as i32).max(1)
+            }
+        }
+    }
+
     /// Pushes all possible actions to the given node.
     fn push_actions(&self, node: &mut ActionTreeNode, info: &BuildTreeInfo) {
         let player = node.player;
@@ -534,7 +783,8 @@ impl ActionTree {
 
         let pot = self.config.starting_pot + 2 * (node.amount + to_call);
         let max_amount = opponent_stack + prev_amount;
-        let min_amount = (prev_amount + to_call).clamp(1, max_amount);
+        let full_min_amount = self.min_amount(prev_amount, to_call);
+        let min_amount = full_min_amount.clamp(1, max_amount);
 
         let spr_after_call = opponent_stack as f64 / pot as f64;
         let compute_geometric = |num_streets: i32, max_ratio: f64| {
@@ -542,6 +792,14 @@ impl ActionTree {
             (pot as f64 * ratio.min(max_ratio)).round() as i32
         };
 
+        let compute_big_blinds =
+            |num_bbs: f64| (self.config.big_blind as f64 * num_bbs).round() as i32;
+
+        // solves `(opponent_stack - amount) / (pot + 2 * amount) = spr` for `amount`
+        let compute_target_spr = |spr: f64| {
+            ((opponent_stack as f64 - spr * pot as f64) / (1.0 + 2.0 * spr)).round() as i32
+        };
+
         let (bet_options, donk_options, num_remaining_streets) = match node.board_state {
             BoardState::Flop => (&self.config.flop_bet_sizes, &None, 3),
             BoardState::Turn => (&self.config.turn_bet_sizes, &self.config.turn_donk_sizes, 2),
@@ -552,6 +810,14 @@ impl ActionTree {
             ),
         };
 
+        let (bet_options, donk_options) = match info.size_rule {
+            Some(rule) => {
+                let rule = &self.config.board_bet_sizes[rule];
+                (&rule.bet_sizes, &rule.donk_sizes)
+            }
+            None => (bet_options, donk_options),
+        };
+
         let mut actions = Vec::new();
 
         if donk_options.is_some()
@@ -578,6 +844,12 @@ impl ActionTree {
                         let amount = compute_geometric(num_streets, max_ratio);
                         actions.push(Action::Bet(amount));
                     }
+                    BetSize::BigBlinds(num_bbs) => {
+                        actions.push(Action::Bet(compute_big_blinds(num_bbs)));
+                    }
+                    BetSize::TargetSpr(spr) => {
+                        actions.push(Action::Bet(compute_target_spr(spr)));
+                    }
                     BetSize::AllIn => actions.push(Action::AllIn(max_amount)),
                 }
             }
@@ -610,6 +882,12 @@ impl ActionTree {
                         let amount = compute_geometric(num_streets, max_ratio);
                         actions.push(Action::Bet(amount));
                     }
+                    BetSize::BigBlinds(num_bbs) => {
+                        actions.push(Action::Bet(compute_big_blinds(num_bbs)));
+                    }
+                    BetSize::TargetSpr(spr) => {
+                        actions.push(Action::Bet(compute_target_spr(spr)));
+                    }
                     BetSize::AllIn => actions.push(Action::AllIn(max_amount)),
                 }
             }
@@ -625,9 +903,18 @@ impl ActionTree {
             // call
             actions.push(Action::Call);
 
-            if !info.allin_flag {
+            // `num_bets` is the number of previous bets and raises, i.e., the raise depth
+            let options = &bet_options[player as usize];
+            let depth = info.num_bets as usize;
+
+            let is_capped = match self.config.max_bets_per_street {
+                Some(max_bets) => info.num_bets as u32 >= max_bets,
+                None => false,
+            };
+
+            if !info.allin_flag && !is_capped && options.is_raise_allowed(depth) {
                 // raise
-                for &bet_size in &bet_options[player as usize].raise {
+                for &bet_size in options.raise_sizes(depth) {
                     match bet_size {
                         BetSize::PotRelative(ratio) => {
                             let amount = prev_amount + (pot as f64 * ratio).round() as i32;
@@ -650,6 +937,14 @@ impl ActionTree {
                             let amount = compute_geometric(num_streets, max_ratio);
                             actions.push(Action::Raise(prev_amount + amount));
                         }
+                        BetSize::BigBlinds(num_bbs) => {
+                            let amount = compute_big_blinds(num_bbs);
+                            actions.push(Action::Raise(prev_amount + amount));
+                        }
+                        BetSize::TargetSpr(spr) => {
+                            let amount = compute_target_spr(spr);
+                            actions.push(Action::Raise(prev_amount + amount));
+                        }
                         BetSize::AllIn => actions.push(Action::AllIn(max_amount)),
                     }
                 }
@@ -692,6 +987,12 @@ impl ActionTree {
             }
         }
 
+        // remove incomplete all-in raises
+        let is_incomplete = prev_amount > 0 && max_amount < full_min_amount;
+        if is_incomplete && self.config.incomplete_raise == IncompleteRaise::Disallow {
+            actions.retain(|action| !matches!(action, Action::AllIn(_)));
+        }
+
         // remove duplicates
         actions.sort_unstable();
         actions.dedup();
@@ -748,9 +1049,13 @@ impl ActionTree {
         if node.is_terminal() {
             // do nothing
         } else if node.children.is_empty() {
-            result.push(line.clone());
+            if !result.contains(line) {
+                result.push(line.clone());
+            }
         } else if node.is_chance() {
-            Self::invalid_terminals_recursive(&node.children[0].lock(), result, line)
+            for child in &node.children {
+                Self::invalid_terminals_recursive(&child.lock(), result, line);
+            }
         } else {
             for (&action, child) in node.actions.iter().zip(node.children.iter()) {
                 line.push(action);
@@ -777,12 +1082,20 @@ impl ActionTree {
         }
 
         if node.is_chance() {
-            return self.add_line_recursive(
-                &mut node.children[0].lock(),
-                line,
-                was_removed,
-                info.create_next(0, Action::Chance(0)),
-            );
+            // succeeds if the line can be added to any of the subtrees
+            let results = node
+                .actions
+                .iter()
+                .zip(node.children.iter())
+                .map(|(&action, child)| {
+                    let next_info = info.create_next(0, action);
+                    self.add_line_recursive(&mut child.lock(), line, was_removed, next_info)
+                })
+                .collect::<Vec<_>>();
+            if results.iter().any(Result::is_ok) {
+                return Ok(results.iter().any(|result| result == &Ok(true)));
+            }
+            return results.into_iter().next().unwrap();
         }
 
         let action = line[0];
@@ -819,7 +1132,7 @@ impl ActionTree {
         let to_call = player_stack - opponent_stack;
 
         let max_amount = opponent_stack + prev_amount;
-        let min_amount = (prev_amount + to_call).clamp(1, max_amount);
+        let min_amount = self.min_amount(prev_amount, to_call).clamp(1, max_amount);
 
         let mut is_replaced = false;
         let action = match action {
@@ -921,7 +1234,16 @@ impl ActionTree {
         }
 
         if node.is_chance() {
-            return Self::remove_line_recursive(&mut node.children[0].lock(), line);
+            // succeeds if the line can be removed from any of the subtrees
+            let results = node
+                .children
+                .iter()
+                .map(|child| Self::remove_line_recursive(&mut child.lock(), line))
+                .collect::<Vec<_>>();
+            if results.iter().any(Result::is_ok) {
+                return Ok(());
+            }
+            return results.into_iter().next().unwrap();
         }
 
         let action = line[0];
@@ -975,14 +1297,116 @@ impl ActionTree {
     }
 }
 
+/// Attempts to convert a line (list of actions) into a string.
+///
+/// Each action is converted into a token: `F` (fold), `X` (check), `C` (call), `B<amount>` (bet),
+/// `R<amount>` (raise), `A<amount>` (all-in), or the card string (chance). Chance actions are
+/// surrounded by `|` to separate streets.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// let qs = card_from_str("Qs").unwrap();
+/// let line = [
+///     Action::Check,
+///     Action::Bet(120),
+///     Action::Call,
+///     Action::Chance(qs),
+///     Action::Bet(300),
+///     Action::Raise(900),
+/// ];
+/// assert_eq!(line_to_string(&line), Ok("X B120 C | Qs | B300 R900".to_string()));
+/// assert_eq!(line_from_str("X B120 C | Qs | B300 R900"), Ok(line.to_vec()));
+/// ```
+pub fn line_to_string(line: &[Action]) -> Result<String, String> {
+    let mut tokens = Vec::with_capacity(line.len());
+
+    for &action in line {
+        match action {
+            Action::None => return Err("Invalid action: None".to_string()),
+            Action::Fold => tokens.push("F".to_string()),
+            Action::Check => tokens.push("X".to_string()),
+            Action::Call => tokens.push("C".to_string()),
+            Action::Bet(amount) => tokens.push(format!("B{amount}")),
+            Action::Raise(amount) => tokens.push(format!("R{amount}")),
+            Action::AllIn(amount) => tokens.push(format!("A{amount}")),
+            Action::Chance(card) => {
+                if !tokens.is_empty() && tokens.last().unwrap() != "|" {
+                    tokens.push("|".to_string());
+                }
+                tokens.push(card_to_string(card)?);
+                tokens.push("|".to_string());
+            }
+        }
+    }
+
+    if tokens.last().is_some_and(|t| t == "|") {
+        tokens.pop();
+    }
+
+    Ok(tokens.join(" "))
+}
+
+/// Attempts to convert a string into a line (list of actions).
+///
+/// The tokens are separated by whitespace and interpreted in the same way as [`line_to_string`].
+/// `|` is optional and ignored.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// let line = line_from_str("X B120 R360 A970 C | 2h").unwrap();
+/// assert_eq!(line[3], Action::AllIn(970));
+/// assert_eq!(line[5], Action::Chance(card_from_str("2h").unwrap()));
+/// assert!(line_from_str("X B").is_err());
+/// ```
+pub fn line_from_str(s: &str) -> Result<Vec<Action>, String> {
+    let mut line = Vec::new();
+
+    for token in s.split_whitespace().filter(|&t| t != "|") {
+        let parse_amount = |amount: &str| {
+            amount
+                .parse::<i32>()
+                .ok()
+                .filter(|&amount| amount > 0)
+                .ok_or_else(|| format!("Invalid amount: {token}"))
+        };
+
+        let is_all_in = token.starts_with('A') && card_from_str(token).is_err();
+
+        let action = if let Some(amount) = token.strip_prefix('B') {
+            Action::Bet(parse_amount(amount)?)
+        } else if let Some(amount) = token.strip_prefix('R') {
+            Action::Raise(parse_amount(amount)?)
+        } else if is_all_in {
+            Action::AllIn(parse_amount(&token[1..])?)
+        } else {
+            match token {
+                "F" => Action::Fold,
+                "X" => Action::Check,
+                "C" => Action::Call,
+                _ => Action::Chance(
+                    card_from_str(token).map_err(|_| format!("Invalid token: {token}"))?,
+                ),
+            }
+        };
+
+        line.push(action);
+    }
+
+    Ok(line)
+}
+
 impl ActionTreeNode {
     #[inline]
-    fn is_terminal(&self) -> bool {
+    pub(crate) fn is_terminal(&self) -> bool {
         self.player & PLAYER_TERMINAL_FLAG != 0
     }
 
     #[inline]
-    fn is_chance(&self) -> bool {
+    pub(crate) fn is_chance(&self) -> bool {
         self.player & PLAYER_CHANCE_FLAG != 0
     }
 }
@@ -997,6 +1421,8 @@ impl BuildTreeInfo {
             oop_call_flag: false,
             stack: [stack, stack],
             prev_amount: 0,
+            depth: 0,
+            size_rule: None,
         }
     }
 
@@ -1007,6 +1433,12 @@ impl BuildTreeInfo {
         let mut oop_call_flag = self.oop_call_flag;
         let mut stack = self.stack;
         let mut prev_amount = self.prev_amount;
+        let mut depth = self.depth;
+        let mut size_rule = self.size_rule;
+
+        if !matches!(action, Action::Chance(_)) {
+            depth += 1;
+        }
 
         match action {
             Action::Check => {
@@ -1025,6 +1457,9 @@ impl BuildTreeInfo {
                 stack[player as usize] -= amount - prev_amount + to_call;
                 prev_amount = amount;
             }
+            Action::Chance(rule) => {
+                size_rule = (rule as usize).checked_sub(1);
+            }
             _ => {}
         }
 
@@ -1035,19 +1470,25 @@ impl BuildTreeInfo {
             oop_call_flag,
             stack,
             prev_amount,
+            depth,
+            size_rule,
         }
     }
 }
 
-/// Returns the number of action nodes of [flop, turn, river].
+impl BoardBetSizes {
+    /// Returns whether `board` has all the textures of the rule.
+    #[inline]
+    pub fn matches(&self, board: &[Card]) -> bool {
+        self.textures.iter().all(|texture| texture.matches(board))
+    }
+}
+
+/// Returns the number of action nodes of [flop, turn, river], including the subtrees of the board
+/// bet size rules.
 pub(crate) fn count_num_action_nodes(node: &ActionTreeNode) -> [u64; 3] {
     let mut ret = [0, 0, 0];
-    count_num_action_nodes_recursive(node, 0, &mut ret);
-    if ret[1] == 0 {
-        ret = [0, 0, ret[0]];
-    } else if ret[2] == 0 {
-        ret = [0, ret[0], ret[1]];
-    }
+    count_num_action_nodes_recursive(node, node.board_state as usize, &mut ret);
     ret
 }
 
@@ -1055,11 +1496,10 @@ fn count_num_action_nodes_recursive(node: &ActionTreeNode, street: usize, count:
     count[street] += 1;
     if node.is_terminal() {
         // do nothing
-    } else if node.is_chance() {
-        count_num_action_nodes_recursive(&node.children[0].lock(), street + 1, count);
     } else {
+        let next_street = street + node.is_chance() as usize;
         for child in &node.children {
-            count_num_action_nodes_recursive(&child.lock(), street, count);
+            count_num_action_nodes_recursive(&child.lock(), next_street, count);
         }
     }
 }
@@ -1,10 +1,12 @@
+use std::fmt;
+
 #[cfg(feature = "bincode")]
 use bincode::{Decode, Encode};
 
 /// Bet size options for the first bets and raises.
 ///
 /// In the `try_from()` method, multiple bet sizes can be specified using a comma-separated string.
-/// Each element must be a string ending in one of the following characters: %, x, c, r, e, a.
+/// Each element must be a string ending in one of the following: %, x, c, r, e, bb, spr, a.
 ///
 /// - %: Percentage of the pot. (e.g., "70%")
 /// - x: Multiple of the previous bet. Valid for only raises. (e.g., "2.5x")
@@ -16,8 +18,22 @@ use bincode::{Decode, Encode};
 ///   - Xe: The geometric size with X streets remaining. X must be a positive integer. (e.g., "2e")
 ///   - XeY%: Same as Xe, but the maximum size is Y% of the pot. (e.g., "3e200%")
 ///   - If specified for raises, the number of previous raises is subtracted from X.
+/// - bb: Constant value in big blinds ([`TreeConfig::big_blind`] must be set). Must be positive.
+///   For raises, the value is added to the previous bet like "c". (e.g., "2.5bb")
+/// - spr: The size that leaves the specified stack-to-pot ratio after the opponent's call. Must be
+///   non-negative. (e.g., "1spr")
 /// - a: All-in. (e.g., "a")
 ///
+/// [`TreeConfig::big_blind`]: crate::TreeConfig::big_blind
+///
+/// The raise string accepts two additional forms:
+///
+/// - `|` separates the sizes by raise depth: the first group is used for the first raise of the
+///   street, the second group for the re-raise, and so on. The last group is also used for deeper
+///   raises. (e.g., "2.5x | 2x | a")
+/// - r: Maximum number of raises per street, including all-in raises. Must be a non-negative
+///   integer. (e.g., "3r")
+///
 /// # Examples
 /// ```
 /// use postflop_solver::BetSize::*;
@@ -36,6 +52,13 @@ use bincode::{Decode, Encode};
 /// );
 ///
 /// assert_eq!(bet_size.raise, vec![PrevBetRelative(2.5)]);
+///
+/// let bet_size = BetSizeOptions::try_from(("50%", "3x | 2.2x, a | a, 3r")).unwrap();
+///
+/// assert_eq!(bet_size.raise_sizes(1), &[PrevBetRelative(3.0)]);
+/// assert_eq!(bet_size.raise_sizes(2), &[PrevBetRelative(2.2), AllIn]);
+/// assert_eq!(bet_size.raise_sizes(4), &[AllIn]);
+/// assert_eq!(bet_size.max_raises, Some(3));
 /// ```
 #[derive(Debug, Clone, Default, PartialEq)]
 #[cfg_attr(feature = "bincode", derive(Decode, Encode))]
@@ -45,6 +68,21 @@ pub struct BetSizeOptions {
 
     /// Bet size options for raise.
     pub raise: Vec<BetSize>,
+
+    /// Bet size options for re-raises: the `i`-th element is used for the `(i + 2)`-th raise of the
+    /// street, and the last element is also used for deeper raises. If empty, `raise` is used for
+    /// all raises.
+    pub reraise: Vec<Vec<BetSize>>,
+
+    /// Maximum number of raises per street (set `None` for no limit). The raises of both players
+    /// are counted, but only the options of the acting player are checked.
+    ///
+    /// This is checked independently of [`TreeConfig::max_bets_per_street`], which counts the
+    /// first bet as well, so the stricter limit takes effect: `Some(n)` corresponds to the
+    /// `max_bets_per_street` of `n + 1` for these options only.
+    ///
+    /// [`TreeConfig::max_bets_per_street`]: crate::TreeConfig::max_bets_per_street
+    pub max_raises: Option<u32>,
 }
 
 /// Bet size options for the donk bets.
@@ -76,6 +114,18 @@ pub enum BetSize {
     /// If `i32 == 0`, the number of streets is as follows: flop = 3, turn = 2, river = 1.
     Geometric(i32, f64),
 
+    /// Constant bet size in big blinds (see [`TreeConfig::big_blind`]).
+    ///
+    /// For raise actions, the size is added to the previous bet.
+    ///
+    /// [`TreeConfig::big_blind`]: crate::TreeConfig::big_blind
+    BigBlinds(f64),
+
+    /// Bet size that leaves the specified stack-to-pot ratio after the opponent's call.
+    ///
+    /// If the stack-to-pot ratio is already below the target, the minimum size is used.
+    TargetSpr(f64),
+
     /// Bet size representing all-in.
     AllIn,
 }
@@ -88,31 +138,82 @@ impl TryFrom<(&str, &str)> for BetSizeOptions {
     /// See the [`BetSizeOptions`] struct for the description and examples.
     fn try_from((bet_str, raise_str): (&str, &str)) -> Result<Self, Self::Error> {
         let mut bet_sizes = bet_str.split(',').map(str::trim).collect::<Vec<_>>();
-        let mut raise_sizes = raise_str.split(',').map(str::trim).collect::<Vec<_>>();
 
         if bet_sizes.last().unwrap().is_empty() {
             bet_sizes.pop();
         }
 
-        if raise_sizes.last().unwrap().is_empty() {
-            raise_sizes.pop();
-        }
-
         let mut bet = Vec::new();
-        let mut raise = Vec::new();
+        let mut raise_groups = Vec::new();
+        let mut max_raises = None;
 
         for bet_size in bet_sizes {
             bet.push(bet_size_from_str(bet_size, false)?);
         }
 
-        for raise_size in raise_sizes {
-            raise.push(bet_size_from_str(raise_size, true)?);
+        let num_groups = raise_str.split('|').count();
+        for group_str in raise_str.split('|') {
+            let mut raise_sizes = group_str.split(',').map(str::trim).collect::<Vec<_>>();
+
+            if raise_sizes.last().unwrap().is_empty() {
+                raise_sizes.pop();
+            }
+
+            let mut raise = Vec::new();
+
+            for raise_size in raise_sizes {
+                if let Some(value) = max_raises_from_str(raise_size) {
+                    if max_raises.is_some() {
+                        return Err(format!("Duplicate maximum number of raises: {raise_size}"));
+                    }
+                    max_raises = Some(value?);
+                } else {
+                    raise.push(bet_size_from_str(raise_size, true)?);
+                }
+            }
+
+            if raise.is_empty() && num_groups > 1 {
+                return Err(format!("Empty raise sizes in: {raise_str}"));
+            }
+
+            raise.sort_unstable_by(|l, r| l.partial_cmp(r).unwrap());
+            raise_groups.push(raise);
         }
 
         bet.sort_unstable_by(|l, r| l.partial_cmp(r).unwrap());
-        raise.sort_unstable_by(|l, r| l.partial_cmp(r).unwrap());
 
-        Ok(BetSizeOptions { bet, raise })
+        let mut raise_groups = raise_groups.into_iter();
+        let raise = raise_groups.next().unwrap();
+        let reraise = raise_groups.collect();
+
+        Ok(BetSizeOptions {
+            bet,
+            raise,
+            reraise,
+            max_raises,
+        })
+    }
+}
+
+impl BetSizeOptions {
+    /// Returns the raise size options for the `depth`-th raise of the street (`depth >= 1`).
+    #[inline]
+    pub fn raise_sizes(&self, depth: usize) -> &[BetSize] {
+        match depth {
+            0 | 1 => &self.raise,
+            _ if self.reraise.is_empty() => &self.raise,
+            _ => &self.reraise[(depth - 2).min(self.reraise.len() - 1)],
+        }
+    }
+
+    /// Returns whether the `depth`-th raise of the street (`depth >= 1`) is allowed by
+    /// `max_raises`.
+    #[inline]
+    pub fn is_raise_allowed(&self, depth: usize) -> bool {
+        match self.max_raises {
+            Some(max_raises) => depth <= max_raises as usize,
+            None => true,
+        }
     }
 }
 
@@ -141,6 +242,36 @@ impl TryFrom<&str> for DonkSizeOptions {
     }
 }
 
+impl fmt::Display for BetSize {
+    /// Formats the bet size in the same syntax as accepted by the `try_from()` methods.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            BetSize::PotRelative(ratio) => write!(f, "{}%", format_float(ratio * 100.0)),
+            BetSize::PrevBetRelative(ratio) => write!(f, "{}x", format_float(ratio)),
+            BetSize::Additive(adder, 0) => write!(f, "{adder}c"),
+            BetSize::Additive(adder, raise_cap) => write!(f, "{adder}c{raise_cap}r"),
+            BetSize::Geometric(num_streets, max_ratio) => {
+                if num_streets != 0 {
+                    write!(f, "{num_streets}")?;
+                }
+                write!(f, "e")?;
+                if max_ratio != f64::INFINITY {
+                    write!(f, "{}%", format_float(max_ratio * 100.0))?;
+                }
+                Ok(())
+            }
+            BetSize::BigBlinds(num_bbs) => write!(f, "{}bb", format_float(num_bbs)),
+            BetSize::TargetSpr(spr) => write!(f, "{}spr", format_float(spr)),
+            BetSize::AllIn => write!(f, "a"),
+        }
+    }
+}
+
+/// Formats a float with at most 6 decimal places (to hide rounding errors of `* 100.0`).
+fn format_float(x: f64) -> f64 {
+    (x * 1e6).round() / 1e6
+}
+
 fn parse_float(s: &str) -> Option<f64> {
     if s.contains('+') || s.contains('-') || s.contains(|c: char| c.is_ascii_alphabetic()) {
         None
@@ -149,11 +280,42 @@ fn parse_float(s: &str) -> Option<f64> {
     }
 }
 
+/// Parses the maximum number of raises (e.g., "3r"). Returns `None` if `s` is not of this form.
+fn max_raises_from_str(s: &str) -> Option<Result<u32, String>> {
+    let s_lower = s.to_lowercase();
+    let value_str = s_lower.strip_suffix('r')?;
+    if value_str.contains(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let float = parse_float(value_str);
+    Some(match float {
+        Some(float) if float.trunc() == float && float <= 100.0 => Ok(float as u32),
+        Some(_) => Err(format!(
+            "Maximum number of raises must be an integer between 0 and 100: {s}"
+        )),
+        None => Err(format!("Invalid maximum number of raises: {s}")),
+    })
+}
+
 fn bet_size_from_str(s: &str, is_raise: bool) -> Result<BetSize, String> {
     let s_lower = s.to_lowercase();
     let err_msg = format!("Invalid bet size: {s}");
 
-    if let Some(prev_bet_rel) = s_lower.strip_suffix('x') {
+    if let Some(num_bbs) = s_lower.strip_suffix("bb") {
+        // Big blinds
+        let float = parse_float(num_bbs).ok_or(&err_msg)?;
+        if float <= 0.0 {
+            let err_msg = format!("Number of big blinds must be positive: {s}");
+            Err(err_msg)
+        } else {
+            Ok(BetSize::BigBlinds(float))
+        }
+    } else if let Some(spr) = s_lower.strip_suffix("spr") {
+        // Target SPR
+        let float = parse_float(spr).ok_or(&err_msg)?;
+        Ok(BetSize::TargetSpr(float))
+    } else if let Some(prev_bet_rel) = s_lower.strip_suffix('x') {
         // Previous bet relative
         if !is_raise {
             let err_msg = format!("Relative size to the previous bet is not allowed: {s}");
@@ -272,6 +434,10 @@ mod tests {
             ("2e", Geometric(2, f64::INFINITY)),
             ("E37.5%", Geometric(0, 0.375)),
             ("100e.5%", Geometric(100, 0.005)),
+            ("2.5bb", BigBlinds(2.5)),
+            ("10BB", BigBlinds(10.0)),
+            ("0spr", TargetSpr(0.0)),
+            ("1.5SPR", TargetSpr(1.5)),
             ("a", AllIn),
             ("A", AllIn),
         ];
@@ -283,7 +449,7 @@ mod tests {
         let error_tests = [
             "", "0", "1.23", "%", "+42%", "-30%", "x", "0x", "1x", "c", "12.3c", "10c10", "42cr",
             "c3r", "0c0r", "123c101r", "1c2r3", "12c3.4r", "0e", "2.7e", "101e", "3e7", "E%",
-            "1e2e3", "bet", "1a", "a1",
+            "1e2e3", "bet", "1a", "a1", "bb", "0bb", "-1bb", "1b", "spr", "-1spr", "1sp", "1bbspr",
         ];
 
         for s in error_tests {
@@ -291,6 +457,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_bet_size_to_string() {
+        let tests = [
+            (PotRelative(0.75), "75%"),
+            (PotRelative(1.125), "112.5%"),
+            (PotRelative(1.1), "110%"),
+            (PrevBetRelative(2.5), "2.5x"),
+            (Additive(123, 0), "123c"),
+            (Additive(100, 3), "100c3r"),
+            (Geometric(0, f64::INFINITY), "e"),
+            (Geometric(2, f64::INFINITY), "2e"),
+            (Geometric(0, 0.375), "e37.5%"),
+            (Geometric(3, 2.0), "3e200%"),
+            (BigBlinds(2.5), "2.5bb"),
+            (TargetSpr(1.0), "1spr"),
+            (AllIn, "a"),
+        ];
+
+        for (bet_size, expected) in tests {
+            assert_eq!(bet_size.to_string(), expected);
+            assert_eq!(bet_size_from_str(expected, true), Ok(bet_size));
+        }
+    }
+
     #[test]
     fn test_bet_sizes_from_str() {
         let tests = [
@@ -299,7 +489,7 @@ mod tests {
                 "",
                 BetSizeOptions {
                     bet: vec![PotRelative(0.4), PotRelative(0.7)],
-                    raise: Vec::new(),
+                    ..Default::default()
                 },
             ),
             (
@@ -308,6 +498,45 @@ mod tests {
                 BetSizeOptions {
                     bet: vec![Additive(50, 0), Geometric(0, f64::INFINITY), AllIn],
                     raise: vec![PotRelative(0.25), PrevBetRelative(2.5), Geometric(0, 2.0)],
+                    ..Default::default()
+                },
+            ),
+            (
+                "50%",
+                "3x | a, 2x | 20c3r",
+                BetSizeOptions {
+                    bet: vec![PotRelative(0.5)],
+                    raise: vec![PrevBetRelative(3.0)],
+                    reraise: vec![vec![PrevBetRelative(2.0), AllIn], vec![Additive(20, 3)]],
+                    max_raises: None,
+                },
+            ),
+            (
+                "",
+                "0R",
+                BetSizeOptions {
+                    max_raises: Some(0),
+                    ..Default::default()
+                },
+            ),
+            (
+                "3bb, 0.5spr",
+                "1spr, 2r",
+                BetSizeOptions {
+                    bet: vec![BigBlinds(3.0), TargetSpr(0.5)],
+                    raise: vec![TargetSpr(1.0)],
+                    max_raises: Some(2),
+                    ..Default::default()
+                },
+            ),
+            (
+                "",
+                "2.5x, 4r | 60%",
+                BetSizeOptions {
+                    raise: vec![PrevBetRelative(2.5)],
+                    reraise: vec![vec![PotRelative(0.6)]],
+                    max_raises: Some(4),
+                    ..Default::default()
                 },
             ),
         ];
@@ -316,7 +545,18 @@ mod tests {
             assert_eq!((bet, raise).try_into(), Ok(expected));
         }
 
-        let error_tests = [("2.5x", ""), (",", "")];
+        let error_tests = [
+            ("2.5x", ""),
+            (",", ""),
+            ("3r", ""),
+            ("50% | 75%", ""),
+            ("", "2x |"),
+            ("", "| 2x"),
+            ("", "2r, 3r"),
+            ("", "1.5r"),
+            ("", "101r"),
+            ("", "r"),
+        ];
 
         for (bet, raise) in error_tests {
             assert!(BetSizeOptions::try_from((bet, raise)).is_err());
@@ -1,6 +1,7 @@
 use crate::hand::*;
 use crate::range::*;
 use std::mem;
+use std::str::FromStr;
 
 #[cfg(feature = "bincode")]
 use bincode::{Decode, Encode};
@@ -432,6 +433,101 @@ impl CardConfig {
     }
 }
 
+/// An enum representing a texture of the board, used by [`OpponentModel`] and [`BoardBetSizes`].
+///
+/// The texture is evaluated on the board of the node (i.e., including the turn and river cards if
+/// dealt). Every texture is invariant under the permutation of suits.
+///
+/// [`OpponentModel`]: crate::OpponentModel
+/// [`BoardBetSizes`]: crate::BoardBetSizes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "bincode", derive(Decode, Encode))]
+pub enum BoardTexture {
+    /// All cards have different suits (`rainbow`).
+    Rainbow,
+
+    /// The most frequent suit appears exactly twice (`twotone`).
+    TwoTone,
+
+    /// At least three cards have the same suit, i.e., a flush is possible (`monotone`).
+    Monotone,
+
+    /// At least two cards have the same rank (`paired`).
+    Paired,
+
+    /// All cards have different ranks (`unpaired`).
+    Unpaired,
+
+    /// A straight is possible (`connected`).
+    Connected,
+
+    /// A straight is not possible (`disconnected`).
+    Disconnected,
+
+    /// The highest card is an ace (`acehigh`).
+    AceHigh,
+
+    /// The highest card is a king, queen, jack, or ten (`broadwayhigh`).
+    BroadwayHigh,
+
+    /// The highest card is nine or lower (`lowhigh`).
+    LowHigh,
+}
+
+impl BoardTexture {
+    /// Returns whether `board` has the texture.
+    pub fn matches(&self, board: &[Card]) -> bool {
+        let mut suit_count = [0; 4];
+        let mut rank_count = [0; 13];
+        for &card in board {
+            suit_count[card as usize & 3] += 1;
+            rank_count[card as usize >> 2] += 1;
+        }
+
+        let max_suit = suit_count.into_iter().max().unwrap_or(0);
+        let is_paired = rank_count.iter().any(|&c| c >= 2);
+        let top_rank = rank_count.iter().rposition(|&c| c > 0).unwrap_or(0);
+
+        // a straight is possible if a five-rank window contains three distinct ranks
+        let rankset = (0..13).fold(0u16, |acc, r| acc | ((rank_count[r] > 0) as u16) << r);
+        let rankset = (rankset << 1) | (rankset >> 12); // ace is also below deuce
+        let is_connected = (0..10).any(|i| ((rankset >> i) & 0b11111).count_ones() >= 3);
+
+        match self {
+            Self::Rainbow => max_suit == 1,
+            Self::TwoTone => max_suit == 2,
+            Self::Monotone => max_suit >= 3,
+            Self::Paired => is_paired,
+            Self::Unpaired => !is_paired,
+            Self::Connected => is_connected,
+            Self::Disconnected => !is_connected,
+            Self::AceHigh => top_rank == 12,
+            Self::BroadwayHigh => (8..12).contains(&top_rank),
+            Self::LowHigh => top_rank < 8,
+        }
+    }
+}
+
+impl FromStr for BoardTexture {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "rainbow" => Ok(Self::Rainbow),
+            "twotone" => Ok(Self::TwoTone),
+            "monotone" => Ok(Self::Monotone),
+            "paired" => Ok(Self::Paired),
+            "unpaired" => Ok(Self::Unpaired),
+            "connected" => Ok(Self::Connected),
+            "disconnected" => Ok(Self::Disconnected),
+            "acehigh" => Ok(Self::AceHigh),
+            "broadwayhigh" => Ok(Self::BroadwayHigh),
+            "lowhigh" => Ok(Self::LowHigh),
+            _ => Err(format!("Invalid board texture: {s}")),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
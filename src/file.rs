@@ -3,7 +3,7 @@
 //  - Magic number (4 bytes): 90 57 f1 09
 //  - Version number (1 byte): 1
 //  - Compression type (1 byte): 0 (none), 1 (zstd)
-//  - Data type (1 byte): 0 (game), 1 (bunching)
+//  - Data type (1 byte): 0 (game), 1 (bunching), 2 (strategy), 3 (locks)
 //  - Estimated memory usage (`VarIntEncoding`)
 //  - Memo string
 //
@@ -24,6 +24,8 @@ const VERSION: u8 = 1;
 pub enum DataType {
     Game = 0,
     Bunching = 1,
+    Strategy = 2,
+    LockSet = 3,
 }
 
 /// A trait for data that can be saved into a file.
@@ -53,7 +55,8 @@ fn encode_into_std_write<E: Encode, W: Write>(
 ///
 /// # Arguments
 ///
-/// - `data`: The data to be saved, which is either a [`PostFlopGame`] or a [`BunchingData`].
+/// - `data`: The data to be saved, which is either a [`PostFlopGame`], a [`BunchingData`], a
+///   [`SolvedStrategy`], or a [`LockSet`].
 /// - `memo`: A memo string to be saved with the data.
 /// - `writer`: The writer to write the data into.
 /// - `compression_level`: The zstd compression level to use. If `None`, no compression is used.
@@ -123,7 +126,8 @@ pub fn save_data_into_std_write<T: FileData, W: Write>(
 ///
 /// # Arguments
 ///
-/// - `data`: The data to be saved, which is either a [`PostFlopGame`] or a [`BunchingData`].
+/// - `data`: The data to be saved, which is either a [`PostFlopGame`], a [`BunchingData`], a
+///   [`SolvedStrategy`], or a [`LockSet`].
 /// - `memo`: A memo string to be saved with the data.
 /// - `path`: The path to the file to save.
 /// - `compression_level`: The zstd compression level to use. If `None`, no compression is used.
@@ -158,8 +162,8 @@ fn decode_from_std_read<D: Decode, R: Read>(reader: &mut R, err_msg: &str) -> Re
 ///
 /// # Returns
 ///
-/// A tuple of the deserialized data (either a [`PostFlopGame`] or a [`BunchingData`]) and the memo
-/// string.
+/// A tuple of the deserialized data (either a [`PostFlopGame`], a [`BunchingData`], a
+/// [`SolvedStrategy`], or a [`LockSet`]) and the memo string.
 pub fn load_data_from_std_read<T: FileData, R: Read>(
     reader: &mut R,
     max_memory_usage: Option<u64>,
@@ -224,8 +228,8 @@ pub fn load_data_from_std_read<T: FileData, R: Read>(
 ///
 /// # Returns
 ///
-/// A tuple of the deserialized data (either a [`PostFlopGame`] or a [`BunchingData`]) and the memo
-/// string.
+/// A tuple of the deserialized data (either a [`PostFlopGame`], a [`BunchingData`], a
+/// [`SolvedStrategy`], or a [`LockSet`]) and the memo string.
 pub fn load_data_from_file<T: FileData, P: AsRef<Path>>(
     path: P,
     max_memory_usage: Option<u64>,
@@ -263,6 +267,34 @@ impl FileData for BunchingData {
     }
 }
 
+impl FileData for SolvedStrategy {
+    fn data_type() -> DataType {
+        DataType::Strategy
+    }
+
+    fn is_ready_to_save(&self) -> bool {
+        self.is_ready()
+    }
+
+    fn estimated_memory_usage(&self) -> u64 {
+        self.memory_usage()
+    }
+}
+
+impl FileData for LockSet {
+    fn data_type() -> DataType {
+        DataType::LockSet
+    }
+
+    fn is_ready_to_save(&self) -> bool {
+        true
+    }
+
+    fn estimated_memory_usage(&self) -> u64 {
+        self.memory_usage()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
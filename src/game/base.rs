@@ -12,9 +12,9 @@ struct BuildTreeInfo {
     flop_index: usize,
     turn_index: usize,
     river_index: usize,
-    num_storage: u64,
-    num_storage_ip: u64,
-    num_storage_chance: u64,
+    num_storage: [u64; 3],
+    num_storage_ip: [u64; 3],
+    num_storage_chance: [u64; 3],
 }
 
 impl Game for PostFlopGame {
@@ -43,7 +43,9 @@ impl Game for PostFlopGame {
         player: usize,
         cfreach: &[f32],
     ) {
-        if self.bunching_num_dead_cards == 0 {
+        if node.is_leaf() {
+            self.evaluate_leaf(result, node, player, cfreach);
+        } else if self.bunching_num_dead_cards == 0 {
             self.evaluate_internal(result, node, player, cfreach);
         } else {
             self.evaluate_internal_bunching(result, node, player, cfreach);
@@ -67,7 +69,7 @@ impl Game for PostFlopGame {
     #[inline]
     fn set_solved(&mut self) {
         self.state = State::Solved;
-        let history = self.action_history.clone();
+        let history = self.interpreter.action_history.clone();
         self.apply_history(&history);
     }
 
@@ -110,9 +112,23 @@ impl Game for PostFlopGame {
         }
     }
 
+    #[inline]
+    fn aggregate_constraint(&self, node: &Self::Node) -> Option<&AggregateConstraint> {
+        if self.aggregate_constraints.is_empty() {
+            None
+        } else {
+            self.aggregate_constraints.get(&self.node_index(node))
+        }
+    }
+
     #[inline]
     fn is_compression_enabled(&self) -> bool {
-        self.is_compression_enabled
+        self.compression.is_enabled()
+    }
+
+    #[inline]
+    fn storage_precision(&self, node: &Self::Node) -> StoragePrecision {
+        self.compression.precision(node.street())
     }
 }
 
@@ -158,6 +174,7 @@ impl PostFlopGame {
             self.removed_lines,
             self.action_root,
         ) = action_tree.eject();
+        self.removed_game_lines = Vec::new();
 
         self.check_card_config()?;
         self.init_card_fields();
@@ -187,6 +204,10 @@ impl PostFlopGame {
             return Err("Bunching configuration is not ready".to_string());
         }
 
+        if self.tree_config.depth_limit.is_some() {
+            return Err("Bunching effect is not supported for depth-limited trees".to_string());
+        }
+
         let mut flop_sorted = self.card_config.flop;
         flop_sorted.sort_unstable();
         if flop_sorted != bunching_data.flop() {
@@ -226,6 +247,12 @@ impl PostFlopGame {
         &self.tree_config
     }
 
+    /// Returns the root node of the action tree.
+    #[inline]
+    pub(crate) fn action_root(&self) -> MutexGuardLike<'_, ActionTreeNode> {
+        self.action_root.lock()
+    }
+
     /// Obtains the added lines.
     #[inline]
     pub fn added_lines(&self) -> &[Vec<Action>] {
@@ -238,6 +265,14 @@ impl PostFlopGame {
         &self.removed_lines
     }
 
+    /// Obtains the lines removed by [`remove_lines`] in the order of removal.
+    ///
+    /// [`remove_lines`]: #method.remove_lines
+    #[inline]
+    pub fn removed_game_lines(&self) -> &[Vec<Action>] {
+        &self.removed_game_lines
+    }
+
     /// Returns the card list of private hands of the given player.
     ///
     /// The returned list contains only card pairs with positive weight, i.e., card pairs with zero
@@ -261,13 +296,58 @@ impl PostFlopGame {
             panic!("Game is not successfully initialized");
         }
 
-        let num_elements = 2 * self.num_storage + self.num_storage_ip + self.num_storage_chance;
+        let num_storage = self.num_storage.iter().sum::<u64>();
+        let num_storage_ip = self.num_storage_ip.iter().sum::<u64>();
+        let num_storage_chance = self.num_storage_chance.iter().sum::<u64>();
+
+        let num_elements = 2 * num_storage + num_storage_ip + num_storage_chance;
         let uncompressed = 4 * num_elements + self.misc_memory_usage;
         let compressed = 2 * num_elements + self.misc_memory_usage;
 
         (uncompressed, compressed)
     }
 
+    /// Returns the estimated memory usage in bytes with the specified compression configuration.
+    #[inline]
+    pub fn memory_usage_with_compression(&self, compression: &CompressionConfig) -> u64 {
+        if self.state <= State::Uninitialized {
+            panic!("Game is not successfully initialized");
+        }
+
+        self.storage_sizes(compression).iter().sum::<u64>() + self.misc_memory_usage
+    }
+
+    /// Returns the memory usage of the allocated storage in bytes (resident, on disk).
+    ///
+    /// Unlike [`memory_usage`], this method reports the actual size of the allocated storage.
+    /// The storage allocated with [`StorageBackend::Disk`] is counted as on-disk bytes.
+    ///
+    /// [`memory_usage`]: #method.memory_usage
+    #[inline]
+    pub fn allocated_memory_usage(&self) -> (u64, u64) {
+        if self.state <= State::Uninitialized {
+            panic!("Game is not successfully initialized");
+        }
+
+        let mut resident = self.misc_memory_usage;
+        let mut on_disk = 0;
+
+        for storage in [
+            &self.storage1,
+            &self.storage2,
+            &self.storage_ip,
+            &self.storage_chance,
+        ] {
+            if storage.is_on_disk() {
+                on_disk += storage.len() as u64;
+            } else {
+                resident += storage.len() as u64;
+            }
+        }
+
+        (resident, on_disk)
+    }
+
     /// Returns the estimated additional memory usage in bytes when the bunching effect is enabled.
     #[inline]
     pub fn memory_usage_bunching(&self) -> u64 {
@@ -281,7 +361,11 @@ impl PostFlopGame {
     /// Remove lines after building the `PostFlopGame` but before allocating memory.
     ///
     /// This allows the removal of chance-specific lines (e.g., remove overbets on board-pairing
-    /// turns) which we cannot do while building an action tree.
+    /// turns) which we cannot do while building an action tree. The removed lines are recorded in
+    /// [`removed_game_lines`] (not in [`removed_lines`], which belong to the action tree).
+    ///
+    /// [`removed_game_lines`]: #method.removed_game_lines
+    /// [`removed_lines`]: #method.removed_lines
     pub fn remove_lines(&mut self, lines: &[Vec<Action>]) -> Result<(), String> {
         if self.state <= State::Uninitialized {
             return Err("Game is not successfully initialized".to_string());
@@ -292,9 +376,12 @@ impl PostFlopGame {
         for line in lines {
             let mut root = self.root();
             let info = self.remove_line_recursive(&mut root, line)?;
-            self.num_storage -= info.num_storage;
-            self.num_storage_ip -= info.num_storage_ip;
-            self.num_storage_chance -= info.num_storage_chance;
+            for street in 0..3 {
+                self.num_storage[street] -= info.num_storage[street];
+                self.num_storage_ip[street] -= info.num_storage_ip[street];
+                self.num_storage_chance[street] -= info.num_storage_chance[street];
+            }
+            self.removed_game_lines.push(line.clone());
         }
 
         Ok(())
@@ -309,48 +396,91 @@ impl PostFlopGame {
         if self.state <= State::TreeBuilt {
             None
         } else {
-            Some(self.is_compression_enabled)
+            Some(self.compression.is_enabled())
+        }
+    }
+
+    /// Returns the compression configuration if the memory is allocated.
+    #[inline]
+    pub fn compression_config(&self) -> Option<&CompressionConfig> {
+        if self.state <= State::TreeBuilt {
+            None
+        } else {
+            Some(&self.compression)
         }
     }
 
-    /// Allocates the memory.
+    /// Allocates the memory on the heap.
+    ///
+    /// This is equivalent to [`allocate_memory_with_backend`] with [`StorageBackend::Memory`].
+    ///
+    /// [`allocate_memory_with_backend`]: #method.allocate_memory_with_backend
+    #[inline]
     pub fn allocate_memory(&mut self, enable_compression: bool) {
+        self.allocate_memory_with_backend(enable_compression, &StorageBackend::Memory)
+            .unwrap();
+    }
+
+    /// Allocates the memory with the specified storage backend.
+    ///
+    /// Returns `Err` if the storage files of [`StorageBackend::Disk`] cannot be created. In that
+    /// case, the memory is not allocated (the solved result, if any, is lost).
+    #[inline]
+    pub fn allocate_memory_with_backend(
+        &mut self,
+        enable_compression: bool,
+        backend: &StorageBackend,
+    ) -> Result<(), String> {
+        self.allocate_memory_with_compression(&enable_compression.into(), backend)
+    }
+
+    /// Allocates the memory with the specified precision of each street and storage backend.
+    ///
+    /// See [`CompressionConfig`] for details. Returns `Err` in the same conditions as
+    /// [`allocate_memory_with_backend`].
+    ///
+    /// [`allocate_memory_with_backend`]: #method.allocate_memory_with_backend
+    pub fn allocate_memory_with_compression(
+        &mut self,
+        compression: &CompressionConfig,
+        backend: &StorageBackend,
+    ) -> Result<(), String> {
         if self.state <= State::Uninitialized {
             panic!("Game is not successfully initialized");
         }
 
         if self.state == State::MemoryAllocated
             && self.storage_mode == BoardState::River
-            && self.is_compression_enabled == enable_compression
+            && self.compression == *compression
+            && self.storage_backend == *backend
         {
-            return;
+            return Ok(());
         }
 
-        let num_bytes = if enable_compression { 2 } else { 4 };
-        if num_bytes * self.num_storage > isize::MAX as u64
-            || num_bytes * self.num_storage_chance > isize::MAX as u64
-        {
+        let storage_sizes = self.storage_sizes(compression);
+        if storage_sizes.iter().any(|&size| size > isize::MAX as u64) {
             panic!("Memory usage exceeds maximum size");
         }
 
-        self.state = State::MemoryAllocated;
-        self.is_compression_enabled = enable_compression;
-
+        // release the current storage first to reduce the peak memory usage
+        self.state = State::TreeBuilt;
         self.clear_storage();
 
-        let storage_bytes = (num_bytes * self.num_storage) as usize;
-        let storage_ip_bytes = (num_bytes * self.num_storage_ip) as usize;
-        let storage_chance_bytes = (num_bytes * self.num_storage_chance) as usize;
+        if let Err(e) = self.allocate_storage(&storage_sizes, backend) {
+            self.clear_storage();
+            return Err(e);
+        }
 
-        self.storage1 = vec![0; storage_bytes];
-        self.storage2 = vec![0; storage_bytes];
-        self.storage_ip = vec![0; storage_ip_bytes];
-        self.storage_chance = vec![0; storage_chance_bytes];
+        self.state = State::MemoryAllocated;
+        self.compression = *compression;
+        self.storage_backend = backend.clone();
 
         self.allocate_memory_nodes();
 
         self.storage_mode = BoardState::River;
         self.target_storage_mode = BoardState::River;
+
+        Ok(())
     }
 
     /// Checks the card configuration.
@@ -565,28 +695,75 @@ impl PostFlopGame {
     /// Initializes the interpreter.
     #[inline]
     pub(super) fn init_interpreter(&mut self) {
-        let vecs = [
-            vec![0.0; self.num_private_hands(0)],
-            vec![0.0; self.num_private_hands(1)],
-        ];
-
-        self.weights = vecs.clone();
-        self.normalized_weights = vecs.clone();
-        self.cfvalues_cache = vecs;
+        self.interpreter = InterpreterState::new(self);
     }
 
     /// Clears the storage.
     #[inline]
     fn clear_storage(&mut self) {
-        self.storage1 = Vec::new();
-        self.storage2 = Vec::new();
-        self.storage_ip = Vec::new();
-        self.storage_chance = Vec::new();
+        self.storage1 = Storage::default();
+        self.storage2 = Storage::default();
+        self.storage_ip = Storage::default();
+        self.storage_chance = Storage::default();
     }
 
-    /// Counts the number of nodes in the game tree.
+    /// Allocates the storage of the specified sizes with the specified backend.
+    fn allocate_storage(
+        &mut self,
+        sizes: &[u64; 4],
+        backend: &StorageBackend,
+    ) -> Result<(), String> {
+        self.storage1 = Storage::new(sizes[0] as usize, backend)?;
+        self.storage2 = Storage::new(sizes[1] as usize, backend)?;
+        self.storage_ip = Storage::new(sizes[2] as usize, backend)?;
+        self.storage_chance = Storage::new(sizes[3] as usize, backend)?;
+        Ok(())
+    }
+
+    /// Computes the sizes of `storage1`, `storage2`, `storage_ip`, and `storage_chance` in bytes.
+    pub(super) fn storage_sizes(&self, compression: &CompressionConfig) -> [u64; 4] {
+        let mut ret = [0u64; 4];
+
+        for (street, precision) in [compression.flop, compression.turn, compression.river]
+            .iter()
+            .enumerate()
+        {
+            // each street is stored contiguously and aligned to its element size
+            let num_bytes = Self::storage_num_bytes(precision).map(|x| x as u64);
+            let num_elements = [
+                self.num_storage[street],
+                self.num_storage[street],
+                self.num_storage_ip[street],
+                self.num_storage_chance[street],
+            ];
+            let num_bytes = [num_bytes[0], num_bytes[1], num_bytes[2], num_bytes[2]];
+            for i in 0..4 {
+                ret[i] = ret[i].next_multiple_of(num_bytes[i]) + num_bytes[i] * num_elements[i];
+            }
+        }
+
+        ret
+    }
+
+    /// Returns the number of bytes per element of `storage1`, `storage2`, and `storage3` of action
+    /// nodes.
+    ///
+    /// `storage2` is shared by the cumulative regrets and the counterfactual values, and the
+    /// counterfactual values of chance nodes have the same precision as `storage3`.
     #[inline]
-    fn count_num_nodes(&self) -> [u64; 3] {
+    pub(super) fn storage_num_bytes(precision: &StoragePrecision) -> [usize; 3] {
+        let regrets = precision.regrets.num_bytes();
+        let cfvalues = precision.cfvalues.num_bytes();
+        [
+            precision.strategy.num_bytes(),
+            regrets.max(cfvalues),
+            cfvalues,
+        ]
+    }
+
+    /// Returns the number of copies of each action node of [flop, turn, river] in the game tree,
+    /// i.e., the number of possible boards after applying the isomorphism.
+    pub(super) fn street_coefficients(&self) -> [u64; 3] {
         let (turn_coef, river_coef) = match (self.card_config.turn, self.card_config.river) {
             (NOT_DEALT, _) => {
                 let mut river_coef = 0;
@@ -605,18 +782,146 @@ impl PostFlopGame {
             _ => (0, 1),
         };
 
-        let num_action_nodes = count_num_action_nodes(&self.action_root.lock());
+        [1, turn_coef as u64, river_coef as u64]
+    }
 
-        [
-            num_action_nodes[0],
-            num_action_nodes[1] * turn_coef as u64,
-            num_action_nodes[2] * river_coef as u64,
-        ]
+    /// Counts the number of nodes in the game tree.
+    #[inline]
+    fn count_num_nodes(&self) -> [u64; 3] {
+        if self.tree_config.board_bet_sizes.is_empty() {
+            let coef = self.street_coefficients();
+            let num_action_nodes = count_num_action_nodes(&self.action_root.lock());
+            return [
+                num_action_nodes[0] * coef[0],
+                num_action_nodes[1] * coef[1],
+                num_action_nodes[2] * coef[2],
+            ];
+        }
+
+        let mut ret = [0; 3];
+        let rules = &self.tree_config.board_bet_sizes;
+        self.visit_action_tree(&self.action_root.lock(), rules, &mut |node, _, count| {
+            ret[node.board_state as usize] += count;
+        });
+        ret
+    }
+
+    /// Calls `visitor` for each node of the action tree built with the board bet size `rules`,
+    /// passing whether the node is the first action node of the street and the number of copies of
+    /// the node in the game tree.
+    ///
+    /// If no rules are given, the number of copies only depends on the street. Otherwise, the turn
+    /// cards are enumerated one by one and the river cards are grouped by the subtree they select.
+    pub(super) fn visit_action_tree<F: FnMut(&ActionTreeNode, bool, u64)>(
+        &self,
+        root: &ActionTreeNode,
+        rules: &[BoardBetSizes],
+        visitor: &mut F,
+    ) {
+        let mut ctx = VisitContext {
+            rules,
+            coef: self.street_coefficients(),
+            visitor,
+        };
+        let count = ctx.coef[root.board_state as usize];
+        self.visit_action_tree_recursive(root, true, self.card_config.turn, count, &mut ctx);
+    }
+
+    fn visit_action_tree_recursive<F: FnMut(&ActionTreeNode, bool, u64)>(
+        &self,
+        node: &ActionTreeNode,
+        is_first: bool,
+        turn: Card,
+        count: u64,
+        ctx: &mut VisitContext<F>,
+    ) {
+        (ctx.visitor)(node, is_first, count);
+
+        if node.is_terminal() {
+            // do nothing
+        } else if node.is_chance() {
+            if ctx.rules.is_empty() {
+                let count = ctx.coef[node.board_state as usize + 1];
+                let child = node.children[0].lock();
+                self.visit_action_tree_recursive(&child, true, turn, count, ctx);
+            } else if turn == NOT_DEALT {
+                let mask = self.chance_mask(NOT_DEALT);
+                for card in (0..52).filter(|&card| mask & (1 << card) != 0) {
+                    let index = self.size_rule_child(node, ctx.rules, card, NOT_DEALT);
+                    let child = node.children[index].lock();
+                    self.visit_action_tree_recursive(&child, true, card, count, ctx);
+                }
+            } else {
+                let mut num_cards = vec![0; node.children.len()];
+                let mask = self.chance_mask(turn);
+                for card in (0..52).filter(|&card| mask & (1 << card) != 0) {
+                    num_cards[self.size_rule_child(node, ctx.rules, turn, card)] += 1;
+                }
+                for (child, &n) in node.children.iter().zip(num_cards.iter()) {
+                    if n > 0 {
+                        let child = child.lock();
+                        self.visit_action_tree_recursive(&child, true, turn, count * n, ctx);
+                    }
+                }
+            }
+        } else {
+            for child in &node.children {
+                self.visit_action_tree_recursive(&child.lock(), false, turn, count, ctx);
+            }
+        }
+    }
+
+    /// Returns the bit mask of the cards dealt at a chance node, excluding the isomorphic cards.
+    /// `turn` is `NOT_DEALT` when dealing the turn.
+    fn chance_mask(&self, turn: Card) -> u64 {
+        let flop = self.card_config.flop;
+        let flop_mask: u64 = (1 << flop[0]) | (1 << flop[1]) | (1 << flop[2]);
+
+        let (board_mask, skip_cards) = match turn {
+            NOT_DEALT => (flop_mask, &self.isomorphism_card_turn),
+            _ => (
+                flop_mask | (1 << turn),
+                &self.isomorphism_card_river[turn as usize & 3],
+            ),
+        };
+
+        let skip_mask: u64 = skip_cards.iter().map(|&card| 1 << card).sum();
+        ((1 << 52) - 1) & !(board_mask | skip_mask)
+    }
+
+    /// Returns the index of the child of the chance node `action_node` to build after dealing a
+    /// card, i.e., the subtree of the first board bet size rule matching the board, or the default
+    /// subtree if no rule matches.
+    fn size_rule_child(
+        &self,
+        action_node: &ActionTreeNode,
+        rules: &[BoardBetSizes],
+        turn: Card,
+        river: Card,
+    ) -> usize {
+        if action_node.actions.len() == 1 {
+            return 0;
+        }
+
+        let mut board = self.card_config.flop.to_vec();
+        board.push(turn);
+        if river != NOT_DEALT {
+            board.push(river);
+        }
+
+        action_node
+            .actions
+            .iter()
+            .position(|&action| match action {
+                Action::Chance(rule) if rule > 0 => rules[rule as usize - 1].matches(&board),
+                _ => false,
+            })
+            .unwrap_or(0)
     }
 
     /// Computes the memory usage of this struct.
     #[inline]
-    fn memory_usage_internal(&self) -> u64 {
+    pub(super) fn memory_usage_internal(&self) -> u64 {
         // untracked: tree_config, action_root
 
         let mut memory_usage = mem::size_of::<Self>() as u64;
@@ -691,9 +996,15 @@ impl PostFlopGame {
 
         if node.is_chance() {
             self.push_chances(node_index, info);
-            for action_index in 0..node.num_actions() {
+            for (action_index, child) in node.children().iter().enumerate() {
                 let child_index = node_index + node.children_offset as usize + action_index;
-                self.build_tree_recursive(child_index, &action_node.children[0].lock(), info);
+                let (turn, river) = {
+                    let child = child.lock();
+                    (child.turn, child.river)
+                };
+                let rules = &self.tree_config.board_bet_sizes;
+                let index = self.size_rule_child(action_node, rules, turn, river);
+                self.build_tree_recursive(child_index, &action_node.children[index].lock(), info);
             }
         } else {
             self.push_actions(node_index, action_node, info);
@@ -711,17 +1022,13 @@ impl PostFlopGame {
     /// Pushes the chance actions to the `node`.
     fn push_chances(&self, node_index: usize, info: &mut BuildTreeInfo) {
         let mut node = self.node_arena[node_index].lock();
-        let flop = self.card_config.flop;
-        let flop_mask: u64 = (1 << flop[0]) | (1 << flop[1]) | (1 << flop[2]);
+        let mask = self.chance_mask(node.turn);
 
         // deal turn
         if node.turn == NOT_DEALT {
-            let skip_cards = &self.isomorphism_card_turn;
-            let skip_mask: u64 = skip_cards.iter().map(|&card| 1 << card).sum();
-
             node.children_offset = (info.turn_index - node_index) as u32;
             for card in 0..52 {
-                if (1 << card) & (flop_mask | skip_mask) == 0 {
+                if (1 << card) & mask != 0 {
                     node.num_children += 1;
                     let mut child = node.children().last().unwrap().lock();
                     child.prev_action = Action::Chance(card);
@@ -733,13 +1040,9 @@ impl PostFlopGame {
         }
         // deal river
         else {
-            let turn_mask = flop_mask | (1 << node.turn);
-            let skip_cards = &self.isomorphism_card_river[node.turn as usize & 3];
-            let skip_mask: u64 = skip_cards.iter().map(|&card| 1 << card).sum();
-
             node.children_offset = (info.river_index - node_index) as u32;
             for card in 0..52 {
-                if (1 << card) & (turn_mask | skip_mask) == 0 {
+                if (1 << card) & mask != 0 {
                     node.num_children += 1;
                     let mut child = node.children().last().unwrap().lock();
                     child.prev_action = Action::Chance(card);
@@ -755,7 +1058,7 @@ impl PostFlopGame {
             .cfvalue_storage_player()
             .map_or(0, |player| self.num_private_hands(player)) as u32;
 
-        info.num_storage_chance += node.num_elements as u64;
+        info.num_storage_chance[node.street() as usize] += node.num_elements as u64;
     }
 
     /// Pushes the actions to the `node`.
@@ -797,8 +1100,9 @@ impl PostFlopGame {
             _ => 0,
         };
 
-        info.num_storage += node.num_elements as u64;
-        info.num_storage_ip += node.num_elements_ip as u64;
+        let street = node.street() as usize;
+        info.num_storage[street] += node.num_elements as u64;
+        info.num_storage_ip[street] += node.num_elements_ip as u64;
     }
 
     /// Sets the bunching effect.
@@ -1332,12 +1636,13 @@ impl PostFlopGame {
             return;
         }
 
+        let street = node.street() as usize;
         if node.is_chance() {
-            info.num_storage_chance += node.num_elements as u64;
+            info.num_storage_chance[street] += node.num_elements as u64;
             node.num_elements = 0;
         } else {
-            info.num_storage += node.num_elements as u64;
-            info.num_storage_ip += node.num_elements_ip as u64;
+            info.num_storage[street] += node.num_elements as u64;
+            info.num_storage_ip[street] += node.num_elements_ip as u64;
             node.num_elements = 0;
             node.num_elements_ip = 0;
         }
@@ -1390,10 +1695,9 @@ impl PostFlopGame {
         // 3. re-define `num_elements` after we remove children and actions
 
         // STEP 1
-        let mut info = BuildTreeInfo {
-            num_storage: self.num_private_hands(node.player as usize) as u64,
-            ..Default::default()
-        };
+        let mut info = BuildTreeInfo::default();
+        info.num_storage[node.street() as usize] =
+            self.num_private_hands(node.player as usize) as u64;
 
         let mut node_to_remove = node.play(index);
         Self::calculate_removed_line_info_recursive(&mut node_to_remove, &mut info);
@@ -1417,34 +1721,50 @@ impl PostFlopGame {
     }
 
     /// Allocates memory recursively.
+    ///
+    /// The nodes are stored in the order of streets, so aligning the offset of each node to its
+    /// element size does not waste memory except at the boundaries of streets.
     fn allocate_memory_nodes(&mut self) {
-        let num_bytes = if self.is_compression_enabled { 2 } else { 4 };
-        let mut action_counter = 0;
-        let mut ip_counter = 0;
-        let mut chance_counter = 0;
+        let mut action_counter = [0usize; 2];
+        let mut ip_counter = 0usize;
+        let mut chance_counter = 0usize;
 
         for node in &self.node_arena {
             let mut node = node.lock();
+            let precision = self.compression.precision(node.street());
+            let num_bytes = Self::storage_num_bytes(&precision);
             if node.is_terminal() {
                 // do nothing
             } else if node.is_chance() {
+                chance_counter = chance_counter.next_multiple_of(num_bytes[2]);
                 unsafe {
                     let ptr = self.storage_chance.as_mut_ptr();
                     node.storage1 = ptr.add(chance_counter);
                 }
-                chance_counter += num_bytes * node.num_elements as usize;
+                chance_counter += num_bytes[2] * node.num_elements as usize;
             } else {
+                action_counter[0] = action_counter[0].next_multiple_of(num_bytes[0]);
+                action_counter[1] = action_counter[1].next_multiple_of(num_bytes[1]);
+                ip_counter = ip_counter.next_multiple_of(num_bytes[2]);
                 unsafe {
                     let ptr1 = self.storage1.as_mut_ptr();
                     let ptr2 = self.storage2.as_mut_ptr();
                     let ptr3 = self.storage_ip.as_mut_ptr();
-                    node.storage1 = ptr1.add(action_counter);
-                    node.storage2 = ptr2.add(action_counter);
+                    node.storage1 = ptr1.add(action_counter[0]);
+                    node.storage2 = ptr2.add(action_counter[1]);
                     node.storage3 = ptr3.add(ip_counter);
                 }
-                action_counter += num_bytes * node.num_elements as usize;
-                ip_counter += num_bytes * node.num_elements_ip as usize;
+                action_counter[0] += num_bytes[0] * node.num_elements as usize;
+                action_counter[1] += num_bytes[1] * node.num_elements as usize;
+                ip_counter += num_bytes[2] * node.num_elements_ip as usize;
             }
         }
     }
 }
+
+/// The state shared by the recursion of [`PostFlopGame::visit_action_tree`].
+struct VisitContext<'a, F> {
+    rules: &'a [BoardBetSizes],
+    coef: [u64; 3],
+    visitor: &'a mut F,
+}
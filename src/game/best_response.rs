@@ -0,0 +1,178 @@
+use super::*;
+use crate::interface::*;
+use crate::utility::*;
+
+impl PostFlopGame {
+    /// Returns the best-response (maximally exploitative) strategy of the current player against
+    /// the current strategy of the opponent.
+    ///
+    /// The return value has the same layout as the [`strategy`] method: a vector of the length of
+    /// `#(actions) * #(private hands)`, where each hand puts probability one on its best action.
+    /// The opponent's strategy is the current average strategy (including locked nodes), and the
+    /// opponent's reach probabilities are taken from the actions played so far. Locked hands of
+    /// the current player keep their locked strategy.
+    ///
+    /// The best response is computed on demand, so it can be navigated with the [`play`] method
+    /// just like the regular strategy.
+    ///
+    /// Panics if the current node is a terminal node or a chance node. Also, panics if the memory
+    /// is not yet allocated.
+    ///
+    /// **Time complexity:** *O*(size of the subtree).
+    ///
+    /// [`strategy`]: #method.strategy
+    /// [`play`]: #method.play
+    pub fn best_response_strategy(&self) -> Vec<f32> {
+        if self.state < State::MemoryAllocated {
+            panic!("Memory is not allocated");
+        }
+
+        if self.is_terminal_node() {
+            panic!("Terminal node is not allowed");
+        }
+
+        if self.is_chance_node() {
+            panic!("Chance node is not allowed");
+        }
+
+        let node = self.node();
+        let player = self.current_player();
+        let num_actions = node.num_actions();
+        let num_hands = self.num_private_hands(player);
+
+        let cfreach = self.opponent_cfreach(player);
+        let cfvalues = (0..num_actions)
+            .map(|action| self.best_cfvalues(&node.play(action), player, &cfreach))
+            .collect::<Vec<_>>();
+
+        let mut ret = vec![0.0; num_actions * num_hands];
+        for hand in 0..num_hands {
+            let mut best_action = 0;
+            for action in 1..num_actions {
+                if cfvalues[action][hand] > cfvalues[best_action][hand] {
+                    best_action = action;
+                }
+            }
+            ret[best_action * num_hands + hand] = 1.0;
+        }
+
+        let locking = self.locking_strategy(&node);
+        apply_locking_strategy(&mut ret, locking);
+
+        ret.chunks_exact_mut(num_hands).for_each(|chunk| {
+            self.apply_swap(chunk, player, false);
+        });
+
+        ret
+    }
+
+    /// Returns the expected values of each private hand of the given player when the player
+    /// plays the best response from the current node against the current strategy of the
+    /// opponent.
+    ///
+    /// The values are on the same scale as the [`expected_values`] method, so the difference of
+    /// the two is the gain of exploiting the opponent for each hand.
+    ///
+    /// Panics if the memory is not yet allocated.
+    ///
+    /// After mutating the current node, you must call the [`cache_normalized_weights`] method
+    /// before calling this method.
+    ///
+    /// **Time complexity:** *O*(size of the subtree).
+    ///
+    /// [`expected_values`]: #method.expected_values
+    /// [`cache_normalized_weights`]: #method.cache_normalized_weights
+    pub fn best_response_values(&self, player: usize) -> Vec<f32> {
+        if self.state < State::MemoryAllocated {
+            panic!("Memory is not allocated");
+        }
+
+        if !self.interpreter.is_normalized_weight_cached {
+            panic!("Normalized weights are not cached");
+        }
+
+        let cfreach = self.opponent_cfreach(player);
+        let mut ret = self.best_cfvalues(&self.node(), player, &cfreach);
+        self.apply_swap(&mut ret, player, false);
+
+        let num_combinations = match self.bunching_num_dead_cards {
+            0 => self.num_combinations,
+            _ => self.bunching_num_combinations,
+        } as f32;
+
+        let starting_pot = self.tree_config.starting_pot;
+        let total_bet_amount = self.total_bet_amount();
+        let bias = (total_bet_amount[player] - total_bet_amount[player ^ 1]).max(0);
+        let offset = starting_pot as f32 * 0.5 + (self.node().amount + bias) as f32;
+
+        ret.iter_mut()
+            .zip(self.interpreter.weights[player].iter())
+            .zip(self.interpreter.normalized_weights[player].iter())
+            .for_each(|((v, &w_raw), &w_normalized)| {
+                if w_normalized == 0.0 {
+                    *v = 0.0;
+                } else {
+                    *v *= num_combinations * (w_raw / w_normalized);
+                    *v += offset;
+                }
+            });
+
+        ret
+    }
+
+    /// Returns the expected value gain of the given player when switching from the current
+    /// (equilibrium) strategy to the best response at the current node.
+    ///
+    /// The gain is the average of `best_response_values(player) - expected_values(player)`
+    /// weighted by the normalized reach probabilities, so it is always nonnegative up to numerical
+    /// errors. At the root node, this is the amount the player can win by exploiting the opponent.
+    /// If the given player never reaches the current node, `0.0` is returned.
+    ///
+    /// Panics if the game is not solved.
+    ///
+    /// After mutating the current node, you must call the [`cache_normalized_weights`] method
+    /// before calling this method.
+    ///
+    /// [`cache_normalized_weights`]: #method.cache_normalized_weights
+    pub fn best_response_gain(&self, player: usize) -> f32 {
+        if self.state != State::Solved {
+            panic!("Game is not solved");
+        }
+
+        let normalized_weights = &self.interpreter.normalized_weights[player];
+        if normalized_weights.iter().all(|&w| w == 0.0) {
+            return 0.0;
+        }
+
+        let best_response = self.best_response_values(player);
+        let expected_values = self.expected_values(player);
+
+        let gain = best_response
+            .iter()
+            .zip(&expected_values)
+            .map(|(&br, &ev)| br - ev)
+            .collect::<Vec<_>>();
+
+        compute_average(&gain, normalized_weights)
+    }
+
+    /// Returns the reach probabilities of the opponent of `player` in terms of the representative
+    /// (i.e., unswapped) cards of the current node.
+    #[inline]
+    fn opponent_cfreach(&self, player: usize) -> Vec<f32> {
+        let mut cfreach = self.interpreter.weights[player ^ 1].clone();
+        self.apply_swap(&mut cfreach, player ^ 1, true);
+        cfreach
+    }
+
+    /// Computes the counterfactual values of the best response at `node` in terms of the
+    /// representative cards.
+    #[inline]
+    fn best_cfvalues(&self, node: &PostFlopNode, player: usize, cfreach: &[f32]) -> Vec<f32> {
+        let num_hands = self.num_private_hands(player);
+        let mut ret = Vec::with_capacity(num_hands);
+        compute_best_cfv_recursive(ret.spare_capacity_mut(), self, node, player, cfreach);
+        unsafe { ret.set_len(num_hands) };
+        ret
+    }
+}
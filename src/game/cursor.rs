@@ -0,0 +1,227 @@
+use super::*;
+
+/// A cursor for querying the results of a [`PostFlopGame`] without mutating it.
+///
+/// A cursor borrows the game immutably and holds its own current node, weights, and swap state,
+/// so any number of cursors can navigate one game at the same time, including from different
+/// threads. A cursor can be obtained by [`PostFlopGame::cursor`], and its methods behave the same
+/// as the navigation methods of [`PostFlopGame`].
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// let card_config = CardConfig {
+///     range: ["TT+,AQs+".parse().unwrap(), "99+,AJs+".parse().unwrap()],
+///     flop: flop_from_str("Td9d6h").unwrap(),
+///     turn: card_from_str("Qc").unwrap(),
+///     river: card_from_str("2s").unwrap(),
+///     ..Default::default()
+/// };
+///
+/// let tree_config = TreeConfig {
+///     initial_state: BoardState::River,
+///     starting_pot: 100,
+///     effective_stack: 100,
+///     river_bet_sizes: [("50%", "").try_into().unwrap(), ("50%", "").try_into().unwrap()],
+///     ..Default::default()
+/// };
+///
+/// let action_tree = ActionTree::new(tree_config).unwrap();
+/// let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+/// game.allocate_memory(false);
+/// solve(&mut game, 100, 0.0, false);
+///
+/// std::thread::scope(|s| {
+///     for action in 0..2 {
+///         let game = &game;
+///         s.spawn(move || {
+///             let mut cursor = game.cursor();
+///             cursor.play(action);
+///             cursor.cache_normalized_weights();
+///             let ev = cursor.expected_values(1);
+///             assert_eq!(ev.len(), game.private_cards(1).len());
+///         });
+///     }
+/// });
+/// ```
+#[derive(Clone)]
+pub struct GameCursor<'a> {
+    game: &'a PostFlopGame,
+    state: InterpreterState,
+}
+
+impl PostFlopGame {
+    /// Creates a [`GameCursor`] pointing to the root node of the game.
+    ///
+    /// Panics if the memory is not yet allocated.
+    #[inline]
+    pub fn cursor(&self) -> GameCursor<'_> {
+        if self.state < State::MemoryAllocated {
+            panic!("Memory is not allocated");
+        }
+
+        let mut state = InterpreterState::new(self);
+        state.back_to_root(self);
+        GameCursor { game: self, state }
+    }
+}
+
+impl<'a> GameCursor<'a> {
+    /// Returns the reference to the underlying game.
+    #[inline]
+    pub fn game(&self) -> &'a PostFlopGame {
+        self.game
+    }
+
+    /// Moves the current node back to the root node. See [`PostFlopGame::back_to_root`].
+    #[inline]
+    pub fn back_to_root(&mut self) {
+        self.state.back_to_root(self.game);
+    }
+
+    /// Returns the history of the current node. See [`PostFlopGame::history`].
+    #[inline]
+    pub fn history(&self) -> &[usize] {
+        self.state.history(self.game)
+    }
+
+    /// Applies the given history from the root node. See [`PostFlopGame::apply_history`].
+    #[inline]
+    pub fn apply_history(&mut self, history: &[usize]) {
+        self.state.apply_history(self.game, history);
+    }
+
+    /// Applies the given line from the root node. See [`PostFlopGame::apply_line`].
+    #[inline]
+    pub fn apply_line(&mut self, line: &[Action]) -> Result<(), String> {
+        self.state.apply_line(self.game, line)
+    }
+
+    /// Returns the line from the root node to the current node. See
+    /// [`PostFlopGame::current_line`].
+    #[inline]
+    pub fn current_line(&self) -> Vec<Action> {
+        self.state.current_line(self.game)
+    }
+
+    /// Returns whether the current node is a terminal node. See
+    /// [`PostFlopGame::is_terminal_node`].
+    #[inline]
+    pub fn is_terminal_node(&self) -> bool {
+        self.state.is_terminal_node(self.game)
+    }
+
+    /// Returns whether the current node is a leaf node of a depth-limited game tree. See
+    /// [`PostFlopGame::is_leaf_node`].
+    #[inline]
+    pub fn is_leaf_node(&self) -> bool {
+        self.state.is_leaf_node(self.game)
+    }
+
+    /// Returns whether the current node is a chance node. See [`PostFlopGame::is_chance_node`].
+    #[inline]
+    pub fn is_chance_node(&self) -> bool {
+        self.state.is_chance_node(self.game)
+    }
+
+    /// Returns the available actions for the current node. See
+    /// [`PostFlopGame::available_actions`].
+    #[inline]
+    pub fn available_actions(&self) -> Vec<Action> {
+        self.state.available_actions(self.game)
+    }
+
+    /// If the current node is a chance node, returns a list of cards that can be dealt. See
+    /// [`PostFlopGame::possible_cards`].
+    #[inline]
+    pub fn possible_cards(&self) -> u64 {
+        self.state.possible_cards(self.game)
+    }
+
+    /// Returns the current player (0 = OOP, 1 = IP). See [`PostFlopGame::current_player`].
+    #[inline]
+    pub fn current_player(&self) -> usize {
+        self.state.current_player(self.game)
+    }
+
+    /// Returns the current board. See [`PostFlopGame::current_board`].
+    #[inline]
+    pub fn current_board(&self) -> Vec<u8> {
+        self.state.current_board(self.game)
+    }
+
+    /// Plays the given action. See [`PostFlopGame::play`].
+    #[inline]
+    pub fn play(&mut self, action: usize) {
+        self.state.play(self.game, action);
+    }
+
+    /// Plays the given action. See [`PostFlopGame::play_action`].
+    #[inline]
+    pub fn play_action(&mut self, action: Action) -> Result<(), String> {
+        self.state.play_action(self.game, action)
+    }
+
+    /// Returns the available action nearest to the given action. See
+    /// [`PostFlopGame::nearest_action`].
+    #[inline]
+    pub fn nearest_action(&self, action: Action) -> Option<Action> {
+        self.state.nearest_action(self.game, action)
+    }
+
+    /// Computes the normalized weights and caches them. See
+    /// [`PostFlopGame::cache_normalized_weights`].
+    #[inline]
+    pub fn cache_normalized_weights(&mut self) {
+        self.state.cache_normalized_weights(self.game);
+    }
+
+    /// Returns the weights of each private hand of the given player. See
+    /// [`PostFlopGame::weights`].
+    #[inline]
+    pub fn weights(&self, player: usize) -> &[f32] {
+        self.state.weights(self.game, player)
+    }
+
+    /// Returns the normalized weights of each private hand of the given player. See
+    /// [`PostFlopGame::normalized_weights`].
+    #[inline]
+    pub fn normalized_weights(&self, player: usize) -> &[f32] {
+        self.state.normalized_weights(self.game, player)
+    }
+
+    /// Returns the equity of each private hand of the given player. See
+    /// [`PostFlopGame::equity`].
+    #[inline]
+    pub fn equity(&self, player: usize) -> Vec<f32> {
+        self.state.equity(self.game, player)
+    }
+
+    /// Returns the expected values of each private hand of the given player. See
+    /// [`PostFlopGame::expected_values`].
+    #[inline]
+    pub fn expected_values(&self, player: usize) -> Vec<f32> {
+        self.state.expected_values(self.game, player)
+    }
+
+    /// Returns the expected values of each action of each private hand of the given player. See
+    /// [`PostFlopGame::expected_values_detail`].
+    #[inline]
+    pub fn expected_values_detail(&self, player: usize) -> Vec<f32> {
+        self.state.expected_values_detail(self.game, player)
+    }
+
+    /// Returns the strategy of the current player. See [`PostFlopGame::strategy`].
+    #[inline]
+    pub fn strategy(&self) -> Vec<f32> {
+        self.state.strategy(self.game)
+    }
+
+    /// Returns the total bet amount of each player (OOP, IP). See
+    /// [`PostFlopGame::total_bet_amount`].
+    #[inline]
+    pub fn total_bet_amount(&self) -> [i32; 2] {
+        self.state.total_bet_amount()
+    }
+}
@@ -0,0 +1,89 @@
+use super::*;
+use crate::interface::*;
+use crate::solver::*;
+
+impl PostFlopGame {
+    /// Returns the cumulative regrets of the current player at the current node.
+    ///
+    /// The return value has the same layout as the [`strategy`] method: the regret of the `i`-th
+    /// action with the `j`-th private hand is stored in the `i * #(private hands) + j`-th element.
+    /// The regrets are discounted as in the solver and decoded if the storage is compressed.
+    ///
+    /// This method must be called before the game is solved (i.e., finalized), since the storage
+    /// of the regrets is reused for the counterfactual values after that. Panics if the memory is
+    /// not yet allocated, the game is already solved, or the current node is a terminal node or a
+    /// chance node.
+    ///
+    /// [`strategy`]: #method.strategy
+    pub fn cumulative_regrets(&self) -> Vec<f32> {
+        self.check_diagnosable();
+        let node = self.node();
+        self.swapped_rows(decoded_regrets(self, &node))
+    }
+
+    /// Returns the cumulative strategy of the current player at the current node, that is, the
+    /// discounted sum of the strategies of the past iterations before normalization.
+    ///
+    /// The return value has the same layout as the [`strategy`] method, and normalizing each hand
+    /// of it gives the average strategy (apart from locking). The sum of each hand indicates how
+    /// much strategy mass the hand has accumulated so far.
+    ///
+    /// Panics if the memory is not yet allocated, the game is already solved, or the current node
+    /// is a terminal node or a chance node.
+    ///
+    /// [`strategy`]: #method.strategy
+    pub fn cumulative_strategy(&self) -> Vec<f32> {
+        self.check_diagnosable();
+        let node = self.node();
+        self.swapped_rows(decoded_cum_strategy(self, &node))
+    }
+
+    /// Returns the instantaneous strategy of the current player at the current node, i.e., the
+    /// strategy obtained by regret matching from the current regrets, which the next iteration of
+    /// the solver plays.
+    ///
+    /// Locking and the aggregate-frequency constraints are applied in the same way as the solver.
+    /// A large difference between this strategy and the average strategy ([`strategy`]) indicates
+    /// that the node has not converged yet.
+    ///
+    /// Panics if the memory is not yet allocated, the game is already solved, or the current node
+    /// is a terminal node or a chance node.
+    ///
+    /// [`strategy`]: #method.strategy
+    pub fn instantaneous_strategy(&self) -> Vec<f32> {
+        self.check_diagnosable();
+        let node = self.node();
+        self.swapped_rows(current_strategy(self, &node))
+    }
+
+    /// Panics if the diagnostics of the current node are not available.
+    #[inline]
+    fn check_diagnosable(&self) {
+        if self.state < State::MemoryAllocated {
+            panic!("Memory is not allocated");
+        }
+
+        if self.state == State::Solved {
+            panic!("Game is already solved");
+        }
+
+        if self.is_terminal_node() {
+            panic!("Terminal node is not allowed");
+        }
+
+        if self.is_chance_node() {
+            panic!("Chance node is not allowed");
+        }
+    }
+
+    /// Converts each row of the values of the current player into the actual cards.
+    #[inline]
+    fn swapped_rows(&self, mut values: Vec<f32>) -> Vec<f32> {
+        let player = self.current_player();
+        let num_hands = self.num_private_hands(player);
+        values.chunks_exact_mut(num_hands).for_each(|row| {
+            self.apply_swap(row, player, false);
+        });
+        values
+    }
+}
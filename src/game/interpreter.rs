@@ -2,114 +2,695 @@ use super::*;
 use crate::interface::*;
 use crate::sliceop::*;
 use crate::utility::*;
+use std::mem;
 
-/// Decodes the encoded `i16` slice to the `f32` slice.
-#[inline]
-fn decode_signed_slice(slice: &[i16], scale: f32) -> Vec<f32> {
-    let decoder = scale / i16::MAX as f32;
-    slice.iter().map(|&x| x as f32 * decoder).collect()
-}
+impl PostFlopGame {
+    /// Moves the current node back to the root node.
+    #[inline]
+    pub fn back_to_root(&mut self) {
+        self.with_interpreter(|state, game| state.back_to_root(game))
+    }
+
+    /// Returns the history of the current node.
+    ///
+    /// The history is a list of action indices, i.e., the arguments of [`play`]. If `usize::MAX`
+    /// was passed to [`play`], it is replaced with the actual action index.
+    ///
+    /// [`play`]: #method.play
+    #[inline]
+    pub fn history(&self) -> &[usize] {
+        self.interpreter.history(self)
+    }
+
+    /// Applies the given history from the root node.
+    ///
+    /// This method first calls [`back_to_root`] and then calls [`play`] for each action in the
+    /// history. The action of `usize::MAX` is allowed for chance nodes.
+    ///
+    /// [`back_to_root`]: #method.back_to_root
+    /// [`play`]: #method.play
+    #[inline]
+    pub fn apply_history(&mut self, history: &[usize]) {
+        self.with_interpreter(|state, game| state.apply_history(game, history))
+    }
+
+    /// Applies the given line from the root node.
+    ///
+    /// This method first calls [`back_to_root`] and then calls [`play_action`] for each action in
+    /// the line. See [`line_from_str`] for parsing a line from a string.
+    ///
+    /// Returns `Err` if an action cannot be played. In that case, the current node is restored to
+    /// the node before calling this method.
+    ///
+    /// [`back_to_root`]: #method.back_to_root
+    /// [`play_action`]: #method.play_action
+    /// [`line_from_str`]: crate::line_from_str
+    pub fn apply_line(&mut self, line: &[Action]) -> Result<(), String> {
+        self.with_interpreter(|state, game| state.apply_line(game, line))
+    }
+
+    /// Returns the line from the root node to the current node.
+    ///
+    /// Unlike [`available_actions`], the chance actions are the actual dealt cards. The returned
+    /// line can be converted into a string by [`line_to_string`].
+    ///
+    /// [`available_actions`]: #method.available_actions
+    /// [`line_to_string`]: crate::line_to_string
+    pub fn current_line(&self) -> Vec<Action> {
+        self.interpreter.current_line(self)
+    }
+
+    /// Returns whether the current node is a terminal node.
+    ///
+    /// Note that the turn/river node after the call action after the all-in action is considered
+    /// terminal.
+    #[inline]
+    pub fn is_terminal_node(&self) -> bool {
+        self.interpreter.is_terminal_node(self)
+    }
+
+    /// Returns whether the current node is a leaf node of a depth-limited game tree.
+    ///
+    /// Note that the leaf node is also considered terminal.
+    #[inline]
+    pub fn is_leaf_node(&self) -> bool {
+        self.interpreter.is_leaf_node(self)
+    }
+
+    /// Returns whether the current node is a chance node (i.e., turn/river node).
+    ///
+    /// Note that the terminal node is not considered a chance node.
+    #[inline]
+    pub fn is_chance_node(&self) -> bool {
+        self.interpreter.is_chance_node(self)
+    }
+
+    /// Returns the available actions for the current node.
+    ///
+    /// If the current node is a terminal, returns an empty list. If the current node is a
+    /// turn/river node and not a terminal, isomorphic chances are grouped into one representative
+    /// action (in most cases, you should use the [`possible_cards`] method).
+    ///
+    /// [`possible_cards`]: #method.possible_cards
+    #[inline]
+    pub fn available_actions(&self) -> Vec<Action> {
+        self.interpreter.available_actions(self)
+    }
+
+    /// If the current node is a chance node, returns a list of cards that can be dealt.
+    ///
+    /// The returned value is a 64-bit integer.
+    /// The `i`-th bit is set to 1 if the card of ID `i` can be dealt (see [`Card`] for encoding).
+    /// If the current node is not a chance node, `0` is returned.
+    pub fn possible_cards(&self) -> u64 {
+        self.interpreter.possible_cards(self)
+    }
+
+    /// Returns the current player (0 = OOP, 1 = IP).
+    ///
+    /// If the current node is a terminal node or a chance node, returns an undefined value.
+    #[inline]
+    pub fn current_player(&self) -> usize {
+        self.interpreter.current_player(self)
+    }
+
+    /// Returns the current board.
+    ///
+    /// The returned vector is of length 3, 4, or 5. The flop cards, the turn card, and the river
+    /// card, if any, are stored in this order.
+    #[inline]
+    pub fn current_board(&self) -> Vec<u8> {
+        self.interpreter.current_board(self)
+    }
+
+    /// Plays the given action. Playing an action from a terminal node is not allowed.
+    ///
+    /// - `action`
+    ///   - If the current node is a chance node, the `action` corresponds to the dealt card (see
+    ///     [`Card`] for encoding). If `usize::MAX` is passed, the card is selected as the possible
+    ///     card with the lowest index.
+    ///   - If the current node is not a chance node, plays the `action`-th action of
+    ///     [`available_actions`].
+    ///
+    /// Panics if the memory is not yet allocated or the current node is a terminal node.
+    ///
+    /// **Time complexity:** *O*(#(OOP private hands) + #(IP private hands))
+    ///
+    /// [`available_actions`]: #method.available_actions
+    pub fn play(&mut self, action: usize) {
+        self.with_interpreter(|state, game| state.play(game, action))
+    }
+
+    /// Plays the given action.
+    ///
+    /// If the current node is a chance node, `action` must be [`Action::Chance`] with a card that
+    /// can be dealt (see [`possible_cards`]). Otherwise, `action` must be one of
+    /// [`available_actions`]; a bet, raise, or all-in action also matches an available
+    /// aggressive action of the same amount (e.g., `Bet(970)` matches `AllIn(970)`).
+    ///
+    /// Returns `Err` if the action cannot be played, with the nearest available action (see
+    /// [`nearest_action`]) in the message if any. Panics if the memory is not yet allocated.
+    ///
+    /// [`possible_cards`]: #method.possible_cards
+    /// [`available_actions`]: #method.available_actions
+    /// [`nearest_action`]: #method.nearest_action
+    pub fn play_action(&mut self, action: Action) -> Result<(), String> {
+        self.with_interpreter(|state, game| state.play_action(game, action))
+    }
+
+    /// Returns the available action nearest to the given action.
+    ///
+    /// If `action` is available, returns it as is. If `action` is a bet, raise, or all-in action,
+    /// returns the available bet, raise, or all-in action whose amount is the closest to that of
+    /// `action` (the smaller one is preferred in case of a tie). Otherwise, returns `None`.
+    ///
+    /// Panics if the current node is a terminal node or a chance node.
+    pub fn nearest_action(&self, action: Action) -> Option<Action> {
+        self.interpreter.nearest_action(self, action)
+    }
+
+    /// Computes the normalized weights and caches them.
+    ///
+    /// After mutating the current node, this method must be called once before calling
+    /// [`normalized_weights`], [`equity`], [`expected_values`], or [`expected_values_detail`].
+    ///
+    /// **Time complexity:**
+    /// - (no bunching) *O*(#(OOP private hands) + #(IP private hands))
+    /// - (bunching) *O*(#(OOP private hands) * #(IP private hands))
+    ///
+    /// [`normalized_weights`]: #method.normalized_weights
+    /// [`equity`]: #method.equity
+    /// [`expected_values`]: #method.expected_values
+    /// [`expected_values_detail`]: #method.expected_values_detail
+    pub fn cache_normalized_weights(&mut self) {
+        self.with_interpreter(|state, game| state.cache_normalized_weights(game))
+    }
+
+    /// Returns the weights of each private hand of the given player.
+    ///
+    /// If a hand overlaps with the board, returns 0.0.
+    ///
+    /// **Time complexity:** *O*(1).
+    #[inline]
+    pub fn weights(&self, player: usize) -> &[f32] {
+        self.interpreter.weights(self, player)
+    }
+
+    /// Returns the normalized weights of each private hand of the given player.
+    ///
+    /// The "normalized weights" represent the actual number of combinations that the player is
+    /// holding each hand.
+    ///
+    /// After mutating the current node, you must call the [`cache_normalized_weights`] method
+    /// before calling this method.
+    ///
+    /// **Time complexity:** *O*(1).
+    ///
+    /// [`cache_normalized_weights`]: #method.cache_normalized_weights
+    #[inline]
+    pub fn normalized_weights(&self, player: usize) -> &[f32] {
+        self.interpreter.normalized_weights(self, player)
+    }
+
+    /// Returns the equity of each private hand of the given player.
+    ///
+    /// After mutating the current node, you must call the [`cache_normalized_weights`] method
+    /// before calling this method.
+    ///
+    /// **Time complexity:**
+    /// - (no bunching) *O*(#(possible 5-card boards) * (#(OOP private hands) + #(IP private hands))).
+    /// - (bunching) *O*(#(OOP private hands) * #(IP private hands)).
+    ///
+    /// [`cache_normalized_weights`]: #method.cache_normalized_weights
+    pub fn equity(&self, player: usize) -> Vec<f32> {
+        self.interpreter.equity(self, player)
+    }
+
+    /// Returns the expected values of each private hand of the given player.
+    ///
+    /// Panics if the game is not solved.
+    ///
+    /// After mutating the current node, you must call the [`cache_normalized_weights`] method
+    /// before calling this method.
+    ///
+    /// **Time complexity:** see [`expected_values_detail`].
+    ///
+    /// [`cache_normalized_weights`]: #method.cache_normalized_weights
+    /// [`expected_values_detail`]: #method.expected_values_detail
+    pub fn expected_values(&self, player: usize) -> Vec<f32> {
+        self.interpreter.expected_values(self, player)
+    }
+
+    /// Returns the expected values of each action of each private hand of the given player.
+    ///
+    /// If the given player is the current player, the return value is a vector of the length of
+    /// `#(actions) * #(private hands)`. The expected value of the `i`-th action with the `j`-th
+    /// private hand is stored in the `i * #(private hands) + j`-th element.
+    ///
+    /// Otherwise, this method is the same as the [`expected_values`] method, so the return vector
+    /// is the length of `#(private hands)`.
+    ///
+    /// Panics if the game is not solved.
+    ///
+    /// After mutating the current node, you must call the [`cache_normalized_weights`] method
+    /// before calling this method.
+    ///
+    /// **Time complexity:**
+    /// - (with bunching and the current node is terminal) *O*(#(OOP private hands) * #(IP private hands)).
+    /// - (otherwise) *O*(#(actions) * #(private hands)).
+    ///
+    /// [`expected_values`]: #method.expected_value
+    /// [`cache_normalized_weights`]: #method.cache_normalized_weights
+    pub fn expected_values_detail(&self, player: usize) -> Vec<f32> {
+        self.interpreter.expected_values_detail(self, player)
+    }
+
+    /// Returns the strategy of the current player.
+    ///
+    /// The return value is a vector of the length of `#(actions) * #(private hands)`.
+    /// The probability of the `i`-th action with the `j`-th private hand is stored in the
+    /// `i * #(private hands) + j`-th element.
+    ///
+    /// If a hand overlaps with the board, an undefined value is returned.
+    ///
+    /// Panics if the current node is a terminal node or a chance node. Also, panics if the memory
+    /// is not yet allocated.
+    ///
+    /// **Time complexity:** *O*(#(actions) * #(private hands)).
+    pub fn strategy(&self) -> Vec<f32> {
+        self.interpreter.strategy(self)
+    }
+
+    /// Returns the total bet amount of each player (OOP, IP).
+    #[inline]
+    pub fn total_bet_amount(&self) -> [i32; 2] {
+        self.interpreter.total_bet_amount()
+    }
+
+    /// Locks the strategy of the current node.
+    ///
+    /// The `strategy` argument must be a slice of the length of `#(actions) * #(private hands)`.
+    ///
+    /// - A negative value is treated as a zero.
+    /// - If the `i * #(private hands) + j`-th element of the `strategy` is positive for some `i`,
+    ///   the `j`-th private hand will be locked. The probability for each action will be normalized
+    ///   so that their sum is 1.0.
+    /// - If the `i * #(private hands) + j`-th element of the `strategy` is not positive for all
+    ///   `i`, the `j`-th private hand will not be locked. That is, the solver can adjust the
+    ///   strategy of the `j`-th private hand.
+    ///
+    /// This method must be called after allocating memory and before solving the game.
+    /// Panics if the memory is not yet allocated or the game is already solved.
+    /// Also, panics if the current node is a terminal node or a chance node.
+    pub fn lock_current_strategy(&mut self, strategy: &[f32]) {
+        if self.state < State::MemoryAllocated {
+            panic!("Memory is not allocated");
+        }
+
+        if self.state == State::Solved {
+            panic!("Game is already solved");
+        }
+
+        if self.is_terminal_node() {
+            panic!("Terminal node is not allowed");
+        }
+
+        if self.is_chance_node() {
+            panic!("Chance node is not allowed");
+        }
+
+        let mut node = self.node();
+        let player = self.current_player();
+        let num_actions = node.num_actions();
+        let num_hands = self.num_private_hands(player);
+
+        if strategy.len() != num_actions * num_hands {
+            panic!("Invalid strategy length");
+        }
+
+        let mut locking = vec![-1.0; num_actions * num_hands];
+
+        for hand in 0..num_hands {
+            let mut sum = 0.0;
+            let mut lock = false;
+
+            for action in 0..num_actions {
+                let freq = strategy[action * num_hands + hand];
+                if freq > 0.0 {
+                    sum += freq as f64;
+                    lock = true;
+                }
+            }
+
+            if lock {
+                for action in 0..num_actions {
+                    let freq = strategy[action * num_hands + hand].max(0.0) as f64;
+                    locking[action * num_hands + hand] = (freq / sum) as f32;
+                }
+            }
+        }
+
+        locking.chunks_exact_mut(num_hands).for_each(|chunk| {
+            self.apply_swap(chunk, player, true);
+        });
+
+        node.is_locked = true;
+        let index = self.node_index(&node);
+        self.locking_strategy.insert(index, locking);
+    }
+
+    /// Unlocks the strategy of the current node.
+    ///
+    /// This method must be called after allocating memory and before solving the game.
+    /// Panics if the memory is not yet allocated or the game is already solved.
+    /// Also, panics if the current node is a terminal node or a chance node.
+    #[inline]
+    pub fn unlock_current_strategy(&mut self) {
+        if self.state < State::MemoryAllocated {
+            panic!("Memory is not allocated");
+        }
+
+        if self.state == State::Solved {
+            panic!("Game is already solved");
+        }
+
+        if self.is_terminal_node() {
+            panic!("Terminal node is not allowed");
+        }
+
+        if self.is_chance_node() {
+            panic!("Chance node is not allowed");
+        }
+
+        let mut node = self.node();
+        if !node.is_locked {
+            return;
+        }
+
+        node.is_locked = false;
+        let index = self.node_index(&node);
+        self.locking_strategy.remove(&index);
+    }
+
+    /// Returns the locking strategy of the current node.
+    ///
+    /// If the current node is not locked, `None` is returned.
+    ///
+    /// Otherwise, returns a reference to the vector of the length of
+    /// `#(actions) * #(private hands)`.
+    /// The probability of the `i`-th action with the `j`-th private hand is stored in the
+    /// `i * #(private hands) + j`-th element.
+    /// If the `j`-th private hand is not locked, returns `-1.0` for all `i`.
+    #[inline]
+    pub fn current_locking_strategy(&self) -> Option<Vec<f32>> {
+        if self.state < State::MemoryAllocated {
+            panic!("Memory is not allocated");
+        }
 
-impl PostFlopGame {
-    /// Moves the current node back to the root node.
+        if self.is_terminal_node() {
+            panic!("Terminal node is not allowed");
+        }
+
+        if self.is_chance_node() {
+            panic!("Chance node is not allowed");
+        }
+
+        let index = self.node_index(&self.node());
+        self.locking_strategy.get(&index).map(|s| {
+            let mut ret = s.clone();
+            let player = self.current_player();
+            let num_hands = self.num_private_hands(player);
+            ret.chunks_exact_mut(num_hands).for_each(|chunk| {
+                self.apply_swap(chunk, player, false);
+            });
+            ret
+        })
+    }
+
+    /// Returns the reference to the current node.
     #[inline]
-    pub fn back_to_root(&mut self) {
-        if self.state <= State::Uninitialized {
+    pub(super) fn node(&self) -> MutexGuardLike<PostFlopNode> {
+        self.interpreter.node(self)
+    }
+
+    /// Returns the index of the given node.
+    #[inline]
+    pub(super) fn node_index(&self, node: &PostFlopNode) -> usize {
+        let node_ptr = node as *const _ as *const MutexLike<PostFlopNode>;
+        unsafe { node_ptr.offset_from(self.node_arena.as_ptr()) as usize }
+    }
+
+    /// Assigns zero weights to the hands that are not possible.
+    pub(super) fn assign_zero_weights(&mut self) {
+        self.with_interpreter(|state, game| state.assign_zero_weights(game))
+    }
+
+    /// Returns the strategy of the given player node, which must be reached from the current node
+    /// without dealing a card (i.e., the swaps of the current node are applied).
+    pub(super) fn strategy_at(&self, node: &PostFlopNode) -> Vec<f32> {
+        self.interpreter.strategy_at(self, node)
+    }
+
+    /// Applies the swap.
+    #[inline]
+    pub(super) fn apply_swap(&self, slice: &mut [f32], player: usize, reverse: bool) {
+        self.interpreter.apply_swap(self, slice, player, reverse)
+    }
+
+    /// Internal method for calculating the equity.
+    pub(super) fn equity_internal(
+        &self,
+        result: &mut [f64],
+        player: usize,
+        turn: Card,
+        river: Card,
+        opponent_weights: &[f32],
+        amount: f64,
+    ) {
+        let pair_index = card_pair_to_index(turn, river);
+        let hand_strength = &self.hand_strength[pair_index];
+        let player_strength = &hand_strength[player];
+        let opponent_strength = &hand_strength[player ^ 1];
+
+        let player_len = player_strength.len();
+        let opponent_len = opponent_strength.len();
+
+        if player_len == 0 || opponent_len == 0 {
+            return;
+        }
+
+        let player_cards = &self.private_cards[player];
+        let opponent_cards = &self.private_cards[player ^ 1];
+
+        let mut weight_sum = 0.0;
+        let mut weight_minus = [0.0; 52];
+
+        let valid_player_strength = &player_strength[1..player_len - 1];
+        let mut i = 1;
+
+        for &StrengthItem { strength, index } in valid_player_strength {
+            unsafe {
+                while opponent_strength.get_unchecked(i).strength < strength {
+                    let opponent_index = opponent_strength.get_unchecked(i).index as usize;
+                    let (c1, c2) = *opponent_cards.get_unchecked(opponent_index);
+                    let weight_i = *opponent_weights.get_unchecked(opponent_index) as f64;
+                    weight_sum += weight_i;
+                    *weight_minus.get_unchecked_mut(c1 as usize) += weight_i;
+                    *weight_minus.get_unchecked_mut(c2 as usize) += weight_i;
+                    i += 1;
+                }
+                let (c1, c2) = *player_cards.get_unchecked(index as usize);
+                let opponent_weight = weight_sum
+                    - weight_minus.get_unchecked(c1 as usize)
+                    - weight_minus.get_unchecked(c2 as usize);
+                *result.get_unchecked_mut(index as usize) += amount * opponent_weight;
+            }
+        }
+
+        weight_sum = 0.0;
+        weight_minus.fill(0.0);
+        i = opponent_len - 2;
+
+        for &StrengthItem { strength, index } in valid_player_strength.iter().rev() {
+            unsafe {
+                while opponent_strength.get_unchecked(i).strength > strength {
+                    let opponent_index = opponent_strength.get_unchecked(i).index as usize;
+                    let (c1, c2) = *opponent_cards.get_unchecked(opponent_index);
+                    let weight_i = *opponent_weights.get_unchecked(opponent_index) as f64;
+                    weight_sum += weight_i;
+                    *weight_minus.get_unchecked_mut(c1 as usize) += weight_i;
+                    *weight_minus.get_unchecked_mut(c2 as usize) += weight_i;
+                    i -= 1;
+                }
+                let (c1, c2) = *player_cards.get_unchecked(index as usize);
+                let opponent_weight = weight_sum
+                    - weight_minus.get_unchecked(c1 as usize)
+                    - weight_minus.get_unchecked(c2 as usize);
+                *result.get_unchecked_mut(index as usize) -= amount * opponent_weight;
+            }
+        }
+    }
+
+    /// Runs `f` with the interpreter state temporarily taken out of the game.
+    #[inline]
+    fn with_interpreter<T>(&mut self, f: impl FnOnce(&mut InterpreterState, &Self) -> T) -> T {
+        let mut state = mem::take(&mut self.interpreter);
+        let ret = f(&mut state, self);
+        self.interpreter = state;
+        ret
+    }
+}
+
+/// The state of the result interpreter, i.e., the current node and the quantities that depend on
+/// the path from the root node.
+///
+/// [`PostFlopGame`] holds one instance to implement its navigation methods, and [`GameCursor`]
+/// holds its own instance to navigate a shared game.
+#[derive(Debug, Clone, Default)]
+pub(super) struct InterpreterState {
+    pub(super) action_history: Vec<usize>,
+    pub(super) node_history: Vec<usize>,
+    pub(super) is_normalized_weight_cached: bool,
+    pub(super) turn: Card,
+    pub(super) river: Card,
+    pub(super) turn_swapped_suit: Option<(u8, u8)>,
+    pub(super) turn_swap: Option<u8>,
+    pub(super) river_swap: Option<(u8, u8)>,
+    pub(super) total_bet_amount: [i32; 2],
+    pub(super) weights: [Vec<f32>; 2],
+    pub(super) normalized_weights: [Vec<f32>; 2],
+    pub(super) cfvalues_cache: [Vec<f32>; 2],
+}
+
+impl InterpreterState {
+    /// Creates a new state for the given game. The state must be moved to the root node by
+    /// [`back_to_root`](Self::back_to_root) before use.
+    pub(super) fn new(game: &PostFlopGame) -> Self {
+        let vecs = [
+            vec![0.0; game.num_private_hands(0)],
+            vec![0.0; game.num_private_hands(1)],
+        ];
+
+        Self {
+            weights: vecs.clone(),
+            normalized_weights: vecs.clone(),
+            cfvalues_cache: vecs,
+            ..Default::default()
+        }
+    }
+
+    pub(super) fn back_to_root(&mut self, game: &PostFlopGame) {
+        if game.state <= State::Uninitialized {
             panic!("Game is not successfully initialized");
         }
 
         self.action_history.clear();
         self.node_history.clear();
         self.is_normalized_weight_cached = false;
-        self.turn = self.card_config.turn;
-        self.river = self.card_config.river;
+        self.turn = game.card_config.turn;
+        self.river = game.card_config.river;
         self.turn_swapped_suit = None;
         self.turn_swap = None;
         self.river_swap = None;
         self.total_bet_amount = [0, 0];
 
-        self.weights[0].copy_from_slice(&self.initial_weights[0]);
-        self.weights[1].copy_from_slice(&self.initial_weights[1]);
-        self.assign_zero_weights();
+        self.weights[0].copy_from_slice(&game.initial_weights[0]);
+        self.weights[1].copy_from_slice(&game.initial_weights[1]);
+        self.assign_zero_weights(game);
     }
 
-    /// Returns the history of the current node.
-    ///
-    /// The history is a list of action indices, i.e., the arguments of [`play`]. If `usize::MAX`
-    /// was passed to [`play`], it is replaced with the actual action index.
-    ///
-    /// [`play`]: #method.play
-    #[inline]
-    pub fn history(&self) -> &[usize] {
-        if self.state <= State::Uninitialized {
+    pub(super) fn history(&self, game: &PostFlopGame) -> &[usize] {
+        if game.state <= State::Uninitialized {
             panic!("Game is not successfully initialized");
         }
 
         &self.action_history
     }
 
-    /// Applies the given history from the root node.
-    ///
-    /// This method first calls [`back_to_root`] and then calls [`play`] for each action in the
-    /// history. The action of `usize::MAX` is allowed for chance nodes.
-    ///
-    /// [`back_to_root`]: #method.back_to_root
-    /// [`play`]: #method.play
-    #[inline]
-    pub fn apply_history(&mut self, history: &[usize]) {
-        if self.state <= State::Uninitialized {
+    pub(super) fn apply_history(&mut self, game: &PostFlopGame, history: &[usize]) {
+        if game.state <= State::Uninitialized {
             panic!("Game is not successfully initialized");
         }
 
-        self.back_to_root();
+        self.back_to_root(game);
         for &action in history {
-            self.play(action);
+            self.play(game, action);
         }
     }
 
-    /// Returns whether the current node is a terminal node.
-    ///
-    /// Note that the turn/river node after the call action after the all-in action is considered
-    /// terminal.
-    #[inline]
-    pub fn is_terminal_node(&self) -> bool {
-        if self.state <= State::Uninitialized {
+    pub(super) fn apply_line(
+        &mut self,
+        game: &PostFlopGame,
+        line: &[Action],
+    ) -> Result<(), String> {
+        let history = self.action_history.clone();
+        self.back_to_root(game);
+
+        for (i, &action) in line.iter().enumerate() {
+            if let Err(e) = self.play_action(game, action) {
+                self.apply_history(game, &history);
+                return Err(format!("Action #{i}: {e}"));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn current_line(&self, game: &PostFlopGame) -> Vec<Action> {
+        if game.state <= State::Uninitialized {
             panic!("Game is not successfully initialized");
         }
 
-        let node = self.node();
-        node.is_terminal() || node.amount == self.tree_config.effective_stack
+        let mut node_index = 0;
+        let mut ret = Vec::with_capacity(self.action_history.len());
+
+        for (&action, &next_index) in self.action_history.iter().zip(&self.node_history) {
+            let node = game.node_arena[node_index].lock();
+            ret.push(if node.is_chance() {
+                Action::Chance(action as Card)
+            } else {
+                node.play(action).prev_action
+            });
+            node_index = next_index;
+        }
+
+        ret
     }
 
-    /// Returns whether the current node is a chance node (i.e., turn/river node).
-    ///
-    /// Note that the terminal node is not considered a chance node.
-    #[inline]
-    pub fn is_chance_node(&self) -> bool {
-        if self.state <= State::Uninitialized {
+    pub(super) fn is_terminal_node(&self, game: &PostFlopGame) -> bool {
+        if game.state <= State::Uninitialized {
             panic!("Game is not successfully initialized");
         }
 
-        self.node().is_chance() && !self.is_terminal_node()
+        let node = self.node(game);
+        node.is_terminal() || node.amount == game.tree_config.effective_stack
     }
 
-    /// Returns the available actions for the current node.
-    ///
-    /// If the current node is a terminal, returns an empty list. If the current node is a
-    /// turn/river node and not a terminal, isomorphic chances are grouped into one representative
-    /// action (in most cases, you should use the [`possible_cards`] method).
-    ///
-    /// [`possible_cards`]: #method.possible_cards
-    #[inline]
-    pub fn available_actions(&self) -> Vec<Action> {
-        if self.state <= State::Uninitialized {
+    pub(super) fn is_leaf_node(&self, game: &PostFlopGame) -> bool {
+        if game.state <= State::Uninitialized {
             panic!("Game is not successfully initialized");
         }
 
-        if self.is_terminal_node() {
+        self.node(game).is_leaf()
+    }
+
+    pub(super) fn is_chance_node(&self, game: &PostFlopGame) -> bool {
+        if game.state <= State::Uninitialized {
+            panic!("Game is not successfully initialized");
+        }
+
+        self.node(game).is_chance() && !self.is_terminal_node(game)
+    }
+
+    pub(super) fn available_actions(&self, game: &PostFlopGame) -> Vec<Action> {
+        if game.state <= State::Uninitialized {
+            panic!("Game is not successfully initialized");
+        }
+
+        if self.is_terminal_node(game) {
             Vec::new()
         } else {
-            self.node()
+            self.node(game)
                 .children()
                 .iter()
                 .map(|c| c.lock().prev_action)
@@ -117,26 +698,21 @@ impl PostFlopGame {
         }
     }
 
-    /// If the current node is a chance node, returns a list of cards that can be dealt.
-    ///
-    /// The returned value is a 64-bit integer.
-    /// The `i`-th bit is set to 1 if the card of ID `i` can be dealt (see [`Card`] for encoding).
-    /// If the current node is not a chance node, `0` is returned.
-    pub fn possible_cards(&self) -> u64 {
-        if self.state <= State::Uninitialized {
+    pub(super) fn possible_cards(&self, game: &PostFlopGame) -> u64 {
+        if game.state <= State::Uninitialized {
             panic!("Game is not successfully initialized");
         }
 
-        if !self.is_chance_node() {
+        if !self.is_chance_node(game) {
             return 0;
         }
 
-        let flop = self.card_config.flop;
+        let flop = game.card_config.flop;
         let mut board_mask: u64 = (1 << flop[0]) | (1 << flop[1]) | (1 << flop[2]);
         let mut dead_mask: u64 = 0;
 
         // no bunching
-        if self.bunching_num_dead_cards == 0 {
+        if game.bunching_num_dead_cards == 0 {
             if self.turn != NOT_DEALT {
                 board_mask |= 1 << self.turn;
             }
@@ -146,13 +722,13 @@ impl PostFlopGame {
                 let new_board_mask = board_mask | bit_card;
 
                 if new_board_mask != board_mask {
-                    for &(c1, c2) in &self.private_cards[0] {
+                    for &(c1, c2) in &game.private_cards[0] {
                         let oop_mask: u64 = (1 << c1) | (1 << c2);
                         if oop_mask & new_board_mask != 0 {
                             continue;
                         }
                         let combined_mask = oop_mask | new_board_mask;
-                        for &(c3, c4) in &self.private_cards[1] {
+                        for &(c3, c4) in &game.private_cards[1] {
                             let ip_mask: u64 = (1 << c3) | (1 << c4);
                             if ip_mask & combined_mask == 0 {
                                 continue 'outer;
@@ -166,19 +742,19 @@ impl PostFlopGame {
         }
         // bunching
         else {
-            let node_turn = self.node().turn;
+            let node_turn = self.node(game).turn;
             if node_turn != NOT_DEALT {
                 board_mask |= 1 << node_turn;
             }
 
-            let ip_len = self.num_private_hands(1);
+            let ip_len = game.num_private_hands(1);
             let mut children = Vec::new();
             let (iso_ref, iso_card) = if node_turn == NOT_DEALT {
-                (&self.isomorphism_ref_turn, &self.isomorphism_card_turn)
+                (&game.isomorphism_ref_turn, &game.isomorphism_card_turn)
             } else {
                 (
-                    &self.isomorphism_ref_river[node_turn as usize],
-                    &self.isomorphism_card_river[node_turn as usize & 3],
+                    &game.isomorphism_ref_river[node_turn as usize],
+                    &game.isomorphism_card_river[node_turn as usize & 3],
                 )
             };
 
@@ -195,15 +771,15 @@ impl PostFlopGame {
                 if new_board_mask != board_mask {
                     children.push(card);
                     let indices = if node_turn == NOT_DEALT {
-                        &self.bunching_num_turn[0][card as usize]
+                        &game.bunching_num_turn[0][card as usize]
                     } else {
-                        &self.bunching_num_river[0][card_pair_to_index(node_turn, card)]
+                        &game.bunching_num_river[0][card_pair_to_index(node_turn, card)]
                     };
                     for &index in indices {
                         if index == 0 {
                             continue;
                         }
-                        let slice = &self.bunching_arena[index..index + ip_len];
+                        let slice = &game.bunching_arena[index..index + ip_len];
                         if slice.iter().any(|&n| n > 0.0) {
                             continue 'outer;
                         }
@@ -225,29 +801,20 @@ impl PostFlopGame {
         ((1 << 52) - 1) ^ dead_mask
     }
 
-    /// Returns the current player (0 = OOP, 1 = IP).
-    ///
-    /// If the current node is a terminal node or a chance node, returns an undefined value.
-    #[inline]
-    pub fn current_player(&self) -> usize {
-        if self.state <= State::Uninitialized {
+    pub(super) fn current_player(&self, game: &PostFlopGame) -> usize {
+        if game.state <= State::Uninitialized {
             panic!("Game is not successfully initialized");
         }
 
-        self.node().player()
+        self.node(game).player()
     }
 
-    /// Returns the current board.
-    ///
-    /// The returned vector is of length 3, 4, or 5. The flop cards, the turn card, and the river
-    /// card, if any, are stored in this order.
-    #[inline]
-    pub fn current_board(&self) -> Vec<u8> {
-        if self.state <= State::Uninitialized {
+    pub(super) fn current_board(&self, game: &PostFlopGame) -> Vec<u8> {
+        if game.state <= State::Uninitialized {
             panic!("Game is not successfully initialized");
         }
 
-        let mut ret = self.card_config.flop.to_vec();
+        let mut ret = game.card_config.flop.to_vec();
         if self.turn != NOT_DEALT {
             ret.push(self.turn);
         }
@@ -257,40 +824,26 @@ impl PostFlopGame {
         ret
     }
 
-    /// Plays the given action. Playing an action from a terminal node is not allowed.
-    ///
-    /// - `action`
-    ///   - If the current node is a chance node, the `action` corresponds to the dealt card (see
-    ///     [`Card`] for encoding). If `usize::MAX` is passed, the card is selected as the possible
-    ///     card with the lowest index.
-    ///   - If the current node is not a chance node, plays the `action`-th action of
-    ///     [`available_actions`].
-    ///
-    /// Panics if the memory is not yet allocated or the current node is a terminal node.
-    ///
-    /// **Time complexity:** *O*(#(OOP private hands) + #(IP private hands))
-    ///
-    /// [`available_actions`]: #method.available_actions
-    pub fn play(&mut self, action: usize) {
-        if self.state < State::MemoryAllocated {
+    pub(super) fn play(&mut self, game: &PostFlopGame, action: usize) {
+        if game.state < State::MemoryAllocated {
             panic!("Memory is not allocated");
         }
 
-        if self.is_terminal_node() {
+        if self.is_terminal_node(game) {
             panic!("Terminal node is not allowed");
         }
 
         // chance node
-        if self.is_chance_node() {
+        if self.is_chance_node(game) {
             let is_turn = self.turn == NOT_DEALT;
-            if self.storage_mode == BoardState::Flop
-                || (!is_turn && self.storage_mode == BoardState::Turn)
+            if game.storage_mode == BoardState::Flop
+                || (!is_turn && game.storage_mode == BoardState::Turn)
             {
                 panic!("Storage mode is not compatible");
             }
 
             let actual_card = if action == usize::MAX {
-                self.possible_cards().trailing_zeros() as Card
+                self.possible_cards(game).trailing_zeros() as Card
             } else {
                 action as Card
             };
@@ -308,7 +861,7 @@ impl PostFlopGame {
                 actual_card
             };
 
-            let actions = self.available_actions();
+            let actions = self.available_actions(game);
             let mut action_index = usize::MAX;
 
             // find the action index from available actions
@@ -321,12 +874,12 @@ impl PostFlopGame {
 
             // find the action index from isomorphic chances
             if action_index == usize::MAX {
-                let node = self.node();
-                let isomorphism = self.isomorphic_chances(&node);
+                let node = self.node(game);
+                let isomorphism = game.isomorphic_chances(&node);
                 let isomorphic_cards = if node.turn == NOT_DEALT {
-                    &self.isomorphism_card_turn
+                    &game.isomorphism_card_turn
                 } else {
-                    &self.isomorphism_card_river[node.turn as usize & 3]
+                    &game.isomorphism_card_river[node.turn as usize & 3]
                 };
                 for (i, &repr_index) in isomorphism.iter().enumerate() {
                     if action_card == isomorphic_cards[i] {
@@ -337,13 +890,13 @@ impl PostFlopGame {
                             }
                             self.turn_swap = Some(action_card & 3);
                         } else {
-                            // `self.turn != self.node().turn` if `self.turn_swap.is_some()`.
+                            // `self.turn != node.turn` if `self.turn_swap.is_some()`.
                             // This is possible only when the flop is monotone.
                             // In this case, there is only one suit that can be swapped and the
                             // following code works correctly.
                             self.river_swap = Some((
                                 self.turn & 3,
-                                self.isomorphism_card_river[self.turn as usize & 3][i] & 3,
+                                game.isomorphism_card_river[self.turn as usize & 3][i] & 3,
                             ));
                         }
                         break;
@@ -357,7 +910,7 @@ impl PostFlopGame {
             }
 
             // update the state
-            let node_index = self.node_index(&self.node().play(action_index));
+            let node_index = game.node_index(&self.node(game).play(action_index));
             self.node_history.push(node_index);
             if is_turn {
                 self.turn = actual_card;
@@ -366,78 +919,115 @@ impl PostFlopGame {
             }
 
             // update the weights
-            self.assign_zero_weights();
+            self.assign_zero_weights(game);
         }
         // player node
         else {
             // panic if the action is invalid
-            let node = self.node();
+            let node = self.node(game);
             if action >= node.num_actions() {
                 panic!("Invalid action");
             }
 
             let player = node.player();
-            let num_hands = self.num_private_hands(player);
+            let num_hands = game.num_private_hands(player);
 
             // update the weights
             if node.num_actions() > 1 {
-                let strategy = self.strategy();
+                let strategy = self.strategy(game);
                 let weights = row(&strategy, action, num_hands);
                 mul_slice(&mut self.weights[player], weights);
             }
 
             // cache the counterfactual values
-            let node = self.node();
-            let vec = if self.is_compression_enabled {
-                let slice = row(node.cfvalues_compressed(), action, num_hands);
-                let scale = node.cfvalue_scale();
-                decode_signed_slice(slice, scale)
-            } else {
-                row(node.cfvalues(), action, num_hands).to_vec()
-            };
-            self.cfvalues_cache[player].copy_from_slice(&vec);
+            let node = self.node(game);
+            let precision = game.storage_precision(&node).cfvalues;
+            let cfvalues = node.decoded_cfvalues(precision);
+            self.cfvalues_cache[player].copy_from_slice(row(&cfvalues, action, num_hands));
 
             // update the bet amounts
-            let node = self.node();
-            match node.play(action).prev_action {
-                Action::Call => {
-                    self.total_bet_amount[player] = self.total_bet_amount[player ^ 1];
-                }
-                Action::Bet(amount) | Action::Raise(amount) | Action::AllIn(amount) => {
-                    let prev_bet_amount = match node.prev_action {
-                        Action::Bet(a) | Action::Raise(a) | Action::AllIn(a) => a,
-                        _ => 0,
-                    };
-                    let to_call = self.total_bet_amount[player ^ 1] - self.total_bet_amount[player];
-                    self.total_bet_amount[player] += amount - prev_bet_amount + to_call;
+            let node = self.node(game);
+            update_bet_amount(&mut self.total_bet_amount, &node, action);
+
+            // update the node
+            let node_index = game.node_index(&self.node(game).play(action));
+            self.node_history.push(node_index);
+        }
+
+        self.action_history.push(action);
+        self.is_normalized_weight_cached = false;
+    }
+
+    pub(super) fn play_action(
+        &mut self,
+        game: &PostFlopGame,
+        action: Action,
+    ) -> Result<(), String> {
+        if game.state < State::MemoryAllocated {
+            panic!("Memory is not allocated");
+        }
+
+        if self.is_terminal_node(game) {
+            return Err(format!("{action:?} is played at a terminal node"));
+        }
+
+        if self.is_chance_node(game) {
+            return match action {
+                Action::Chance(card)
+                    if card < 52 && self.possible_cards(game) & (1 << card) != 0 =>
+                {
+                    self.play(game, card as usize);
+                    Ok(())
                 }
-                _ => {}
+                Action::Chance(_) => Err(format!("{action:?} cannot be dealt")),
+                _ => Err(format!("{action:?} is played at a chance node")),
+            };
+        }
+
+        let actions = self.available_actions(game);
+        let index = actions.iter().position(|&a| a == action).or_else(|| {
+            let target = bet_amount(action)?;
+            actions.iter().position(|&a| bet_amount(a) == Some(target))
+        });
+
+        match index {
+            Some(index) => {
+                self.play(game, index);
+                Ok(())
             }
+            None => match self.nearest_action(game, action) {
+                Some(nearest) => Err(format!(
+                    "{action:?} is not available (nearest: {nearest:?})"
+                )),
+                None => Err(format!("{action:?} is not available")),
+            },
+        }
+    }
 
-            // update the node
-            let node_index = self.node_index(&self.node().play(action));
-            self.node_history.push(node_index);
+    pub(super) fn nearest_action(&self, game: &PostFlopGame, action: Action) -> Option<Action> {
+        if self.is_terminal_node(game) {
+            panic!("Terminal node is not allowed");
         }
 
-        self.action_history.push(action);
-        self.is_normalized_weight_cached = false;
+        if self.is_chance_node(game) {
+            panic!("Chance node is not allowed");
+        }
+
+        let actions = self.available_actions(game);
+        if actions.contains(&action) {
+            return Some(action);
+        }
+
+        let target = bet_amount(action)?;
+        actions
+            .into_iter()
+            .filter_map(|a| bet_amount(a).map(|amount| (a, amount)))
+            .min_by_key(|&(_, amount)| ((amount - target).abs(), amount))
+            .map(|(a, _)| a)
     }
 
-    /// Computes the normalized weights and caches them.
-    ///
-    /// After mutating the current node, this method must be called once before calling
-    /// [`normalized_weights`], [`equity`], [`expected_values`], or [`expected_values_detail`].
-    ///
-    /// **Time complexity:**
-    /// - (no bunching) *O*(#(OOP private hands) + #(IP private hands))
-    /// - (bunching) *O*(#(OOP private hands) * #(IP private hands))
-    ///
-    /// [`normalized_weights`]: #method.normalized_weights
-    /// [`equity`]: #method.equity
-    /// [`expected_values`]: #method.expected_values
-    /// [`expected_values_detail`]: #method.expected_values_detail
-    pub fn cache_normalized_weights(&mut self) {
-        if self.state <= State::Uninitialized {
+    pub(super) fn cache_normalized_weights(&mut self, game: &PostFlopGame) {
+        if game.state <= State::Uninitialized {
             panic!("Game is not successfully initialized");
         }
 
@@ -446,7 +1036,7 @@ impl PostFlopGame {
         }
 
         // no bunching
-        if self.bunching_num_dead_cards == 0 {
+        if game.bunching_num_dead_cards == 0 {
             let mut board_mask: u64 = 0;
             if self.turn != NOT_DEALT {
                 board_mask |= 1 << self.turn;
@@ -461,7 +1051,7 @@ impl PostFlopGame {
             for player in 0..2 {
                 let weight_sum_player = &mut weight_sum[player];
                 let weight_sum_minus_player = &mut weight_sum_minus[player];
-                self.private_cards[player]
+                game.private_cards[player]
                     .iter()
                     .zip(self.weights[player].iter())
                     .for_each(|(&(c1, c2), &w)| {
@@ -476,8 +1066,8 @@ impl PostFlopGame {
             }
 
             for player in 0..2 {
-                let player_cards = &self.private_cards[player];
-                let same_hand_index = &self.same_hand_index[player];
+                let player_cards = &game.private_cards[player];
+                let same_hand_index = &game.same_hand_index[player];
                 let player_weights = &self.weights[player];
                 let opponent_weights = &self.weights[player ^ 1];
                 let opponent_weight_sum = weight_sum[player ^ 1];
@@ -514,28 +1104,28 @@ impl PostFlopGame {
             } else {
                 weights_buf[0].extend_from_slice(&self.weights[0]);
                 weights_buf[1].extend_from_slice(&self.weights[1]);
-                self.apply_swap(&mut weights_buf[0], 0, true);
-                self.apply_swap(&mut weights_buf[1], 1, true);
+                self.apply_swap(game, &mut weights_buf[0], 0, true);
+                self.apply_swap(game, &mut weights_buf[1], 1, true);
                 &weights_buf
             };
 
             for player in 0..2 {
-                let node = self.node();
+                let node = self.node(game);
                 let indices = if node.river != NOT_DEALT {
-                    &self.bunching_num_river[player][card_pair_to_index(node.turn, node.river)]
+                    &game.bunching_num_river[player][card_pair_to_index(node.turn, node.river)]
                 } else if node.turn != NOT_DEALT {
-                    &self.bunching_num_turn[player][node.turn as usize]
+                    &game.bunching_num_turn[player][node.turn as usize]
                 } else {
-                    &self.bunching_num_flop[player]
+                    &game.bunching_num_flop[player]
                 };
 
-                let opponent_len = self.num_private_hands(player ^ 1);
+                let opponent_len = game.num_private_hands(player ^ 1);
                 let mut normalized_weights = indices
                     .iter()
                     .zip(weights[player].iter())
                     .map(|(&index, &w)| {
                         if index != 0 {
-                            let slice = &self.bunching_arena[index..index + opponent_len];
+                            let slice = &game.bunching_arena[index..index + opponent_len];
                             w * inner_product(&weights[player ^ 1], slice)
                         } else {
                             0.0
@@ -543,7 +1133,7 @@ impl PostFlopGame {
                     })
                     .collect::<Vec<_>>();
 
-                self.apply_swap(&mut normalized_weights, player, false);
+                self.apply_swap(game, &mut normalized_weights, player, false);
                 self.normalized_weights[player] = normalized_weights;
             }
         }
@@ -551,34 +1141,16 @@ impl PostFlopGame {
         self.is_normalized_weight_cached = true;
     }
 
-    /// Returns the weights of each private hand of the given player.
-    ///
-    /// If a hand overlaps with the board, returns 0.0.
-    ///
-    /// **Time complexity:** *O*(1).
-    #[inline]
-    pub fn weights(&self, player: usize) -> &[f32] {
-        if self.state <= State::Uninitialized {
+    pub(super) fn weights(&self, game: &PostFlopGame, player: usize) -> &[f32] {
+        if game.state <= State::Uninitialized {
             panic!("Game is not successfully initialized");
         }
 
         &self.weights[player]
     }
 
-    /// Returns the normalized weights of each private hand of the given player.
-    ///
-    /// The "normalized weights" represent the actual number of combinations that the player is
-    /// holding each hand.
-    ///
-    /// After mutating the current node, you must call the [`cache_normalized_weights`] method
-    /// before calling this method.
-    ///
-    /// **Time complexity:** *O*(1).
-    ///
-    /// [`cache_normalized_weights`]: #method.cache_normalized_weights
-    #[inline]
-    pub fn normalized_weights(&self, player: usize) -> &[f32] {
-        if self.state <= State::Uninitialized {
+    pub(super) fn normalized_weights(&self, game: &PostFlopGame, player: usize) -> &[f32] {
+        if game.state <= State::Uninitialized {
             panic!("Game is not successfully initialized");
         }
 
@@ -589,18 +1161,8 @@ impl PostFlopGame {
         &self.normalized_weights[player]
     }
 
-    /// Returns the equity of each private hand of the given player.
-    ///
-    /// After mutating the current node, you must call the [`cache_normalized_weights`] method
-    /// before calling this method.
-    ///
-    /// **Time complexity:**
-    /// - (no bunching) *O*(#(possible 5-card boards) * (#(OOP private hands) + #(IP private hands))).
-    /// - (bunching) *O*(#(OOP private hands) * #(IP private hands)).
-    ///
-    /// [`cache_normalized_weights`]: #method.cache_normalized_weights
-    pub fn equity(&self, player: usize) -> Vec<f32> {
-        if self.state <= State::Uninitialized {
+    pub(super) fn equity(&self, game: &PostFlopGame, player: usize) -> Vec<f32> {
+        if game.state <= State::Uninitialized {
             panic!("Game is not successfully initialized");
         }
 
@@ -608,406 +1170,187 @@ impl PostFlopGame {
             panic!("Normalized weights are not cached");
         }
 
-        let num_hands = self.num_private_hands(player);
+        let num_hands = game.num_private_hands(player);
 
-        let tmp = if self.bunching_num_dead_cards == 0 {
+        let tmp = if game.bunching_num_dead_cards == 0 {
             let mut tmp = vec![0.0; num_hands];
+            let weights = &self.weights[player ^ 1];
             if self.river != NOT_DEALT {
-                self.equity_internal(&mut tmp, player, self.turn, self.river, 0.5);
+                game.equity_internal(&mut tmp, player, self.turn, self.river, weights, 0.5);
             } else if self.turn != NOT_DEALT {
                 for river in 0..52 {
                     if self.turn != river {
-                        self.equity_internal(&mut tmp, player, self.turn, river, 0.5 / 44.0);
+                        let amount = 0.5 / 44.0;
+                        game.equity_internal(&mut tmp, player, self.turn, river, weights, amount);
                     }
                 }
             } else {
                 for turn in 0..52 {
                     for river in turn + 1..52 {
-                        self.equity_internal(&mut tmp, player, turn, river, 1.0 / (45.0 * 44.0));
+                        let amount = 1.0 / (45.0 * 44.0);
+                        game.equity_internal(&mut tmp, player, turn, river, weights, amount);
                     }
                 }
             }
-            tmp.into_iter().map(|v| v as f32).collect()
-        } else {
-            let mut tmp = self.equity_internal_bunching(player);
-            self.apply_swap(&mut tmp, player, false);
-            tmp
-        };
-
-        tmp.iter()
-            .zip(self.weights[player].iter())
-            .zip(self.normalized_weights[player].iter())
-            .map(|((&v, &w_raw), &w_normalized)| {
-                if w_normalized > 0.0 {
-                    v * (w_raw / w_normalized) + 0.5
-                } else {
-                    0.0
-                }
-            })
-            .collect()
-    }
-
-    /// Returns the expected values of each private hand of the given player.
-    ///
-    /// Panics if the game is not solved.
-    ///
-    /// After mutating the current node, you must call the [`cache_normalized_weights`] method
-    /// before calling this method.
-    ///
-    /// **Time complexity:** see [`expected_values_detail`].
-    ///
-    /// [`cache_normalized_weights`]: #method.cache_normalized_weights
-    /// [`expected_values_detail`]: #method.expected_values_detail
-    pub fn expected_values(&self, player: usize) -> Vec<f32> {
-        if self.state != State::Solved {
-            panic!("Game is not solved");
-        }
-
-        if !self.is_normalized_weight_cached {
-            panic!("Normalized weights are not cached");
-        }
-
-        let expected_value_detail = self.expected_values_detail(player);
-
-        if self.is_terminal_node() || self.is_chance_node() || self.current_player() != player {
-            return expected_value_detail;
-        }
-
-        let num_actions = self.node().num_actions();
-        let num_hands = self.num_private_hands(player);
-        let strategy = self.strategy();
-
-        let mut ret = Vec::with_capacity(num_hands);
-        for i in 0..num_hands {
-            let mut expected_value = 0.0;
-            for j in 0..num_actions {
-                let index = i + j * num_hands;
-                expected_value += expected_value_detail[index] * strategy[index];
-            }
-            ret.push(expected_value);
-        }
-
-        ret
-    }
-
-    /// Returns the expected values of each action of each private hand of the given player.
-    ///
-    /// If the given player is the current player, the return value is a vector of the length of
-    /// `#(actions) * #(private hands)`. The expected value of the `i`-th action with the `j`-th
-    /// private hand is stored in the `i * #(private hands) + j`-th element.
-    ///
-    /// Otherwise, this method is the same as the [`expected_values`] method, so the return vector
-    /// is the length of `#(private hands)`.
-    ///
-    /// Panics if the game is not solved.
-    ///
-    /// After mutating the current node, you must call the [`cache_normalized_weights`] method
-    /// before calling this method.
-    ///
-    /// **Time complexity:**
-    /// - (with bunching and the current node is terminal) *O*(#(OOP private hands) * #(IP private hands)).
-    /// - (otherwise) *O*(#(actions) * #(private hands)).
-    ///
-    /// [`expected_values`]: #method.expected_value
-    /// [`cache_normalized_weights`]: #method.cache_normalized_weights
-    pub fn expected_values_detail(&self, player: usize) -> Vec<f32> {
-        if self.state != State::Solved {
-            panic!("Game is not solved");
-        }
-
-        if !self.is_normalized_weight_cached {
-            panic!("Normalized weights are not cached");
-        }
-
-        let node = self.node();
-        let num_hands = self.num_private_hands(player);
-
-        let mut chance_factor = 1;
-        if self.card_config.turn == NOT_DEALT && self.turn != NOT_DEALT {
-            chance_factor *= 45 - self.bunching_num_dead_cards;
-        }
-        if self.card_config.river == NOT_DEALT && self.river != NOT_DEALT {
-            chance_factor *= 44 - self.bunching_num_dead_cards;
-        }
-
-        let num_combinations = match self.bunching_num_dead_cards {
-            0 => self.num_combinations,
-            _ => self.bunching_num_combinations,
-        };
-
-        let mut have_actions = false;
-        let mut normalizer = (num_combinations * chance_factor as f64) as f32;
-
-        let mut ret = if node.is_terminal() {
-            normalizer = num_combinations as f32;
-            let mut ret = Vec::with_capacity(num_hands);
-            let mut cfreach = self.weights[player ^ 1].clone();
-            self.apply_swap(&mut cfreach, player ^ 1, true);
-            self.evaluate(ret.spare_capacity_mut(), &node, player, &cfreach);
-            unsafe { ret.set_len(num_hands) };
-            ret
-        } else if node.is_chance() && node.cfvalue_storage_player() == Some(player) {
-            if self.is_compression_enabled {
-                let slice = node.cfvalues_chance_compressed();
-                let scale = node.cfvalue_chance_scale();
-                decode_signed_slice(slice, scale)
-            } else {
-                node.cfvalues_chance().to_vec()
-            }
-        } else if node.has_cfvalues_ip() && player == PLAYER_IP as usize {
-            if self.is_compression_enabled {
-                let slice = node.cfvalues_ip_compressed();
-                let scale = node.cfvalue_ip_scale();
-                decode_signed_slice(slice, scale)
-            } else {
-                node.cfvalues_ip().to_vec()
-            }
-        } else if player == self.current_player() {
-            have_actions = true;
-            if self.is_compression_enabled {
-                let slice = node.cfvalues_compressed();
-                let scale = node.cfvalue_scale();
-                decode_signed_slice(slice, scale)
-            } else {
-                node.cfvalues().to_vec()
-            }
-        } else {
-            self.cfvalues_cache[player].to_vec()
-        };
-
-        let starting_pot = self.tree_config.starting_pot;
-        let total_bet_amount = self.total_bet_amount();
-        let bias = (total_bet_amount[player] - total_bet_amount[player ^ 1]).max(0);
-
-        ret.chunks_exact_mut(num_hands)
-            .enumerate()
-            .for_each(|(action, row)| {
-                let is_fold = have_actions && self.node().play(action).prev_action == Action::Fold;
-                self.apply_swap(row, player, false);
-                row.iter_mut()
-                    .zip(self.weights[player].iter())
-                    .zip(self.normalized_weights[player].iter())
-                    .for_each(|((v, &w_raw), &w_normalized)| {
-                        if is_fold || w_normalized == 0.0 {
-                            *v = 0.0;
-                        } else {
-                            *v *= normalizer * (w_raw / w_normalized);
-                            *v += starting_pot as f32 * 0.5 + (self.node().amount + bias) as f32;
-                        }
-                    });
-            });
-
-        ret
-    }
-
-    /// Returns the strategy of the current player.
-    ///
-    /// The return value is a vector of the length of `#(actions) * #(private hands)`.
-    /// The probability of the `i`-th action with the `j`-th private hand is stored in the
-    /// `i * #(private hands) + j`-th element.
-    ///
-    /// If a hand overlaps with the board, an undefined value is returned.
-    ///
-    /// Panics if the current node is a terminal node or a chance node. Also, panics if the memory
-    /// is not yet allocated.
-    ///
-    /// **Time complexity:** *O*(#(actions) * #(private hands)).
-    pub fn strategy(&self) -> Vec<f32> {
-        if self.state < State::MemoryAllocated {
-            panic!("Memory is not allocated");
-        }
-
-        if self.is_terminal_node() {
-            panic!("Terminal node is not allowed");
-        }
-
-        if self.is_chance_node() {
-            panic!("Chance node is not allowed");
-        }
-
-        let node = self.node();
-        let player = self.current_player();
-        let num_actions = node.num_actions();
-        let num_hands = self.num_private_hands(player);
-
-        let mut ret = if self.is_compression_enabled {
-            normalized_strategy_compressed(node.strategy_compressed(), num_actions)
-        } else {
-            normalized_strategy(node.strategy(), num_actions)
-        };
-
-        let locking = self.locking_strategy(&node);
-        apply_locking_strategy(&mut ret, locking);
-
-        ret.chunks_exact_mut(num_hands).for_each(|chunk| {
-            self.apply_swap(chunk, player, false);
-        });
-
-        ret
-    }
-
-    /// Returns the total bet amount of each player (OOP, IP).
-    #[inline]
-    pub fn total_bet_amount(&self) -> [i32; 2] {
-        self.total_bet_amount
-    }
-
-    /// Locks the strategy of the current node.
-    ///
-    /// The `strategy` argument must be a slice of the length of `#(actions) * #(private hands)`.
-    ///
-    /// - A negative value is treated as a zero.
-    /// - If the `i * #(private hands) + j`-th element of the `strategy` is positive for some `i`,
-    ///   the `j`-th private hand will be locked. The probability for each action will be normalized
-    ///   so that their sum is 1.0.
-    /// - If the `i * #(private hands) + j`-th element of the `strategy` is not positive for all
-    ///   `i`, the `j`-th private hand will not be locked. That is, the solver can adjust the
-    ///   strategy of the `j`-th private hand.
-    ///
-    /// This method must be called after allocating memory and before solving the game.
-    /// Panics if the memory is not yet allocated or the game is already solved.
-    /// Also, panics if the current node is a terminal node or a chance node.
-    pub fn lock_current_strategy(&mut self, strategy: &[f32]) {
-        if self.state < State::MemoryAllocated {
-            panic!("Memory is not allocated");
-        }
+            tmp.into_iter().map(|v| v as f32).collect()
+        } else {
+            let mut tmp = self.equity_internal_bunching(game, player);
+            self.apply_swap(game, &mut tmp, player, false);
+            tmp
+        };
 
-        if self.state == State::Solved {
-            panic!("Game is already solved");
-        }
+        tmp.iter()
+            .zip(self.weights[player].iter())
+            .zip(self.normalized_weights[player].iter())
+            .map(|((&v, &w_raw), &w_normalized)| {
+                if w_normalized > 0.0 {
+                    v * (w_raw / w_normalized) + 0.5
+                } else {
+                    0.0
+                }
+            })
+            .collect()
+    }
 
-        if self.is_terminal_node() {
-            panic!("Terminal node is not allowed");
+    pub(super) fn expected_values(&self, game: &PostFlopGame, player: usize) -> Vec<f32> {
+        if game.state != State::Solved {
+            panic!("Game is not solved");
         }
 
-        if self.is_chance_node() {
-            panic!("Chance node is not allowed");
+        if !self.is_normalized_weight_cached {
+            panic!("Normalized weights are not cached");
         }
 
-        let mut node = self.node();
-        let player = self.current_player();
-        let num_actions = node.num_actions();
-        let num_hands = self.num_private_hands(player);
+        let expected_value_detail = self.expected_values_detail(game, player);
 
-        if strategy.len() != num_actions * num_hands {
-            panic!("Invalid strategy length");
+        if self.is_terminal_node(game)
+            || self.is_chance_node(game)
+            || self.current_player(game) != player
+        {
+            return expected_value_detail;
         }
 
-        let mut locking = vec![-1.0; num_actions * num_hands];
-
-        for hand in 0..num_hands {
-            let mut sum = 0.0;
-            let mut lock = false;
-
-            for action in 0..num_actions {
-                let freq = strategy[action * num_hands + hand];
-                if freq > 0.0 {
-                    sum += freq as f64;
-                    lock = true;
-                }
-            }
+        let num_actions = self.node(game).num_actions();
+        let num_hands = game.num_private_hands(player);
+        let strategy = self.strategy(game);
 
-            if lock {
-                for action in 0..num_actions {
-                    let freq = strategy[action * num_hands + hand].max(0.0) as f64;
-                    locking[action * num_hands + hand] = (freq / sum) as f32;
-                }
+        let mut ret = Vec::with_capacity(num_hands);
+        for i in 0..num_hands {
+            let mut expected_value = 0.0;
+            for j in 0..num_actions {
+                let index = i + j * num_hands;
+                expected_value += expected_value_detail[index] * strategy[index];
             }
+            ret.push(expected_value);
         }
 
-        locking.chunks_exact_mut(num_hands).for_each(|chunk| {
-            self.apply_swap(chunk, player, true);
-        });
-
-        node.is_locked = true;
-        let index = self.node_index(&node);
-        self.locking_strategy.insert(index, locking);
+        ret
     }
 
-    /// Unlocks the strategy of the current node.
-    ///
-    /// This method must be called after allocating memory and before solving the game.
-    /// Panics if the memory is not yet allocated or the game is already solved.
-    /// Also, panics if the current node is a terminal node or a chance node.
-    #[inline]
-    pub fn unlock_current_strategy(&mut self) {
-        if self.state < State::MemoryAllocated {
-            panic!("Memory is not allocated");
+    pub(super) fn expected_values_detail(&self, game: &PostFlopGame, player: usize) -> Vec<f32> {
+        if game.state != State::Solved {
+            panic!("Game is not solved");
         }
 
-        if self.state == State::Solved {
-            panic!("Game is already solved");
+        if !self.is_normalized_weight_cached {
+            panic!("Normalized weights are not cached");
         }
 
-        if self.is_terminal_node() {
-            panic!("Terminal node is not allowed");
-        }
+        let node = self.node(game);
+        let num_hands = game.num_private_hands(player);
 
-        if self.is_chance_node() {
-            panic!("Chance node is not allowed");
+        let mut chance_factor = 1;
+        if game.card_config.turn == NOT_DEALT && self.turn != NOT_DEALT {
+            chance_factor *= 45 - game.bunching_num_dead_cards;
         }
-
-        let mut node = self.node();
-        if !node.is_locked {
-            return;
+        if game.card_config.river == NOT_DEALT && self.river != NOT_DEALT {
+            chance_factor *= 44 - game.bunching_num_dead_cards;
         }
 
-        node.is_locked = false;
-        let index = self.node_index(&node);
-        self.locking_strategy.remove(&index);
+        let num_combinations = match game.bunching_num_dead_cards {
+            0 => game.num_combinations,
+            _ => game.bunching_num_combinations,
+        };
+
+        let mut have_actions = false;
+        let mut normalizer = (num_combinations * chance_factor as f64) as f32;
+
+        let mut ret = if node.is_terminal() {
+            normalizer = num_combinations as f32;
+            let mut ret = Vec::with_capacity(num_hands);
+            let mut cfreach = self.weights[player ^ 1].clone();
+            self.apply_swap(game, &mut cfreach, player ^ 1, true);
+            game.evaluate(ret.spare_capacity_mut(), &node, player, &cfreach);
+            unsafe { ret.set_len(num_hands) };
+            ret
+        } else if node.is_chance() && node.cfvalue_storage_player() == Some(player) {
+            node.decoded_cfvalues_chance(game.storage_precision(&node).cfvalues)
+        } else if node.has_cfvalues_ip() && player == PLAYER_IP as usize {
+            node.decoded_cfvalues_ip(game.storage_precision(&node).cfvalues)
+        } else if player == self.current_player(game) {
+            have_actions = true;
+            node.decoded_cfvalues(game.storage_precision(&node).cfvalues)
+        } else {
+            self.cfvalues_cache[player].to_vec()
+        };
+
+        let starting_pot = game.tree_config.starting_pot;
+        let total_bet_amount = self.total_bet_amount();
+        let bias = (total_bet_amount[player] - total_bet_amount[player ^ 1]).max(0);
+
+        ret.chunks_exact_mut(num_hands)
+            .enumerate()
+            .for_each(|(action, row)| {
+                let is_fold =
+                    have_actions && self.node(game).play(action).prev_action == Action::Fold;
+                self.apply_swap(game, row, player, false);
+                row.iter_mut()
+                    .zip(self.weights[player].iter())
+                    .zip(self.normalized_weights[player].iter())
+                    .for_each(|((v, &w_raw), &w_normalized)| {
+                        if is_fold || w_normalized == 0.0 {
+                            *v = 0.0;
+                        } else {
+                            *v *= normalizer * (w_raw / w_normalized);
+                            *v +=
+                                starting_pot as f32 * 0.5 + (self.node(game).amount + bias) as f32;
+                        }
+                    });
+            });
+
+        ret
     }
 
-    /// Returns the locking strategy of the current node.
-    ///
-    /// If the current node is not locked, `None` is returned.
-    ///
-    /// Otherwise, returns a reference to the vector of the length of
-    /// `#(actions) * #(private hands)`.
-    /// The probability of the `i`-th action with the `j`-th private hand is stored in the
-    /// `i * #(private hands) + j`-th element.
-    /// If the `j`-th private hand is not locked, returns `-1.0` for all `i`.
-    #[inline]
-    pub fn current_locking_strategy(&self) -> Option<Vec<f32>> {
-        if self.state < State::MemoryAllocated {
+    pub(super) fn strategy(&self, game: &PostFlopGame) -> Vec<f32> {
+        if game.state < State::MemoryAllocated {
             panic!("Memory is not allocated");
         }
 
-        if self.is_terminal_node() {
+        if self.is_terminal_node(game) {
             panic!("Terminal node is not allowed");
         }
 
-        if self.is_chance_node() {
+        if self.is_chance_node(game) {
             panic!("Chance node is not allowed");
         }
 
-        let index = self.node_index(&self.node());
-        self.locking_strategy.get(&index).map(|s| {
-            let mut ret = s.clone();
-            let player = self.current_player();
-            let num_hands = self.num_private_hands(player);
-            ret.chunks_exact_mut(num_hands).for_each(|chunk| {
-                self.apply_swap(chunk, player, false);
-            });
-            ret
-        })
+        self.strategy_at(game, &self.node(game))
     }
 
-    /// Returns the reference to the current node.
     #[inline]
-    fn node(&self) -> MutexGuardLike<PostFlopNode> {
-        self.node_arena[self.node_history.last().cloned().unwrap_or(0)].lock()
+    pub(super) fn total_bet_amount(&self) -> [i32; 2] {
+        self.total_bet_amount
     }
 
-    /// Returns the index of the given node.
+    /// Returns the reference to the current node.
     #[inline]
-    pub(super) fn node_index(&self, node: &PostFlopNode) -> usize {
-        let node_ptr = node as *const _ as *const MutexLike<PostFlopNode>;
-        unsafe { node_ptr.offset_from(self.node_arena.as_ptr()) as usize }
+    pub(super) fn node<'a>(&self, game: &'a PostFlopGame) -> MutexGuardLike<'a, PostFlopNode> {
+        game.node_arena[self.node_history.last().cloned().unwrap_or(0)].lock()
     }
 
     /// Assigns zero weights to the hands that are not possible.
-    pub(super) fn assign_zero_weights(&mut self) {
-        if self.bunching_num_dead_cards == 0 {
+    pub(super) fn assign_zero_weights(&mut self, game: &PostFlopGame) {
+        if game.bunching_num_dead_cards == 0 {
             let mut board_mask: u64 = 0;
             if self.turn != NOT_DEALT {
                 board_mask |= 1 << self.turn;
@@ -1019,7 +1362,7 @@ impl PostFlopGame {
             for player in 0..2 {
                 let mut dead_mask: u64 = (1 << 52) - 1;
 
-                for &(c1, c2) in &self.private_cards[player ^ 1] {
+                for &(c1, c2) in &game.private_cards[player ^ 1] {
                     let mask: u64 = (1 << c1) | (1 << c2);
                     if mask & board_mask == 0 {
                         dead_mask &= mask;
@@ -1031,7 +1374,7 @@ impl PostFlopGame {
 
                 dead_mask |= board_mask;
 
-                self.private_cards[player]
+                game.private_cards[player]
                     .iter()
                     .zip(self.weights[player].iter_mut())
                     .for_each(|(&(c1, c2), w)| {
@@ -1043,14 +1386,14 @@ impl PostFlopGame {
             }
         } else {
             for player in 0..2 {
-                let node = self.node();
-                let opponent_len = self.num_private_hands(player ^ 1);
+                let node = self.node(game);
+                let opponent_len = game.num_private_hands(player ^ 1);
                 let indices = if node.turn == NOT_DEALT {
-                    &self.bunching_num_flop[player]
+                    &game.bunching_num_flop[player]
                 } else if node.river == NOT_DEALT {
-                    &self.bunching_num_turn[player][node.turn as usize]
+                    &game.bunching_num_turn[player][node.turn as usize]
                 } else {
-                    &self.bunching_num_river[player][card_pair_to_index(node.turn, node.river)]
+                    &game.bunching_num_river[player][card_pair_to_index(node.turn, node.river)]
                 };
 
                 let mut weights_buf = Vec::new();
@@ -1058,7 +1401,7 @@ impl PostFlopGame {
                     &mut self.weights[player]
                 } else {
                     weights_buf.extend_from_slice(&self.weights[player]);
-                    self.apply_swap(&mut weights_buf, player, true);
+                    self.apply_swap(game, &mut weights_buf, player, true);
                     &mut weights_buf
                 };
 
@@ -1066,7 +1409,7 @@ impl PostFlopGame {
                     if index == 0 {
                         *w = 0.0;
                     } else {
-                        let slice = &self.bunching_arena[index..index + opponent_len];
+                        let slice = &game.bunching_arena[index..index + opponent_len];
                         if slice.iter().all(|&n| n == 0.0) {
                             *w = 0.0;
                         }
@@ -1074,22 +1417,46 @@ impl PostFlopGame {
                 }
 
                 if self.turn_swap.is_some() || self.river_swap.is_some() {
-                    self.apply_swap(&mut weights_buf, player, false);
+                    self.apply_swap(game, &mut weights_buf, player, false);
                     self.weights[player].copy_from_slice(&weights_buf);
                 }
             }
         }
     }
 
+    /// Returns the strategy of the given player node reached from the current node without
+    /// dealing a card.
+    pub(super) fn strategy_at(&self, game: &PostFlopGame, node: &PostFlopNode) -> Vec<f32> {
+        let player = node.player();
+        let num_hands = game.num_private_hands(player);
+
+        let mut ret = node_strategy(game, node);
+
+        let locking = game.locking_strategy(node);
+        apply_locking_strategy(&mut ret, locking);
+
+        ret.chunks_exact_mut(num_hands).for_each(|chunk| {
+            self.apply_swap(game, chunk, player, false);
+        });
+
+        ret
+    }
+
     /// Applies the swap.
     #[inline]
-    fn apply_swap(&self, slice: &mut [f32], player: usize, reverse: bool) {
+    pub(super) fn apply_swap(
+        &self,
+        game: &PostFlopGame,
+        slice: &mut [f32],
+        player: usize,
+        reverse: bool,
+    ) {
         let turn_swap = self
             .turn_swap
-            .map(|suit| &self.isomorphism_swap_turn[suit as usize][player]);
+            .map(|suit| &game.isomorphism_swap_turn[suit as usize][player]);
 
         let river_swap = self.river_swap.map(|(turn_suit, suit)| {
-            &self.isomorphism_swap_river[turn_suit as usize][suit as usize][player]
+            &game.isomorphism_swap_river[turn_suit as usize][suit as usize][player]
         });
 
         let swaps = if !reverse {
@@ -1106,105 +1473,31 @@ impl PostFlopGame {
     }
 
     /// Internal method for calculating the equity.
-    fn equity_internal(
-        &self,
-        result: &mut [f64],
-        player: usize,
-        turn: Card,
-        river: Card,
-        amount: f64,
-    ) {
-        let pair_index = card_pair_to_index(turn, river);
-        let hand_strength = &self.hand_strength[pair_index];
-        let player_strength = &hand_strength[player];
-        let opponent_strength = &hand_strength[player ^ 1];
-
-        let player_len = player_strength.len();
-        let opponent_len = opponent_strength.len();
-
-        if player_len == 0 || opponent_len == 0 {
-            return;
-        }
-
-        let player_cards = &self.private_cards[player];
-        let opponent_cards = &self.private_cards[player ^ 1];
-
-        let opponent_weights = &self.weights[player ^ 1];
-        let mut weight_sum = 0.0;
-        let mut weight_minus = [0.0; 52];
-
-        let valid_player_strength = &player_strength[1..player_len - 1];
-        let mut i = 1;
-
-        for &StrengthItem { strength, index } in valid_player_strength {
-            unsafe {
-                while opponent_strength.get_unchecked(i).strength < strength {
-                    let opponent_index = opponent_strength.get_unchecked(i).index as usize;
-                    let (c1, c2) = *opponent_cards.get_unchecked(opponent_index);
-                    let weight_i = *opponent_weights.get_unchecked(opponent_index) as f64;
-                    weight_sum += weight_i;
-                    *weight_minus.get_unchecked_mut(c1 as usize) += weight_i;
-                    *weight_minus.get_unchecked_mut(c2 as usize) += weight_i;
-                    i += 1;
-                }
-                let (c1, c2) = *player_cards.get_unchecked(index as usize);
-                let opponent_weight = weight_sum
-                    - weight_minus.get_unchecked(c1 as usize)
-                    - weight_minus.get_unchecked(c2 as usize);
-                *result.get_unchecked_mut(index as usize) += amount * opponent_weight;
-            }
-        }
-
-        weight_sum = 0.0;
-        weight_minus.fill(0.0);
-        i = opponent_len - 2;
-
-        for &StrengthItem { strength, index } in valid_player_strength.iter().rev() {
-            unsafe {
-                while opponent_strength.get_unchecked(i).strength > strength {
-                    let opponent_index = opponent_strength.get_unchecked(i).index as usize;
-                    let (c1, c2) = *opponent_cards.get_unchecked(opponent_index);
-                    let weight_i = *opponent_weights.get_unchecked(opponent_index) as f64;
-                    weight_sum += weight_i;
-                    *weight_minus.get_unchecked_mut(c1 as usize) += weight_i;
-                    *weight_minus.get_unchecked_mut(c2 as usize) += weight_i;
-                    i -= 1;
-                }
-                let (c1, c2) = *player_cards.get_unchecked(index as usize);
-                let opponent_weight = weight_sum
-                    - weight_minus.get_unchecked(c1 as usize)
-                    - weight_minus.get_unchecked(c2 as usize);
-                *result.get_unchecked_mut(index as usize) -= amount * opponent_weight;
-            }
-        }
-    }
-
-    /// Internal method for calculating the equity.
-    fn equity_internal_bunching(&self, player: usize) -> Vec<f32> {
+    fn equity_internal_bunching(&self, game: &PostFlopGame, player: usize) -> Vec<f32> {
         let mut weights_buf = Vec::new();
         let opponent_weights = if self.turn_swap.is_none() && self.river_swap.is_none() {
             &self.weights[player ^ 1]
         } else {
             weights_buf.extend_from_slice(&self.weights[player ^ 1]);
-            self.apply_swap(&mut weights_buf, player ^ 1, true);
+            self.apply_swap(game, &mut weights_buf, player ^ 1, true);
             &weights_buf
         };
 
-        let node = self.node();
+        let node = self.node(game);
         let opponent_len = opponent_weights.len();
 
         if node.river == NOT_DEALT {
             let indices = if node.turn != NOT_DEALT {
-                &self.bunching_coef_turn[player][node.turn as usize]
+                &game.bunching_coef_turn[player][node.turn as usize]
             } else {
-                &self.bunching_coef_flop[player]
+                &game.bunching_coef_flop[player]
             };
 
             indices
                 .iter()
                 .map(|&index| {
                     if index != 0 {
-                        let slice = &self.bunching_arena[index..index + opponent_len];
+                        let slice = &game.bunching_arena[index..index + opponent_len];
                         0.5 * inner_product(opponent_weights, slice)
                     } else {
                         0.0
@@ -1215,9 +1508,9 @@ impl PostFlopGame {
         // showdown
         else {
             let pair_index = card_pair_to_index(node.turn, node.river);
-            let indices = &self.bunching_num_river[player][pair_index];
-            let player_strength = &self.bunching_strength[pair_index][player];
-            let opponent_strength = &self.bunching_strength[pair_index][player ^ 1];
+            let indices = &game.bunching_num_river[player][pair_index];
+            let player_strength = &game.bunching_strength[pair_index][player];
+            let opponent_strength = &game.bunching_strength[pair_index][player ^ 1];
 
             indices
                 .iter()
@@ -1226,7 +1519,7 @@ impl PostFlopGame {
                     if index != 0 {
                         inner_product_cond(
                             opponent_weights,
-                            &self.bunching_arena[index..index + opponent_len],
+                            &game.bunching_arena[index..index + opponent_len],
                             opponent_strength,
                             strength,
                             0.5,
@@ -1241,3 +1534,32 @@ impl PostFlopGame {
         }
     }
 }
+
+/// Returns the amount of the bet, raise, or all-in action.
+#[inline]
+pub(super) fn bet_amount(action: Action) -> Option<i32> {
+    match action {
+        Action::Bet(amount) | Action::Raise(amount) | Action::AllIn(amount) => Some(amount),
+        _ => None,
+    }
+}
+
+/// Updates the total bet amounts of both players after playing `action` at the player `node`.
+pub(super) fn update_bet_amount(
+    total_bet_amount: &mut [i32; 2],
+    node: &PostFlopNode,
+    action: usize,
+) {
+    let player = node.player();
+    match node.play(action).prev_action {
+        Action::Call => {
+            total_bet_amount[player] = total_bet_amount[player ^ 1];
+        }
+        Action::Bet(amount) | Action::Raise(amount) | Action::AllIn(amount) => {
+            let prev_bet_amount = bet_amount(node.prev_action).unwrap_or(0);
+            let to_call = total_bet_amount[player ^ 1] - total_bet_amount[player];
+            total_bet_amount[player] += amount - prev_bet_amount + to_call;
+        }
+        _ => {}
+    }
+}
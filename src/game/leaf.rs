@@ -0,0 +1,228 @@
+use super::*;
+use std::mem::MaybeUninit;
+
+/// A struct containing the information of a leaf node of a depth-limited game tree.
+///
+/// See [`DepthLimit`] for how leaf nodes are created.
+#[derive(Debug, Clone, Copy)]
+pub struct LeafInfo {
+    /// Flop cards.
+    pub flop: [Card; 3],
+
+    /// Turn card (`NOT_DEALT` if not dealt yet).
+    pub turn: Card,
+
+    /// River card (`NOT_DEALT` if not dealt yet).
+    pub river: Card,
+
+    /// Pot size at the leaf node. Uncalled bets are not included.
+    pub pot: i32,
+
+    /// Remaining effective stack at the leaf node.
+    pub effective_stack: i32,
+
+    /// Number of combinations used to normalize the counterfactual values.
+    pub num_combinations: f64,
+}
+
+/// A trait for evaluating the leaf nodes of a depth-limited game tree.
+///
+/// Leaf nodes are treated as terminal nodes, so [`solve`] works without any modification once the
+/// evaluator is set by [`PostFlopGame::set_leaf_evaluator`].
+///
+/// [`solve`]: crate::solve
+pub trait LeafEvaluator: Send + Sync {
+    /// Computes the counterfactual values of the private hands of `player` at the leaf node.
+    ///
+    /// - `cfreach`: the reach probabilities of the opponent's private hands, in the same order as
+    ///   [`PostFlopGame::private_cards`].
+    /// - `result`: the output buffer, initialized with zeros.
+    ///
+    /// The value of hand `i` must be the sum of `cfreach[j] * payoff(i, j)` over the opponent's
+    /// hands `j` that do not conflict with hand `i` and the board, divided by
+    /// `leaf.num_combinations`. The payoff is measured relative to the state where both players
+    /// have put half of the pot, i.e., winning the whole pot yields `pot / 2`.
+    fn evaluate(
+        &self,
+        game: &PostFlopGame,
+        leaf: &LeafInfo,
+        player: usize,
+        cfreach: &[f32],
+        result: &mut [f32],
+    );
+}
+
+/// A [`LeafEvaluator`] that values each hand by its equity multiplied by a realization factor.
+///
+/// The equity is computed by enumerating all possible runouts, and the rake is not considered.
+/// The realization factor is ignored when the players are all-in.
+///
+/// **Warning**: If the realization factors differ from `1.0`, the game is no longer zero-sum, so
+/// the exploitability is not well-defined.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EquityRealization {
+    /// Realization factor of each player (OOP, IP). `1.0` means that the equity is fully realized.
+    pub factor: [f64; 2],
+}
+
+impl Default for EquityRealization {
+    #[inline]
+    fn default() -> Self {
+        Self { factor: [1.0; 2] }
+    }
+}
+
+impl LeafEvaluator for EquityRealization {
+    fn evaluate(
+        &self,
+        game: &PostFlopGame,
+        leaf: &LeafInfo,
+        player: usize,
+        cfreach: &[f32],
+        result: &mut [f32],
+    ) {
+        let amount = 0.5 * leaf.pot as f64 / leaf.num_combinations;
+        let factor = match leaf.effective_stack {
+            0 => 1.0,
+            _ => self.factor[player],
+        };
+
+        let mut showdown = vec![0.0; result.len()];
+        game.leaf_showdown_values(&mut showdown, leaf, player, cfreach, amount);
+
+        if factor == 1.0 {
+            result
+                .iter_mut()
+                .zip(showdown)
+                .for_each(|(r, v)| *r = v as f32);
+            return;
+        }
+
+        // equity * pot - half_pot = factor * showdown + (factor - 1) * half_pot
+        let mut cfreach_sum = vec![0.0; result.len()];
+        game.leaf_cfreach_sum(&mut cfreach_sum, leaf, player, cfreach);
+
+        for ((r, v), s) in result.iter_mut().zip(showdown).zip(cfreach_sum) {
+            *r = (factor * v + (factor - 1.0) * amount * s) as f32;
+        }
+    }
+}
+
+impl PostFlopGame {
+    /// Sets the evaluator of the leaf nodes of a depth-limited game tree.
+    ///
+    /// If no evaluator is set, [`EquityRealization::default()`] is used. The evaluator is not
+    /// serialized, so it must be set again after loading the game.
+    #[inline]
+    pub fn set_leaf_evaluator<T: LeafEvaluator + 'static>(&mut self, evaluator: T) {
+        self.leaf_evaluator = Some(Box::new(evaluator));
+    }
+
+    /// Resets the evaluator of the leaf nodes to the default one.
+    #[inline]
+    pub fn reset_leaf_evaluator(&mut self) {
+        self.leaf_evaluator = None;
+    }
+
+    /// Evaluates the leaf node.
+    pub(super) fn evaluate_leaf(
+        &self,
+        result: &mut [MaybeUninit<f32>],
+        node: &PostFlopNode,
+        player: usize,
+        cfreach: &[f32],
+    ) {
+        let leaf = LeafInfo {
+            flop: self.card_config.flop,
+            turn: node.turn,
+            river: node.river,
+            pot: self.tree_config.starting_pot + 2 * node.amount,
+            effective_stack: self.tree_config.effective_stack - node.amount,
+            num_combinations: self.num_combinations,
+        };
+
+        result.iter_mut().for_each(|v| {
+            v.write(0.0);
+        });
+
+        let result = unsafe { &mut *(result as *mut _ as *mut [f32]) };
+
+        match &self.leaf_evaluator {
+            Some(evaluator) => evaluator.evaluate(self, &leaf, player, cfreach, result),
+            None => EquityRealization::default().evaluate(self, &leaf, player, cfreach, result),
+        }
+    }
+
+    /// Computes the showdown values averaged over all possible runouts and adds them to `result`.
+    fn leaf_showdown_values(
+        &self,
+        result: &mut [f64],
+        leaf: &LeafInfo,
+        player: usize,
+        cfreach: &[f32],
+        amount: f64,
+    ) {
+        if leaf.river != NOT_DEALT {
+            self.equity_internal(result, player, leaf.turn, leaf.river, cfreach, amount);
+        } else if leaf.turn != NOT_DEALT {
+            for river in 0..52 {
+                if leaf.turn != river {
+                    let amount = amount / 44.0;
+                    self.equity_internal(result, player, leaf.turn, river, cfreach, amount);
+                }
+            }
+        } else {
+            for turn in 0..52 {
+                for river in turn + 1..52 {
+                    let amount = 2.0 * amount / (45.0 * 44.0);
+                    self.equity_internal(result, player, turn, river, cfreach, amount);
+                }
+            }
+        }
+    }
+
+    /// Computes the sum of `cfreach` over the opponent's hands that do not conflict with each hand.
+    fn leaf_cfreach_sum(
+        &self,
+        result: &mut [f64],
+        leaf: &LeafInfo,
+        player: usize,
+        cfreach: &[f32],
+    ) {
+        let valid_indices = if leaf.river != NOT_DEALT {
+            &self.valid_indices_river[card_pair_to_index(leaf.turn, leaf.river)]
+        } else if leaf.turn != NOT_DEALT {
+            &self.valid_indices_turn[leaf.turn as usize]
+        } else {
+            &self.valid_indices_flop
+        };
+
+        let player_cards = &self.private_cards[player];
+        let opponent_cards = &self.private_cards[player ^ 1];
+
+        let mut cfreach_sum = 0.0;
+        let mut cfreach_minus = [0.0; 52];
+
+        for &i in &valid_indices[player ^ 1] {
+            let (c1, c2) = opponent_cards[i as usize];
+            let cfreach_i = cfreach[i as usize] as f64;
+            cfreach_sum += cfreach_i;
+            cfreach_minus[c1 as usize] += cfreach_i;
+            cfreach_minus[c2 as usize] += cfreach_i;
+        }
+
+        let same_hand_index = &self.same_hand_index[player];
+        for &i in &valid_indices[player] {
+            let (c1, c2) = player_cards[i as usize];
+            let same_i = same_hand_index[i as usize];
+            let cfreach_same = match same_i {
+                u16::MAX => 0.0,
+                _ => cfreach[same_i as usize] as f64,
+            };
+            // inclusion-exclusion principle
+            result[i as usize] = cfreach_sum + cfreach_same
+                - cfreach_minus[c1 as usize]
+                - cfreach_minus[c2 as usize];
+        }
+    }
+}
@@ -0,0 +1,759 @@
+use super::*;
+use crate::interface::*;
+use crate::range::*;
+use std::mem;
+
+/// An enum representing the category of a hand on the given board.
+///
+/// The categories are ordered from the strongest to the weakest. Straights and better are
+/// determined by the best five-card hand, while sets, trips, and pairs only count when at least
+/// one hole card participates (e.g., a pair on the board alone is [`HandCategory::HighCard`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HandCategory {
+    /// Straight flush.
+    StraightFlush,
+
+    /// Four of a kind.
+    Quads,
+
+    /// Full house.
+    FullHouse,
+
+    /// Flush.
+    Flush,
+
+    /// Straight.
+    Straight,
+
+    /// Three of a kind with a pocket pair.
+    Set,
+
+    /// Three of a kind with one hole card and a pair on the board.
+    Trips,
+
+    /// Two pair with both hole cards.
+    TwoPair,
+
+    /// Pocket pair higher than all board cards.
+    Overpair,
+
+    /// Pair with the highest board card.
+    TopPair,
+
+    /// Pair with the second highest board card, or pocket pair between the highest and the second
+    /// highest board cards.
+    SecondPair,
+
+    /// Any other pair (including pocket pairs lower than the second highest board card).
+    WeakPair,
+
+    /// No pair made with the hole cards.
+    HighCard,
+}
+
+/// An enum representing a set of hands to which a [`LockRule`] applies.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HandSet {
+    /// Hands of the specified categories.
+    Categories(Vec<HandCategory>),
+
+    /// Hands with a positive weight in the specified range.
+    Range(Box<Range>),
+
+    /// All hands.
+    All,
+}
+
+/// A struct representing a locked node of [`LockSet`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "bincode", derive(Decode, Encode))]
+pub struct LockEntry {
+    /// Line from the root node to the locked node.
+    pub line: Vec<Action>,
+
+    /// Available actions at the locked node.
+    pub actions: Vec<Action>,
+
+    /// Private hands of the player to act.
+    pub hands: Vec<(Card, Card)>,
+
+    /// Locking strategy of the length of `actions.len() * hands.len()`. The probability of the
+    /// `i`-th action with the `j`-th hand is stored in the `i * hands.len() + j`-th element, and
+    /// `-1.0` means that the hand is not locked.
+    pub strategy: Vec<f32>,
+}
+
+/// A portable set of locked strategies, exported by [`PostFlopGame::export_locks`].
+///
+/// This struct can be saved/loaded by [`save_data_to_file`]/[`load_data_from_file`] (requires the
+/// `bincode` feature).
+///
+/// [`save_data_to_file`]: crate::save_data_to_file
+/// [`load_data_from_file`]: crate::load_data_from_file
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "bincode", derive(Decode, Encode))]
+pub struct LockSet {
+    /// Locked nodes.
+    pub entries: Vec<LockEntry>,
+}
+
+impl LockSet {
+    /// Returns the memory usage in bytes.
+    #[inline]
+    pub fn memory_usage(&self) -> u64 {
+        let entry_usage = |e: &LockEntry| {
+            e.line.len() * mem::size_of::<Action>()
+                + e.actions.len() * mem::size_of::<Action>()
+                + e.hands.len() * 2
+                + e.strategy.len() * 4
+        };
+        self.entries.iter().map(entry_usage).sum::<usize>() as u64
+    }
+}
+
+/// A struct representing a rule of [`PostFlopGame::lock_strategy_by_rules`].
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// // bet 100% with sets and two pair
+/// let rule = LockRule {
+///     hands: HandSet::Categories(vec![HandCategory::Set, HandCategory::TwoPair]),
+///     frequencies: vec![(Action::Bet(20), 1.0)],
+///     is_aggregate: false,
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct LockRule {
+    /// Hands to which the rule applies. Hands already matched by a preceding rule are excluded.
+    pub hands: HandSet,
+
+    /// Frequency of each action. Unlisted actions are assigned zero, and the sum must be 1.0.
+    pub frequencies: Vec<(Action, f64)>,
+
+    /// If `false`, every matched hand takes the actions with `frequencies`.
+    ///
+    /// If `true`, `frequencies` is the aggregate frequency over the hands matched by this rule and
+    /// all preceding rules, weighted by the initial range. The matched hands take the same mixed
+    /// strategy so that the aggregate frequency is achieved.
+    pub is_aggregate: bool,
+}
+
+impl HandCategory {
+    /// Classifies the hand on the given board of three to five cards.
+    ///
+    /// # Examples
+    /// ```
+    /// use postflop_solver::*;
+    ///
+    /// let board = flop_from_str("Td9d6h").unwrap();
+    /// let hand = (card_from_str("Ah").unwrap(), card_from_str("Tc").unwrap());
+    /// assert_eq!(HandCategory::classify(hand, &board), HandCategory::TopPair);
+    /// ```
+    pub fn classify(hand: (Card, Card), board: &[Card]) -> Self {
+        let mut rank_count = [0u8; 13];
+        let mut board_rank_count = [0u8; 13];
+        let mut rankset_suit = [0u16; 4];
+
+        for &card in board {
+            board_rank_count[card as usize >> 2] += 1;
+        }
+
+        for card in [hand.0, hand.1].into_iter().chain(board.iter().cloned()) {
+            rank_count[card as usize >> 2] += 1;
+            rankset_suit[card as usize & 3] |= 1 << (card >> 2);
+        }
+
+        let rankset = rankset_suit.iter().fold(0, |acc, &x| acc | x);
+        let count_of = |n: u8| rank_count.iter().filter(|&&c| c == n).count();
+
+        // five-card hands
+        if rankset_suit
+            .iter()
+            .any(|&s| s.count_ones() >= 5 && has_straight(s))
+        {
+            return Self::StraightFlush;
+        }
+        if count_of(4) > 0 {
+            return Self::Quads;
+        }
+        if count_of(3) >= 2 || (count_of(3) == 1 && count_of(2) > 0) {
+            return Self::FullHouse;
+        }
+        if rankset_suit.iter().any(|&s| s.count_ones() >= 5) {
+            return Self::Flush;
+        }
+        if has_straight(rankset) {
+            return Self::Straight;
+        }
+
+        // hands made with the hole cards
+        let (rank1, rank2) = (hand.0 as usize >> 2, hand.1 as usize >> 2);
+        let (count1, count2) = (board_rank_count[rank1], board_rank_count[rank2]);
+
+        let mut board_ranks = (0..13).rev().filter(|&r| board_rank_count[r] > 0);
+        let top_rank = board_ranks.next().unwrap_or(0);
+        let second_rank = board_ranks.next().unwrap_or(0);
+
+        if rank1 == rank2 {
+            if count1 > 0 {
+                Self::Set
+            } else if rank1 > top_rank {
+                Self::Overpair
+            } else if rank1 > second_rank {
+                Self::SecondPair
+            } else {
+                Self::WeakPair
+            }
+        } else if count1 == 2 || count2 == 2 {
+            Self::Trips
+        } else if count1 > 0 && count2 > 0 {
+            Self::TwoPair
+        } else if count1 > 0 || count2 > 0 {
+            let rank = if count1 > 0 { rank1 } else { rank2 };
+            if rank == top_rank {
+                Self::TopPair
+            } else if rank == second_rank {
+                Self::SecondPair
+            } else {
+                Self::WeakPair
+            }
+        } else {
+            Self::HighCard
+        }
+    }
+}
+
+impl HandSet {
+    /// Returns whether the hand is contained in the set.
+    #[inline]
+    pub fn contains(&self, hand: (Card, Card), board: &[Card]) -> bool {
+        match self {
+            Self::Categories(categories) => {
+                categories.contains(&HandCategory::classify(hand, board))
+            }
+            Self::Range(range) => range.get_weight_by_cards(hand.0, hand.1) > 0.0,
+            Self::All => true,
+        }
+    }
+}
+
+/// Returns whether the rank set contains a straight.
+#[inline]
+fn has_straight(rankset: u16) -> bool {
+    const WHEEL: u16 = 0b1_0000_0000_1111;
+    let rankset = rankset as u32;
+    rankset & (rankset << 1) & (rankset << 2) & (rankset << 3) & (rankset << 4) != 0
+        || rankset & WHEEL as u32 == WHEEL as u32
+}
+
+impl PostFlopGame {
+    /// Locks the strategy of the nodes specified by `line`.
+    ///
+    /// `line` is a list of actions from the root node, where the turn and river cards are
+    /// specified by [`Action::Chance`]. `Action::Chance(NOT_DEALT)` is a wildcard that matches
+    /// every possible card (isomorphic cards are grouped into one representative card). The
+    /// `strategy` argument is interpreted in the same way as [`lock_current_strategy`] and is
+    /// applied to all matched nodes.
+    ///
+    /// The current node of the interpreter is restored after locking. Returns the number of locked
+    /// nodes, or `Err` if `line` does not reach a player node or the length of `strategy` is
+    /// invalid. No node is locked if `Err` is returned.
+    ///
+    /// This method must be called after allocating memory and before solving the game.
+    /// Panics if the memory is not yet allocated or the game is already solved.
+    ///
+    /// [`lock_current_strategy`]: #method.lock_current_strategy
+    pub fn lock_strategy_by_line(
+        &mut self,
+        line: &[Action],
+        strategy: &[f32],
+    ) -> Result<usize, String> {
+        self.check_lockable();
+        self.with_history_restored(|game| {
+            game.lock_lines_with(line, |game| {
+                let num_actions = game.available_actions().len();
+                let num_hands = game.num_private_hands(game.current_player());
+                if strategy.len() != num_actions * num_hands {
+                    return Err("Invalid strategy length".to_string());
+                }
+                Ok(strategy.to_vec())
+            })
+        })
+    }
+
+    /// Locks the strategy of the nodes specified by `line` according to the given rules.
+    ///
+    /// `line` is interpreted in the same way as [`lock_strategy_by_line`]. Each hand of the player
+    /// to act is assigned to the first rule whose [`HandSet`] contains it; hands that match no rule
+    /// are not locked. See [`LockRule`] for how the frequencies are interpreted.
+    ///
+    /// The current node of the interpreter is restored after locking. Returns the number of locked
+    /// nodes, or `Err` if `line` does not reach a player node or the rules are invalid (e.g., an
+    /// action is not available, the frequencies do not sum to 1.0, or an aggregate frequency is not
+    /// achievable). No node is locked if `Err` is returned.
+    ///
+    /// This method must be called after allocating memory and before solving the game.
+    /// Panics if the memory is not yet allocated or the game is already solved.
+    ///
+    /// [`lock_strategy_by_line`]: #method.lock_strategy_by_line
+    pub fn lock_strategy_by_rules(
+        &mut self,
+        line: &[Action],
+        rules: &[LockRule],
+    ) -> Result<usize, String> {
+        self.check_lockable();
+        self.with_history_restored(|game| {
+            game.lock_lines_with(line, |game| game.strategy_from_rules(rules))
+        })
+    }
+
+    /// Unlocks the strategy of the nodes specified by `line`.
+    ///
+    /// `line` is interpreted in the same way as [`lock_strategy_by_line`]. The current node of the
+    /// interpreter is restored after unlocking. Returns the number of matched nodes, or `Err` if
+    /// `line` does not reach a player node.
+    ///
+    /// This method must be called after allocating memory and before solving the game.
+    /// Panics if the memory is not yet allocated or the game is already solved.
+    ///
+    /// [`lock_strategy_by_line`]: #method.lock_strategy_by_line
+    pub fn unlock_strategy_by_line(&mut self, line: &[Action]) -> Result<usize, String> {
+        self.check_lockable();
+        self.with_history_restored(|game| {
+            let lines = game.expand_line(line)?;
+            for line in &lines {
+                game.apply_player_line(line)?;
+                game.unlock_current_strategy();
+            }
+            Ok(lines.len())
+        })
+    }
+
+    /// Returns the lines of all locked nodes.
+    ///
+    /// The chance actions of the returned lines are the representative cards.
+    pub fn locked_lines(&self) -> Vec<Vec<Action>> {
+        if self.state < State::MemoryAllocated {
+            panic!("Memory is not allocated");
+        }
+
+        let mut ret = Vec::new();
+        self.locked_nodes_recursive(&self.node_arena[0].lock(), &mut Vec::new(), &mut ret);
+        ret.into_iter().map(|(line, _)| line).collect()
+    }
+
+    /// Exports the locked strategies as a [`LockSet`].
+    ///
+    /// The exported set does not depend on the internal node indices or hand indices, so it can be
+    /// imported into another game with [`import_locks`] as long as the lines are valid there.
+    ///
+    /// [`import_locks`]: #method.import_locks
+    pub fn export_locks(&self) -> LockSet {
+        if self.state < State::MemoryAllocated {
+            panic!("Memory is not allocated");
+        }
+
+        let mut nodes = Vec::new();
+        self.locked_nodes_recursive(&self.node_arena[0].lock(), &mut Vec::new(), &mut nodes);
+
+        let entries = nodes
+            .into_iter()
+            .map(|(line, index)| {
+                let node = self.node_arena[index].lock();
+                let actions = node.children().iter().map(|c| c.lock().prev_action);
+                LockEntry {
+                    line,
+                    actions: actions.collect(),
+                    hands: self.private_cards[node.player()].clone(),
+                    strategy: self.locking_strategy[&index].clone(),
+                }
+            })
+            .collect();
+
+        LockSet { entries }
+    }
+
+    /// Imports the locked strategies exported by [`export_locks`].
+    ///
+    /// The hands that do not exist in this game are ignored, and the hands that do not exist in
+    /// the entry are not locked. Returns `Err` if some line is not valid or the actions of some
+    /// node differ from the entry. No node is locked if `Err` is returned.
+    ///
+    /// This method must be called after allocating memory and before solving the game.
+    /// Panics if the memory is not yet allocated or the game is already solved.
+    ///
+    /// [`export_locks`]: #method.export_locks
+    pub fn import_locks(&mut self, lock_set: &LockSet) -> Result<(), String> {
+        self.check_lockable();
+        self.with_history_restored(|game| {
+            let mut strategies = Vec::with_capacity(lock_set.entries.len());
+            for entry in &lock_set.entries {
+                game.apply_player_line(&entry.line)?;
+                strategies.push(game.strategy_from_entry(entry)?);
+            }
+            for (entry, strategy) in lock_set.entries.iter().zip(&strategies) {
+                game.apply_player_line(&entry.line)?;
+                game.lock_current_strategy(strategy);
+            }
+            Ok(())
+        })
+    }
+
+    /// Sets the aggregate-frequency constraint to the nodes specified by `line`.
+    ///
+    /// `line` is interpreted in the same way as [`lock_strategy_by_line`]. Each element of `bounds`
+    /// is a tuple of `(action, min, max)`, which means that the aggregate frequency of `action`
+    /// must be in `[min, max]`; unlisted actions are unconstrained. The aggregate frequency is
+    /// weighted by the initial range (with the board cards removed), not by the reach
+    /// probabilities, so that the constraint is linear in the strategy.
+    ///
+    /// Unlike locking, the solver chooses which hands take each action: the strategy is adjusted
+    /// to a constrained best response (in the sense of regret matching) every iteration. Locked
+    /// hands of the node are kept fixed and counted in the aggregate frequency.
+    ///
+    /// The current node of the interpreter is restored. Returns the number of constrained nodes, or
+    /// `Err` if `line` does not reach a player node or the bounds are invalid. No node is
+    /// constrained if `Err` is returned.
+    ///
+    /// This method must be called after allocating memory and before solving the game.
+    /// Panics if the memory is not yet allocated or the game is already solved.
+    ///
+    /// [`lock_strategy_by_line`]: #method.lock_strategy_by_line
+    pub fn set_aggregate_constraint(
+        &mut self,
+        line: &[Action],
+        bounds: &[(Action, f64, f64)],
+    ) -> Result<usize, String> {
+        self.check_lockable();
+        self.with_history_restored(|game| {
+            let lines = game.expand_line(line)?;
+            let mut constraints = Vec::with_capacity(lines.len());
+            for line in &lines {
+                game.apply_player_line(line)?;
+                let index = game.node_index(&game.node());
+                constraints.push((index, game.constraint_from_bounds(bounds)?));
+            }
+            game.aggregate_constraints.extend(constraints);
+            Ok(lines.len())
+        })
+    }
+
+    /// Removes the aggregate-frequency constraint from the nodes specified by `line`.
+    ///
+    /// `line` is interpreted in the same way as [`lock_strategy_by_line`]. The current node of the
+    /// interpreter is restored. Returns the number of matched nodes, or `Err` if `line` does not
+    /// reach a player node.
+    ///
+    /// This method must be called after allocating memory and before solving the game.
+    /// Panics if the memory is not yet allocated or the game is already solved.
+    ///
+    /// [`lock_strategy_by_line`]: #method.lock_strategy_by_line
+    pub fn remove_aggregate_constraint(&mut self, line: &[Action]) -> Result<usize, String> {
+        self.check_lockable();
+        self.with_history_restored(|game| {
+            let lines = game.expand_line(line)?;
+            for line in &lines {
+                game.apply_player_line(line)?;
+                let index = game.node_index(&game.node());
+                game.aggregate_constraints.remove(&index);
+            }
+            Ok(lines.len())
+        })
+    }
+
+    /// Returns the aggregate frequency of each action of the current node.
+    ///
+    /// The frequencies are weighted by the initial range (with the board cards removed), which is
+    /// the same definition as [`set_aggregate_constraint`].
+    ///
+    /// Panics if the memory is not yet allocated or the current node is a terminal node or a chance
+    /// node.
+    ///
+    /// [`set_aggregate_constraint`]: #method.set_aggregate_constraint
+    pub fn current_aggregate_frequencies(&self) -> Vec<f32> {
+        let strategy = self.strategy();
+        let weights = self.range_weights();
+        let total = weights.iter().sum::<f64>();
+
+        strategy
+            .chunks_exact(weights.len())
+            .map(|row| {
+                let sum = row.iter().zip(&weights).map(|(&s, &w)| s as f64 * w);
+                (sum.sum::<f64>() / total) as f32
+            })
+            .collect()
+    }
+
+    /// Translates the bounds into the aggregate-frequency constraint of the current node.
+    fn constraint_from_bounds(
+        &self,
+        bounds: &[(Action, f64, f64)],
+    ) -> Result<AggregateConstraint, String> {
+        let actions = self.available_actions();
+        let mut groups: Vec<AggregateBound> = Vec::with_capacity(bounds.len());
+
+        for &(action, min, max) in bounds {
+            let Some(index) = actions.iter().position(|&a| a == action) else {
+                return Err(format!("Action {action:?} is not available"));
+            };
+            if !(0.0 <= min && min <= max && max <= 1.0) {
+                return Err(format!("Invalid bounds of {action:?}: [{min}, {max}]"));
+            }
+            groups.retain(|bound| bound.actions[0] != index);
+            groups.push(AggregateBound {
+                actions: vec![index],
+                lower: min as f32,
+                upper: max as f32,
+            });
+        }
+
+        self.constraint_from_groups(groups)
+    }
+
+    /// Creates the aggregate-frequency constraint of the current node from the disjoint groups.
+    pub(super) fn constraint_from_groups(
+        &self,
+        bounds: Vec<AggregateBound>,
+    ) -> Result<AggregateConstraint, String> {
+        let num_actions = self.available_actions().len();
+        let num_covered = bounds.iter().map(|b| b.actions.len()).sum::<usize>();
+        let lower_sum = bounds.iter().map(|b| b.lower).sum::<f32>();
+        let upper_sum = bounds.iter().map(|b| b.upper).sum::<f32>();
+
+        if lower_sum > 1.0 + 1e-6 || (num_covered == num_actions && upper_sum < 1.0 - 1e-6) {
+            return Err("Bounds are not satisfiable".to_string());
+        }
+
+        let weights = self.range_weights();
+        let total = weights.iter().sum::<f64>();
+        if total == 0.0 {
+            return Err("Range is empty".to_string());
+        }
+
+        let mut weights = weights
+            .iter()
+            .map(|&w| (w / total) as f32)
+            .collect::<Vec<_>>();
+        self.apply_swap(&mut weights, self.current_player(), true);
+
+        Ok(AggregateConstraint { weights, bounds })
+    }
+
+    /// Returns the initial weights of the current player with the board cards removed.
+    fn range_weights(&self) -> Vec<f64> {
+        let player = self.current_player();
+        let board_mask: u64 = self.current_board().iter().map(|&c| 1 << c).sum();
+        self.private_cards[player]
+            .iter()
+            .zip(&self.initial_weights[player])
+            .map(|(&(c1, c2), &w)| {
+                let hand_mask: u64 = (1 << c1) | (1 << c2);
+                if hand_mask & board_mask == 0 {
+                    w as f64
+                } else {
+                    0.0
+                }
+            })
+            .collect()
+    }
+
+    /// Panics if the strategy cannot be locked.
+    #[inline]
+    pub(super) fn check_lockable(&self) {
+        if self.state < State::MemoryAllocated {
+            panic!("Memory is not allocated");
+        }
+
+        if self.state == State::Solved {
+            panic!("Game is already solved");
+        }
+    }
+
+    /// Calls `f` and then restores the current node of the interpreter.
+    #[inline]
+    pub(super) fn with_history_restored<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> T {
+        let history = self.interpreter.action_history.clone();
+        let result = f(self);
+        self.apply_history(&history);
+        result
+    }
+
+    /// Locks the nodes specified by `line` with the strategies computed by `f`.
+    fn lock_lines_with(
+        &mut self,
+        line: &[Action],
+        mut f: impl FnMut(&Self) -> Result<Vec<f32>, String>,
+    ) -> Result<usize, String> {
+        let lines = self.expand_line(line)?;
+
+        // compute all strategies first so that no node is locked on error
+        let mut strategies = Vec::with_capacity(lines.len());
+        for line in &lines {
+            self.apply_player_line(line)?;
+            strategies.push(f(self)?);
+        }
+
+        for (line, strategy) in lines.iter().zip(&strategies) {
+            self.apply_player_line(line)?;
+            self.lock_current_strategy(strategy);
+        }
+
+        Ok(lines.len())
+    }
+
+    /// Expands the wildcard chance actions of `line` into the representative chance actions.
+    fn expand_line(&mut self, line: &[Action]) -> Result<Vec<Vec<Action>>, String> {
+        let mut lines = vec![Vec::new()];
+
+        for &action in line {
+            if action == Action::Chance(NOT_DEALT) {
+                let mut new_lines = Vec::new();
+                for prefix in &lines {
+                    self.apply_line(prefix)?;
+                    if !self.is_chance_node() {
+                        return Err("Wildcard is not at a chance node".to_string());
+                    }
+                    for chance in self.available_actions() {
+                        let mut line = prefix.clone();
+                        line.push(chance);
+                        new_lines.push(line);
+                    }
+                }
+                lines = new_lines;
+            } else {
+                lines.iter_mut().for_each(|line| line.push(action));
+            }
+        }
+
+        Ok(lines)
+    }
+
+    /// Moves the current node to the player node specified by `line` from the root node.
+    fn apply_player_line(&mut self, line: &[Action]) -> Result<(), String> {
+        self.apply_line(line)?;
+        if self.is_terminal_node() || self.is_chance_node() {
+            return Err("Line does not reach a player node".to_string());
+        }
+        Ok(())
+    }
+
+    /// Collects the lines and the indices of the locked nodes.
+    fn locked_nodes_recursive(
+        &self,
+        node: &PostFlopNode,
+        line: &mut Vec<Action>,
+        result: &mut Vec<(Vec<Action>, usize)>,
+    ) {
+        if node.is_terminal() {
+            return;
+        }
+
+        if node.is_locked {
+            result.push((line.clone(), self.node_index(node)));
+        }
+
+        for child in node.children() {
+            let child = child.lock();
+            line.push(child.prev_action);
+            self.locked_nodes_recursive(&child, line, result);
+            line.pop();
+        }
+    }
+
+    /// Translates the entry into the locking strategy of the current node.
+    fn strategy_from_entry(&self, entry: &LockEntry) -> Result<Vec<f32>, String> {
+        let actions = self.available_actions();
+        if actions != entry.actions {
+            return Err(format!(
+                "Actions differ at {:?}: expected = {:?}, actual = {:?}",
+                entry.line, entry.actions, actions
+            ));
+        }
+
+        let num_entry_hands = entry.hands.len();
+        if entry.strategy.len() != actions.len() * num_entry_hands {
+            return Err("Invalid strategy length".to_string());
+        }
+
+        let hands = &self.private_cards[self.current_player()];
+        let num_hands = hands.len();
+        let mut ret = vec![-1.0; actions.len() * num_hands];
+
+        for (h, hand) in hands.iter().enumerate() {
+            if let Some(i) = entry.hands.iter().position(|x| x == hand) {
+                for action in 0..actions.len() {
+                    ret[action * num_hands + h] = entry.strategy[action * num_entry_hands + i];
+                }
+            }
+        }
+
+        Ok(ret)
+    }
+
+    /// Translates the rules into the locking strategy of the current node.
+    fn strategy_from_rules(&self, rules: &[LockRule]) -> Result<Vec<f32>, String> {
+        let player = self.current_player();
+        let actions = self.available_actions();
+        let num_actions = actions.len();
+        let num_hands = self.num_private_hands(player);
+        let board = self.current_board();
+        let hands = &self.private_cards[player];
+        let weights = self.range_weights();
+
+        let mut ret = vec![-1.0; num_actions * num_hands];
+        let mut is_matched = vec![false; num_hands];
+        let mut matched_weight = 0.0;
+        let mut matched_freq = vec![0.0; num_actions];
+
+        for rule in rules {
+            let mut freq = vec![0.0; num_actions];
+            for &(action, f) in &rule.frequencies {
+                let Some(index) = actions.iter().position(|&a| a == action) else {
+                    return Err(format!("Action {action:?} is not available"));
+                };
+                if !(0.0..=1.0).contains(&f) {
+                    return Err(format!("Invalid frequency: {f}"));
+                }
+                freq[index] += f;
+            }
+
+            if (freq.iter().sum::<f64>() - 1.0).abs() > 1e-6 {
+                return Err("Frequencies must sum to 1.0".to_string());
+            }
+
+            let targets = (0..num_hands)
+                .filter(|&h| !is_matched[h] && rule.hands.contains(hands[h], &board))
+                .collect::<Vec<_>>();
+            let weight = targets.iter().map(|&h| weights[h]).sum::<f64>();
+
+            if rule.is_aggregate {
+                if weight == 0.0 {
+                    return Err("Aggregate rule matches no hands".to_string());
+                }
+                for (f, &prev) in freq.iter_mut().zip(&matched_freq) {
+                    *f = (*f * (matched_weight + weight) - prev) / weight;
+                    if !(-1e-6..=1.0 + 1e-6).contains(f) {
+                        return Err("Aggregate frequency is not achievable".to_string());
+                    }
+                    *f = f.clamp(0.0, 1.0);
+                }
+            }
+
+            for &hand in &targets {
+                is_matched[hand] = true;
+                for action in 0..num_actions {
+                    ret[action * num_hands + hand] = freq[action] as f32;
+                }
+            }
+
+            matched_weight += weight;
+            for (m, f) in matched_freq.iter_mut().zip(&freq) {
+                *m += f * weight;
+            }
+        }
+
+        Ok(ret)
+    }
+}
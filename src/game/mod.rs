@@ -1,7 +1,20 @@
 mod base;
+mod best_response;
+mod cursor;
+mod diagnostics;
 mod evaluation;
 mod interpreter;
+mod leaf;
+mod locking;
 mod node;
+mod opponent_model;
+mod planner;
+mod pruning;
+mod solved_strategy;
+mod storage;
+mod translation;
+mod walker;
+mod warm_start;
 
 #[cfg(feature = "bincode")]
 mod serialization;
@@ -11,8 +24,23 @@ mod tests;
 
 use crate::action_tree::*;
 use crate::card::*;
+use crate::interface::AggregateConstraint;
 use crate::mutex_like::*;
+use interpreter::*;
 use std::collections::BTreeMap;
+use storage::*;
+
+pub use crate::card::BoardTexture;
+pub use cursor::*;
+pub use leaf::*;
+pub use locking::*;
+pub use opponent_model::*;
+pub use planner::*;
+pub use pruning::*;
+pub use solved_strategy::*;
+pub use storage::{CompressionConfig, StorageBackend};
+pub use translation::*;
+pub use walker::*;
 
 #[cfg(feature = "bincode")]
 use bincode::{Decode, Encode};
@@ -40,6 +68,7 @@ pub struct PostFlopGame {
     tree_config: TreeConfig,
     added_lines: Vec<Vec<Action>>,
     removed_lines: Vec<Vec<Action>>,
+    removed_game_lines: Vec<Vec<Action>>,
     action_root: Box<MutexLike<ActionTreeNode>>,
 
     // computed from configurations
@@ -79,39 +108,33 @@ pub struct PostFlopGame {
     bunching_coef_flop: [Vec<usize>; 2],
     bunching_coef_turn: [Vec<Vec<usize>>; 2],
 
+    // depth-limited solving
+    leaf_evaluator: Option<Box<dyn LeafEvaluator>>,
+
     // store options
     storage_mode: BoardState,
     target_storage_mode: BoardState,
     num_nodes: [u64; 3],
-    is_compression_enabled: bool,
-    num_storage: u64,
-    num_storage_ip: u64,
-    num_storage_chance: u64,
+    compression: CompressionConfig,
+    storage_backend: StorageBackend,
+    num_storage: [u64; 3],
+    num_storage_ip: [u64; 3],
+    num_storage_chance: [u64; 3],
     misc_memory_usage: u64,
 
     // global storage
     // `storage*` are used as a global storage and are referenced by `PostFlopNode::storage*`.
     // Methods like `PostFlopNode::strategy` define how the storage is used.
     node_arena: Vec<MutexLike<PostFlopNode>>,
-    storage1: Vec<u8>,
-    storage2: Vec<u8>,
-    storage_ip: Vec<u8>,
-    storage_chance: Vec<u8>,
+    storage1: Storage,
+    storage2: Storage,
+    storage_ip: Storage,
+    storage_chance: Storage,
     locking_strategy: BTreeMap<usize, Vec<f32>>,
+    aggregate_constraints: BTreeMap<usize, AggregateConstraint>,
 
     // result interpreter
-    action_history: Vec<usize>,
-    node_history: Vec<usize>,
-    is_normalized_weight_cached: bool,
-    turn: Card,
-    river: Card,
-    turn_swapped_suit: Option<(u8, u8)>,
-    turn_swap: Option<u8>,
-    river_swap: Option<(u8, u8)>,
-    total_bet_amount: [i32; 2],
-    weights: [Vec<f32>; 2],
-    normalized_weights: [Vec<f32>; 2],
-    cfvalues_cache: [Vec<f32>; 2],
+    interpreter: InterpreterState,
 }
 
 /// A struct representing a node in a postflop game tree.
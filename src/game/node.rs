@@ -1,5 +1,6 @@
 use super::*;
 use crate::interface::*;
+use crate::utility::*;
 use std::ptr;
 use std::slice;
 
@@ -148,6 +149,58 @@ impl GameNode for PostFlopNode {
         unsafe { slice::from_raw_parts_mut(self.storage1 as *mut i16, self.num_elements as usize) }
     }
 
+    #[inline]
+    fn strategy_u8(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.storage1 as *const u8, self.num_elements as usize) }
+    }
+
+    #[inline]
+    fn strategy_u8_mut(&mut self) -> &mut [u8] {
+        unsafe { slice::from_raw_parts_mut(self.storage1, self.num_elements as usize) }
+    }
+
+    #[inline]
+    fn regrets_i8(&self) -> &[i8] {
+        unsafe { slice::from_raw_parts(self.storage2 as *const i8, self.num_elements as usize) }
+    }
+
+    #[inline]
+    fn regrets_i8_mut(&mut self) -> &mut [i8] {
+        unsafe { slice::from_raw_parts_mut(self.storage2 as *mut i8, self.num_elements as usize) }
+    }
+
+    #[inline]
+    fn cfvalues_i8(&self) -> &[i8] {
+        unsafe { slice::from_raw_parts(self.storage2 as *const i8, self.num_elements as usize) }
+    }
+
+    #[inline]
+    fn cfvalues_i8_mut(&mut self) -> &mut [i8] {
+        unsafe { slice::from_raw_parts_mut(self.storage2 as *mut i8, self.num_elements as usize) }
+    }
+
+    #[inline]
+    fn cfvalues_ip_i8(&self) -> &[i8] {
+        unsafe { slice::from_raw_parts(self.storage3 as *const i8, self.num_elements_ip as usize) }
+    }
+
+    #[inline]
+    fn cfvalues_ip_i8_mut(&mut self) -> &mut [i8] {
+        unsafe {
+            slice::from_raw_parts_mut(self.storage3 as *mut i8, self.num_elements_ip as usize)
+        }
+    }
+
+    #[inline]
+    fn cfvalues_chance_i8(&self) -> &[i8] {
+        unsafe { slice::from_raw_parts(self.storage1 as *const i8, self.num_elements as usize) }
+    }
+
+    #[inline]
+    fn cfvalues_chance_i8_mut(&mut self) -> &mut [i8] {
+        unsafe { slice::from_raw_parts_mut(self.storage1 as *mut i8, self.num_elements as usize) }
+    }
+
     #[inline]
     fn strategy_scale(&self) -> f32 {
         self.scale1
@@ -229,6 +282,56 @@ impl Default for PostFlopNode {
 }
 
 impl PostFlopNode {
+    #[inline]
+    pub(super) fn is_leaf(&self) -> bool {
+        self.player & PLAYER_LEAF_FLAG == PLAYER_LEAF_FLAG
+    }
+
+    /// Returns the street of the node.
+    #[inline]
+    pub(super) fn street(&self) -> BoardState {
+        if self.river != NOT_DEALT {
+            BoardState::River
+        } else if self.turn != NOT_DEALT {
+            BoardState::Turn
+        } else {
+            BoardState::Flop
+        }
+    }
+
+    /// Returns the decoded counterfactual values of each action.
+    #[inline]
+    pub(super) fn decoded_cfvalues(&self, precision: Precision) -> Vec<f32> {
+        match precision {
+            Precision::Float32 => self.cfvalues().to_vec(),
+            Precision::Int16 => decode_slice(self.cfvalues_compressed(), self.cfvalue_scale()),
+            Precision::Int8 => decode_slice(self.cfvalues_i8(), self.cfvalue_scale()),
+        }
+    }
+
+    /// Returns the decoded counterfactual values of IP.
+    #[inline]
+    pub(super) fn decoded_cfvalues_ip(&self, precision: Precision) -> Vec<f32> {
+        match precision {
+            Precision::Float32 => self.cfvalues_ip().to_vec(),
+            Precision::Int16 => {
+                decode_slice(self.cfvalues_ip_compressed(), self.cfvalue_ip_scale())
+            }
+            Precision::Int8 => decode_slice(self.cfvalues_ip_i8(), self.cfvalue_ip_scale()),
+        }
+    }
+
+    /// Returns the decoded counterfactual values stored in the chance node.
+    #[inline]
+    pub(super) fn decoded_cfvalues_chance(&self, precision: Precision) -> Vec<f32> {
+        let scale = self.cfvalue_chance_scale();
+        match precision {
+            Precision::Float32 => self.cfvalues_chance().to_vec(),
+            Precision::Int16 => decode_slice(self.cfvalues_chance_compressed(), scale),
+            Precision::Int8 => decode_slice(self.cfvalues_chance_i8(), scale),
+        }
+    }
+
     #[inline]
     pub(super) fn children(&self) -> &[MutexLike<Self>] {
         // This is safe because `MutexLike<T>` is a `repr(transparent)` wrapper around `T`.
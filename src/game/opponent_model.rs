@@ -0,0 +1,319 @@
+use super::*;
+use crate::interface::*;
+use std::str::FromStr;
+
+/// An enum representing a class of actions, used by [`OpponentModel`].
+///
+/// Bets and raises of any size belong to the same class, so that frequencies collected from hand
+/// histories can be applied regardless of the bet sizes of the tree. An all-in action is classified
+/// as [`ActionClass::Raise`] when facing a bet and as [`ActionClass::Bet`] otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ActionClass {
+    /// Fold (`F`).
+    Fold,
+
+    /// Check (`X`).
+    Check,
+
+    /// Call (`C`).
+    Call,
+
+    /// Bet of any size (`B`).
+    Bet,
+
+    /// Raise of any size (`R`).
+    Raise,
+
+    /// Any turn or river card (`*`). Only used in lines.
+    Chance,
+}
+
+/// A struct representing an entry of [`OpponentModel`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelEntry {
+    /// Pattern of the line from the root node to the target nodes.
+    pub line: Vec<ActionClass>,
+
+    /// Textures that the board of the target nodes must have (all of them). Empty means any board.
+    pub textures: Vec<BoardTexture>,
+
+    /// Target aggregate frequency of each action class. Unlisted classes are not constrained.
+    pub frequencies: Vec<(ActionClass, f64)>,
+
+    /// Allowed deviation from the target frequencies.
+    pub tolerance: f64,
+}
+
+/// A struct representing a population model of the opponent, applied by
+/// [`PostFlopGame::apply_opponent_model`].
+///
+/// The model can be parsed from a text where each line is an entry of the form
+/// `<line> ; <textures> ; <frequencies>`:
+///
+/// - `<line>`: space-separated action classes from the root node: `F` (fold), `X` (check), `C`
+///   (call), `B` (bet of any size), `R` (raise of any size), and `*` (any turn or river card).
+///   `|` can be inserted as a street separator and is ignored.
+/// - `<textures>`: space- or comma-separated [`BoardTexture`] names (lowercase), or `*` for any
+///   board.
+/// - `<frequencies>`: space-separated `<class>=<frequency>` where the frequency is a number in
+///   `[0, 1]` or a percentage (e.g., `65%`). `tol=<tolerance>` specifies the allowed deviation
+///   (default: 0).
+///
+/// Empty lines and the text after `#` are ignored.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// let model = "
+///     X ; * ; B=65% tol=2%          # c-bet
+///     X B R ; paired ; F=0.45       # fold to check-raise on paired boards
+///     X X | * | X ; * ; B=40%       # delayed c-bet
+/// ";
+///
+/// let model: OpponentModel = model.parse().unwrap();
+/// assert_eq!(model.entries.len(), 3);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OpponentModel {
+    /// Entries of the model. Later entries take precedence over earlier ones.
+    pub entries: Vec<ModelEntry>,
+}
+
+impl ActionClass {
+    /// Classifies `action`, where `actions` is the list of available actions of the node.
+    #[inline]
+    pub fn classify(action: Action, actions: &[Action]) -> Self {
+        match action {
+            Action::Fold => Self::Fold,
+            Action::Check => Self::Check,
+            Action::Call => Self::Call,
+            Action::Bet(_) => Self::Bet,
+            Action::Raise(_) => Self::Raise,
+            Action::AllIn(_) if actions.contains(&Action::Call) => Self::Raise,
+            Action::AllIn(_) => Self::Bet,
+            Action::Chance(_) => Self::Chance,
+            Action::None => panic!("Invalid action"),
+        }
+    }
+}
+
+impl FromStr for ActionClass {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "F" => Ok(Self::Fold),
+            "X" => Ok(Self::Check),
+            "C" => Ok(Self::Call),
+            "B" => Ok(Self::Bet),
+            "R" => Ok(Self::Raise),
+            "*" => Ok(Self::Chance),
+            _ => Err(format!("Invalid action class: {s}")),
+        }
+    }
+}
+
+impl ModelEntry {
+    /// Checks whether the entry is valid.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.tolerance) {
+            return Err(format!("Invalid tolerance: {}", self.tolerance));
+        }
+
+        for (i, &(class, freq)) in self.frequencies.iter().enumerate() {
+            if class == ActionClass::Chance {
+                return Err("Chance class cannot have a frequency".to_string());
+            }
+            if !(0.0..=1.0).contains(&freq) {
+                return Err(format!("Invalid frequency of {class:?}: {freq}"));
+            }
+            if self.frequencies[..i].iter().any(|&(c, _)| c == class) {
+                return Err(format!("Duplicate frequency of {class:?}"));
+            }
+        }
+
+        let lower = |&(_, f): &(ActionClass, f64)| (f - self.tolerance).max(0.0);
+        if self.frequencies.iter().map(lower).sum::<f64>() > 1.0 + 1e-6 {
+            return Err("Frequencies must not sum to more than 1.0".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Returns the bounds of the node with `actions`, or `None` if a class is not available.
+    fn bounds(&self, actions: &[Action]) -> Option<Vec<AggregateBound>> {
+        let mut ret = Vec::with_capacity(self.frequencies.len());
+
+        for &(class, freq) in &self.frequencies {
+            let indices = (0..actions.len())
+                .filter(|&i| ActionClass::classify(actions[i], actions) == class)
+                .collect::<Vec<_>>();
+            if indices.is_empty() {
+                return None;
+            }
+            ret.push(AggregateBound {
+                actions: indices,
+                lower: (freq - self.tolerance).max(0.0) as f32,
+                upper: (freq + self.tolerance).min(1.0) as f32,
+            });
+        }
+
+        Some(ret)
+    }
+}
+
+impl FromStr for ModelEntry {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields = s.split(';').map(str::trim).collect::<Vec<_>>();
+        if fields.len() != 3 {
+            return Err(format!("Expected three fields separated by ';': {s}"));
+        }
+
+        let line = fields[0]
+            .split_whitespace()
+            .filter(|&token| token != "|")
+            .map(str::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let textures = fields[1]
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|&token| !token.is_empty() && token != "*")
+            .map(str::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let parse_value = |s: &str| -> Result<f64, String> {
+            let (s, scale) = match s.strip_suffix('%') {
+                Some(s) => (s, 0.01),
+                None => (s, 1.0),
+            };
+            let value = s.parse::<f64>().map_err(|e| format!("{e}: {s}"))?;
+            Ok(value * scale)
+        };
+
+        let mut frequencies = Vec::new();
+        let mut tolerance = 0.0;
+        for token in fields[2].split_whitespace() {
+            let Some((key, value)) = token.split_once('=') else {
+                return Err(format!("Expected '<class>=<frequency>': {token}"));
+            };
+            match key {
+                "tol" => tolerance = parse_value(value)?,
+                _ => frequencies.push((key.parse()?, parse_value(value)?)),
+            }
+        }
+
+        if frequencies.is_empty() {
+            return Err(format!("No frequency is specified: {s}"));
+        }
+
+        let entry = Self {
+            line,
+            textures,
+            frequencies,
+            tolerance,
+        };
+
+        entry.validate()?;
+        Ok(entry)
+    }
+}
+
+impl FromStr for OpponentModel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut entries = Vec::new();
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            if !line.is_empty() {
+                let entry = line.parse().map_err(|e| format!("Line {}: {e}", i + 1))?;
+                entries.push(entry);
+            }
+        }
+
+        Ok(Self { entries })
+    }
+}
+
+impl PostFlopGame {
+    /// Applies the population model of the opponent to the game.
+    ///
+    /// For each entry of `model`, all player nodes whose line matches the pattern and whose board
+    /// has the textures are constrained by [`set_aggregate_constraint`] with the bounds of
+    /// `[frequency - tolerance, frequency + tolerance]`, where the frequency of a class is the sum
+    /// of the frequencies of all actions in the class (e.g., all bet sizes). Nodes where a class of
+    /// the entry is not available (e.g., no raise is allowed) are skipped. When multiple entries
+    /// match the same node, the last one is used.
+    ///
+    /// The current node of the interpreter is restored. Returns the number of constrained nodes, or
+    /// `Err` if an entry is invalid or the bounds are not satisfiable. No node is constrained if
+    /// `Err` is returned.
+    ///
+    /// This method must be called after allocating memory and before solving the game.
+    /// Panics if the memory is not yet allocated or the game is already solved.
+    ///
+    /// [`set_aggregate_constraint`]: #method.set_aggregate_constraint
+    pub fn apply_opponent_model(&mut self, model: &OpponentModel) -> Result<usize, String> {
+        self.check_lockable();
+        self.with_history_restored(|game| {
+            let mut constraints = BTreeMap::new();
+
+            for entry in &model.entries {
+                entry.validate()?;
+                for line in game.expand_pattern(&entry.line)? {
+                    game.apply_line(&line)?;
+                    if game.is_terminal_node() || game.is_chance_node() {
+                        continue;
+                    }
+
+                    let board = game.current_board();
+                    if !entry.textures.iter().all(|t| t.matches(&board)) {
+                        continue;
+                    }
+
+                    if let Some(bounds) = entry.bounds(&game.available_actions()) {
+                        let index = game.node_index(&game.node());
+                        let constraint = game.constraint_from_groups(bounds);
+                        constraints
+                            .insert(index, constraint.map_err(|e| format!("{line:?}: {e}"))?);
+                    }
+                }
+            }
+
+            let num_constrained = constraints.len();
+            game.aggregate_constraints.extend(constraints);
+            Ok(num_constrained)
+        })
+    }
+
+    /// Expands the pattern into the lines of all matching nodes (the chance actions are the
+    /// representative cards).
+    fn expand_pattern(&mut self, pattern: &[ActionClass]) -> Result<Vec<Vec<Action>>, String> {
+        let mut lines = vec![Vec::new()];
+
+        for &class in pattern {
+            let mut new_lines = Vec::new();
+            for prefix in &lines {
+                self.apply_line(prefix)?;
+                if self.is_terminal_node() {
+                    continue;
+                }
+                let actions = self.available_actions();
+                for &action in &actions {
+                    if ActionClass::classify(action, &actions) == class {
+                        let mut line = prefix.clone();
+                        line.push(action);
+                        new_lines.push(line);
+                    }
+                }
+            }
+            lines = new_lines;
+        }
+
+        Ok(lines)
+    }
+}
@@ -0,0 +1,410 @@
+use super::*;
+use crate::bet_size::*;
+use crate::interface::*;
+use std::mem;
+
+/// A struct representing the estimated size of a game tree.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryEstimate {
+    /// Number of nodes of [flop, turn, river].
+    pub num_nodes: [u64; 3],
+
+    /// Memory usage in bytes when the compression is disabled.
+    pub uncompressed: u64,
+
+    /// Memory usage in bytes when the compression is enabled.
+    pub compressed: u64,
+}
+
+/// A modification of the tree configuration suggested by [`MemoryPlanner::plan`].
+#[derive(Debug, Clone)]
+pub struct MemorySuggestion {
+    /// Human-readable description of the modification (e.g., "Remove 75% from flop OOP bet sizes").
+    pub description: String,
+
+    /// Tree configuration after applying this and all preceding suggestions.
+    pub tree_config: TreeConfig,
+
+    /// Estimated size of the game tree built from `tree_config`.
+    pub estimate: MemoryEstimate,
+}
+
+/// The result of [`MemoryPlanner::plan`].
+#[derive(Debug, Clone)]
+pub struct MemoryPlan {
+    /// Target memory budget in bytes.
+    pub budget: u64,
+
+    /// Whether the compression is assumed to be enabled.
+    pub enable_compression: bool,
+
+    /// Estimated size of the game tree built from the original tree configuration.
+    pub estimate: MemoryEstimate,
+
+    /// Modifications to apply in order. Each suggestion is cumulative, so the last one contains the
+    /// final tree configuration. Empty if the original configuration already fits the budget.
+    pub suggestions: Vec<MemorySuggestion>,
+}
+
+/// A planner for estimating the memory usage of tree configurations without building the game.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// let card_config = CardConfig {
+///     range: ["66+,A8s+,A5s-A4s,AJo+".parse().unwrap(), "QQ-22,AQs-A2s,AQo-A9o".parse().unwrap()],
+///     flop: flop_from_str("Td9d6h").unwrap(),
+///     ..Default::default()
+/// };
+///
+/// let bet_sizes = BetSizeOptions::try_from(("33%, 75%", "2.5x")).unwrap();
+/// let tree_config = TreeConfig {
+///     starting_pot: 200,
+///     effective_stack: 900,
+///     flop_bet_sizes: [bet_sizes.clone(), bet_sizes.clone()],
+///     turn_bet_sizes: [bet_sizes.clone(), bet_sizes.clone()],
+///     river_bet_sizes: [bet_sizes.clone(), bet_sizes],
+///     ..Default::default()
+/// };
+///
+/// let planner = MemoryPlanner::new(card_config).unwrap();
+/// let estimate = planner.estimate(&tree_config).unwrap();
+///
+/// // suggest modifications to halve the memory usage
+/// let plan = planner.plan(&tree_config, estimate.compressed / 2, true).unwrap();
+/// assert!(plan.fits());
+/// for suggestion in &plan.suggestions {
+///     println!("{}", suggestion.description);
+/// }
+/// ```
+pub struct MemoryPlanner {
+    game: PostFlopGame,
+}
+
+/// Counts of the tree elements of each street.
+#[derive(Default)]
+struct StreetCount {
+    num_nodes: [u64; 3],
+    num_storage: [u64; 3],
+    num_storage_ip: [u64; 3],
+    num_storage_chance: [u64; 3],
+}
+
+impl MemoryPlanner {
+    /// Creates a new planner for the given card configuration.
+    pub fn new(card_config: CardConfig) -> Result<Self, String> {
+        let initial_state = match (card_config.turn, card_config.river) {
+            (NOT_DEALT, _) => BoardState::Flop,
+            (_, NOT_DEALT) => BoardState::Turn,
+            _ => BoardState::River,
+        };
+
+        let mut game = PostFlopGame {
+            card_config,
+            ..Default::default()
+        };
+
+        game.tree_config.initial_state = initial_state;
+        game.check_card_config()?;
+        game.init_card_fields();
+
+        Ok(Self { game })
+    }
+
+    /// Estimates the size of the game tree built from the given tree configuration.
+    ///
+    /// The result coincides with the node counts of the built game and
+    /// [`PostFlopGame::memory_usage`] (the bunching effect is not considered).
+    pub fn estimate(&self, tree_config: &TreeConfig) -> Result<MemoryEstimate, String> {
+        let expected_state = self.game.tree_config.initial_state;
+        if tree_config.initial_state != expected_state {
+            return Err(format!(
+                "Invalid initial state of `tree_config`: expected = {:?}, actual = {:?}",
+                expected_state, tree_config.initial_state
+            ));
+        }
+
+        let action_tree = ActionTree::new(tree_config.clone())?;
+        let (_, _, _, root) = action_tree.eject();
+
+        let mut count = StreetCount::default();
+        let rules = &tree_config.board_bet_sizes;
+        self.game
+            .visit_action_tree(&root.lock(), rules, &mut |node, is_first, n| {
+                self.count_node(node, is_first, n, &mut count);
+            });
+
+        let sum = |x: &[u64; 3]| x.iter().sum::<u64>();
+        let num_nodes = count.num_nodes;
+
+        let total_num_nodes = num_nodes.iter().sum::<u64>();
+        let num_storage = sum(&count.num_storage);
+        let num_storage_ip = sum(&count.num_storage_ip);
+        let num_storage_chance = sum(&count.num_storage_chance);
+
+        let misc_memory_usage = self.game.memory_usage_internal()
+            + total_num_nodes * mem::size_of::<MutexLike<PostFlopNode>>() as u64;
+
+        let num_elements = 2 * num_storage + num_storage_ip + num_storage_chance;
+
+        Ok(MemoryEstimate {
+            num_nodes,
+            uncompressed: 4 * num_elements + misc_memory_usage,
+            compressed: 2 * num_elements + misc_memory_usage,
+        })
+    }
+
+    /// Suggests modifications of the tree configuration to fit the game tree into `budget` bytes.
+    ///
+    /// The planner greedily applies the modification with the largest reduction in memory usage
+    /// until the estimate fits the budget. The candidates are removing a single bet, raise, or donk
+    /// size (including the re-raise sizes and those of [`TreeConfig::board_bet_sizes`]), lowering
+    /// [`BetSizeOptions::max_raises`], raising `merging_threshold`, and disabling
+    /// `add_allin_threshold`. If no modification reduces the memory usage, the planner stops
+    /// without fitting the budget.
+    pub fn plan(
+        &self,
+        tree_config: &TreeConfig,
+        budget: u64,
+        enable_compression: bool,
+    ) -> Result<MemoryPlan, String> {
+        let estimate = self.estimate(tree_config)?;
+        let memory_usage = |estimate: &MemoryEstimate| estimate.memory_usage(enable_compression);
+
+        let mut suggestions = Vec::<MemorySuggestion>::new();
+        let mut current_config = tree_config.clone();
+        let mut current_usage = memory_usage(&estimate);
+
+        while current_usage > budget {
+            let mut best: Option<MemorySuggestion> = None;
+
+            for (description, config) in candidate_configs(&current_config) {
+                // skip invalid configurations
+                let Ok(estimate) = self.estimate(&config) else {
+                    continue;
+                };
+
+                let usage = memory_usage(&estimate);
+                if usage
+                    < best
+                        .as_ref()
+                        .map_or(current_usage, |b| memory_usage(&b.estimate))
+                {
+                    best = Some(MemorySuggestion {
+                        description,
+                        tree_config: config,
+                        estimate,
+                    });
+                }
+            }
+
+            match best {
+                Some(suggestion) => {
+                    current_config = suggestion.tree_config.clone();
+                    current_usage = memory_usage(&suggestion.estimate);
+                    suggestions.push(suggestion);
+                }
+                None => break,
+            }
+        }
+
+        Ok(MemoryPlan {
+            budget,
+            enable_compression,
+            estimate,
+            suggestions,
+        })
+    }
+
+    /// Counts the tree elements of `node`, which has `n` copies in the game tree.
+    fn count_node(&self, node: &ActionTreeNode, is_first: bool, n: u64, count: &mut StreetCount) {
+        let street = node.board_state as usize;
+        count.num_nodes[street] += n;
+
+        if node.is_terminal() {
+            // do nothing
+        } else if node.is_chance() {
+            let num_hands = match node.player & PLAYER_MASK {
+                PLAYER_OOP => self.game.num_private_hands(PLAYER_IP as usize),
+                PLAYER_IP => self.game.num_private_hands(PLAYER_OOP as usize),
+                _ => 0,
+            };
+            count.num_storage_chance[street] += num_hands as u64 * n;
+        } else {
+            let num_hands = self.game.num_private_hands(node.player as usize);
+            count.num_storage[street] += (node.actions.len() * num_hands) as u64 * n;
+            if is_first {
+                let num_hands_ip = self.game.num_private_hands(PLAYER_IP as usize);
+                count.num_storage_ip[street] += num_hands_ip as u64 * n;
+            }
+        }
+    }
+}
+
+impl MemoryEstimate {
+    /// Returns the memory usage in bytes for the specified compression mode.
+    #[inline]
+    pub fn memory_usage(&self, enable_compression: bool) -> u64 {
+        match enable_compression {
+            false => self.uncompressed,
+            true => self.compressed,
+        }
+    }
+}
+
+impl MemoryPlan {
+    /// Returns the estimated memory usage after applying all suggestions.
+    #[inline]
+    pub fn final_memory_usage(&self) -> u64 {
+        let estimate = self
+            .suggestions
+            .last()
+            .map_or(&self.estimate, |s| &s.estimate);
+        estimate.memory_usage(self.enable_compression)
+    }
+
+    /// Returns whether the game tree fits the budget after applying all suggestions.
+    #[inline]
+    pub fn fits(&self) -> bool {
+        self.final_memory_usage() <= self.budget
+    }
+}
+
+/// Enumerates the tree configurations that differ from `config` by a single modification.
+pub(super) fn candidate_configs(config: &TreeConfig) -> Vec<(String, TreeConfig)> {
+    let mut ret = Vec::new();
+    let street_name = |street: usize| ["flop", "turn", "river"][street];
+    let player_name = |player: usize| ["OOP", "IP"][player];
+
+    for street in config.initial_state as usize..3 {
+        for player in 0..2 {
+            let options = match street {
+                0 => &config.flop_bet_sizes[player],
+                1 => &config.turn_bet_sizes[player],
+                _ => &config.river_bet_sizes[player],
+            };
+            let name = format!("{} {}", street_name(street), player_name(player));
+            push_option_candidates(&mut ret, config, options, &name, |config| match street {
+                0 => &mut config.flop_bet_sizes[player],
+                1 => &mut config.turn_bet_sizes[player],
+                _ => &mut config.river_bet_sizes[player],
+            });
+        }
+    }
+
+    for street in config.initial_state as usize + 1..3 {
+        let donk_sizes = match street {
+            1 => &config.turn_donk_sizes,
+            _ => &config.river_donk_sizes,
+        };
+        if let Some(donk_sizes) = donk_sizes {
+            for i in 0..donk_sizes.donk.len() {
+                let mut new_donk_sizes = donk_sizes.clone();
+                let removed = new_donk_sizes.donk.remove(i);
+                let mut new_config = config.clone();
+                match street {
+                    1 => new_config.turn_donk_sizes = Some(new_donk_sizes),
+                    _ => new_config.river_donk_sizes = Some(new_donk_sizes),
+                }
+                let description =
+                    format!("Remove {removed} from {} donk sizes", street_name(street));
+                ret.push((description, new_config));
+            }
+        }
+    }
+
+    for (index, rule) in config.board_bet_sizes.iter().enumerate() {
+        let rule_name = format!(
+            "board rule {} ({})",
+            index + 1,
+            street_name(rule.street as usize)
+        );
+
+        for player in 0..2 {
+            let options = &rule.bet_sizes[player];
+            let name = format!("{rule_name} {}", player_name(player));
+            push_option_candidates(&mut ret, config, options, &name, |config| {
+                &mut config.board_bet_sizes[index].bet_sizes[player]
+            });
+        }
+
+        if let Some(donk_sizes) = &rule.donk_sizes {
+            for i in 0..donk_sizes.donk.len() {
+                let mut new_config = config.clone();
+                let new_donk_sizes = new_config.board_bet_sizes[index].donk_sizes.as_mut();
+                let removed = new_donk_sizes.unwrap().donk.remove(i);
+                let description = format!("Remove {removed} from {rule_name} donk sizes");
+                ret.push((description, new_config));
+            }
+        }
+    }
+
+    for threshold in [0.1, 0.2, 0.3] {
+        if config.merging_threshold < threshold {
+            let mut new_config = config.clone();
+            new_config.merging_threshold = threshold;
+            let description = format!("Raise merging_threshold to {threshold}");
+            ret.push((description, new_config));
+        }
+    }
+
+    if config.add_allin_threshold > 0.0 {
+        let mut new_config = config.clone();
+        new_config.add_allin_threshold = 0.0;
+        let description = "Disable add_allin_threshold".to_string();
+        ret.push((description, new_config));
+    }
+
+    ret
+}
+
+/// Pushes the configurations that remove a single size of `options` or lower its `max_raises`,
+/// where `options` is obtained from the configuration by `get_mut`.
+fn push_option_candidates<F>(
+    ret: &mut Vec<(String, TreeConfig)>,
+    config: &TreeConfig,
+    options: &BetSizeOptions,
+    name: &str,
+    get_mut: F,
+) where
+    F: Fn(&mut TreeConfig) -> &mut BetSizeOptions,
+{
+    for (kind, sizes) in [("bet", &options.bet), ("raise", &options.raise)] {
+        for i in 0..sizes.len() {
+            let mut new_config = config.clone();
+            let new_options = get_mut(&mut new_config);
+            let removed = match kind {
+                "bet" => new_options.bet.remove(i),
+                _ => new_options.raise.remove(i),
+            };
+            let description = format!("Remove {removed} from {name} {kind} sizes");
+            ret.push((description, new_config));
+        }
+    }
+
+    for (index, sizes) in options.reraise.iter().enumerate() {
+        for i in 0..sizes.len() {
+            let mut new_config = config.clone();
+            let removed = get_mut(&mut new_config).reraise[index].remove(i);
+            let description = format!(
+                "Remove {removed} from {name} raise sizes for raise {}",
+                index + 2
+            );
+            ret.push((description, new_config));
+        }
+    }
+
+    // `None` is first capped at the depth of the last re-raise group
+    let has_raises = !options.raise.is_empty() || options.reraise.iter().any(|s| !s.is_empty());
+    let max_raises = match options.max_raises {
+        Some(max_raises) => max_raises.checked_sub(1),
+        None => Some(options.reraise.len() as u32 + 1),
+    };
+    if let (true, Some(max_raises)) = (has_raises, max_raises) {
+        let mut new_config = config.clone();
+        get_mut(&mut new_config).max_raises = Some(max_raises);
+        let description = format!("Cap {name} max_raises at {max_raises}");
+        ret.push((description, new_config));
+    }
+}
@@ -0,0 +1,250 @@
+use super::*;
+use crate::interface::*;
+use crate::sliceop::*;
+use crate::solver::*;
+use crate::utility::*;
+use std::collections::BTreeSet;
+
+/// The result of [`PostFlopGame::prune_and_resolve`].
+pub struct PruningReport {
+    /// The pruned game, which is solved by warm-starting from the preliminary solution.
+    pub game: PostFlopGame,
+
+    /// The removed lines in depth-first order (see [`PostFlopGame::low_frequency_lines`]).
+    pub removed_lines: Vec<Vec<Action>>,
+
+    /// The exploitability of the preliminary solution.
+    pub exploitability_before: f32,
+
+    /// The exploitability of the pruned game after resolving.
+    ///
+    /// Note that this value is measured in the pruned game, i.e., the removed lines are not
+    /// available to the best response either.
+    pub exploitability_after: f32,
+}
+
+impl PruningReport {
+    /// Returns the change of the exploitability (after - before).
+    #[inline]
+    pub fn exploitability_change(&self) -> f32 {
+        self.exploitability_after - self.exploitability_before
+    }
+}
+
+impl PostFlopGame {
+    /// Returns the bet lines (including raises and all-ins) whose aggregate frequency is below
+    /// `threshold`.
+    ///
+    /// The aggregate frequency of an action is the average of the strategy over the private hands
+    /// of the acting player, weighted by their weights at the node. The lines are listed in
+    /// depth-first order and contain chance actions, so they can be passed to [`remove_lines`] of
+    /// a game with the same configuration. The lines after a listed line and the nodes that the
+    /// acting player never reaches are not examined.
+    ///
+    /// Panics if the memory is not yet allocated.
+    ///
+    /// [`remove_lines`]: #method.remove_lines
+    pub fn low_frequency_lines(&self, threshold: f32) -> Vec<Vec<Action>> {
+        let mut ret = Vec::new();
+        let mut removed = BTreeSet::new();
+
+        self.walk(&WalkConfig::default(), |node| {
+            if node.is_terminal() || removed.contains(node.line()) {
+                return false;
+            }
+
+            if node.is_chance() {
+                return true;
+            }
+
+            let actions = node.actions();
+            if actions.len() <= 1 {
+                return true;
+            }
+
+            let player = node.player();
+            let weights = node.weights(player).to_vec();
+            let total = weights.iter().map(|&w| w as f64).sum::<f64>();
+            if total <= 0.0 {
+                return false;
+            }
+
+            let strategy = node.strategy();
+            let num_hands = weights.len();
+
+            for (i, &action) in actions.iter().enumerate() {
+                if !matches!(action, Action::Bet(_) | Action::Raise(_) | Action::AllIn(_)) {
+                    continue;
+                }
+
+                let row = row(&strategy, i, num_hands);
+                let freq = row
+                    .iter()
+                    .zip(&weights)
+                    .map(|(&s, &w)| s as f64 * w as f64)
+                    .sum::<f64>()
+                    / total;
+
+                if freq < threshold as f64 {
+                    let line = [node.line(), &[action]].concat();
+                    removed.insert(line.clone());
+                    ret.push(line);
+                }
+            }
+
+            true
+        });
+
+        ret
+    }
+
+    /// Creates a new game with the same configuration except that the given `lines` are removed,
+    /// and initializes its regrets and cumulative strategy from the solution of this game.
+    ///
+    /// The lines must contain chance actions as in [`low_frequency_lines`]. The lines removed from
+    /// this game by [`remove_lines`] are removed from the new game as well, except for those after
+    /// the given `lines`. The memory of the new game is allocated with the same compression
+    /// configuration and storage backend as this game, and it is initialized by
+    /// [`warm_start_from`]. The node locking, aggregate-frequency constraints, bunching effect,
+    /// and leaf evaluator are not carried over, so this method returns `Err` if any of them is
+    /// set.
+    ///
+    /// This game must not be finalized because the cumulative regrets are overwritten by
+    /// [`finalize`]; perform the preliminary solve by [`solve_step`] instead of [`solve`].
+    ///
+    /// [`low_frequency_lines`]: #method.low_frequency_lines
+    /// [`remove_lines`]: #method.remove_lines
+    /// [`warm_start_from`]: #method.warm_start_from
+    pub fn pruned_game(&self, lines: &[Vec<Action>]) -> Result<PostFlopGame, String> {
+        if self.state == State::Solved {
+            return Err("Game is already solved".to_string());
+        }
+
+        if !self.is_ready() {
+            return Err("Game is not ready".to_string());
+        }
+
+        if !self.locking_strategy.is_empty() || !self.aggregate_constraints.is_empty() {
+            return Err("Node locking is not supported".to_string());
+        }
+
+        if self.bunching_num_dead_cards != 0 {
+            return Err("Bunching effect is not supported".to_string());
+        }
+
+        if self.leaf_evaluator.is_some() {
+            return Err("Leaf evaluator is not supported".to_string());
+        }
+
+        // lines of the initial street can be removed from the action tree
+        let (tree_lines, chance_lines): (Vec<_>, Vec<_>) = lines
+            .iter()
+            .cloned()
+            .partition(|line| !line.iter().any(|a| matches!(a, Action::Chance(_))));
+
+        let mut action_tree = ActionTree::new(self.tree_config.clone())?;
+        for line in &self.added_lines {
+            action_tree.add_line(line)?;
+        }
+        for line in self.removed_lines.iter().chain(&tree_lines) {
+            action_tree.remove_line(line)?;
+        }
+
+        // replay the game-level removals unless they are after the given lines
+        let game_lines = self
+            .removed_game_lines
+            .iter()
+            .filter(|line| !lines.iter().any(|prefix| line.starts_with(prefix)))
+            .cloned()
+            .chain(chance_lines)
+            .collect::<Vec<_>>();
+
+        let mut game = PostFlopGame::with_config(self.card_config.clone(), action_tree)?;
+        game.remove_lines(&game_lines)?;
+        game.allocate_memory_with_compression(&self.compression, &self.storage_backend)?;
+
+        game.warm_start_from(self)?;
+
+        Ok(game)
+    }
+
+    /// Simplifies the game tree based on the preliminary solution of this game, and resolves it.
+    ///
+    /// This method removes the [`low_frequency_lines`] with `threshold`, creates the
+    /// [`pruned_game`] warm-started from this game, and continues solving it by [`solve_from`]
+    /// with the given parameters. `start_iteration` should usually be the number of iterations of
+    /// the preliminary solve, which must be performed by [`solve_step`] (see [`pruned_game`]). The
+    /// resolved game is finalized.
+    ///
+    /// # Examples
+    /// ```
+    /// use postflop_solver::*;
+    ///
+    /// let card_config = CardConfig {
+    ///     range: ["TT+,AQs+".parse().unwrap(), "99+,AJs+".parse().unwrap()],
+    ///     flop: flop_from_str("Td9d6h").unwrap(),
+    ///     turn: card_from_str("Qc").unwrap(),
+    ///     river: card_from_str("2s").unwrap(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let sizes = BetSizeOptions::try_from(("25%, 50%, 150%", "")).unwrap();
+    /// let tree_config = TreeConfig {
+    ///     initial_state: BoardState::River,
+    ///     starting_pot: 100,
+    ///     effective_stack: 300,
+    ///     river_bet_sizes: [sizes.clone(), sizes],
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let action_tree = ActionTree::new(tree_config).unwrap();
+    /// let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    /// game.allocate_memory(false);
+    /// for t in 0..50 {
+    ///     solve_step(&game, t);
+    /// }
+    ///
+    /// let report = game.prune_and_resolve(0.05, 50, 100, 0.5).unwrap();
+    /// let mut pruned = report.game;
+    /// for line in &report.removed_lines {
+    ///     assert!(pruned.apply_line(line).is_err());
+    /// }
+    /// ```
+    ///
+    /// [`low_frequency_lines`]: #method.low_frequency_lines
+    /// [`pruned_game`]: #method.pruned_game
+    pub fn prune_and_resolve(
+        &self,
+        threshold: f32,
+        start_iteration: u32,
+        max_num_iterations: u32,
+        target_exploitability: f32,
+    ) -> Result<PruningReport, String> {
+        if self.state == State::Solved {
+            return Err("Game is already solved".to_string());
+        }
+
+        if !self.is_ready() {
+            return Err("Game is not ready".to_string());
+        }
+
+        let exploitability_before = compute_exploitability(self);
+        let removed_lines = self.low_frequency_lines(threshold);
+        let mut game = self.pruned_game(&removed_lines)?;
+
+        let exploitability_after = solve_from(
+            &mut game,
+            start_iteration,
+            max_num_iterations,
+            target_exploitability,
+            false,
+        );
+
+        Ok(PruningReport {
+            game,
+            removed_lines,
+            exploitability_before,
+            exploitability_after,
+        })
+    }
+}
@@ -47,10 +47,7 @@ impl PostFlopGame {
     #[inline]
     pub fn target_memory_usage(&self) -> u64 {
         match self.target_storage_mode {
-            BoardState::River => match self.is_compression_enabled {
-                false => self.memory_usage().0,
-                true => self.memory_usage().1,
-            },
+            BoardState::River => self.memory_usage_with_compression(&self.compression),
             _ => {
                 let num_target_storage = self.num_target_storage();
                 num_target_storage.iter().map(|&x| x as u64).sum::<u64>() + self.misc_memory_usage
@@ -64,10 +61,9 @@ impl PostFlopGame {
             return [0; 4];
         }
 
-        let num_bytes = if self.is_compression_enabled { 2 } else { 4 };
         if self.target_storage_mode == BoardState::River {
             // omit storing the counterfactual values
-            return [num_bytes * self.num_storage as usize, 0, 0, 0];
+            return [self.storage1.len(), 0, 0, 0];
         }
 
         let mut node_index = match self.target_storage_mode {
@@ -80,18 +76,22 @@ impl PostFlopGame {
         while num_storage.iter().any(|&x| x == 0) {
             node_index -= 1;
             let node = self.node_arena[node_index].lock();
+            let precision = self.compression.precision(node.street());
+            let num_bytes = Self::storage_num_bytes(&precision);
             if num_storage[0] == 0 && !node.is_terminal() && !node.is_chance() {
-                let offset = unsafe { node.storage1.offset_from(self.storage1.as_ptr()) };
+                let offset1 = unsafe { node.storage1.offset_from(self.storage1.as_ptr()) };
+                let offset2 = unsafe { node.storage2.offset_from(self.storage2.as_ptr()) };
                 let offset_ip = unsafe { node.storage3.offset_from(self.storage_ip.as_ptr()) };
-                let len = num_bytes * node.num_elements as usize;
-                let len_ip = num_bytes * node.num_elements_ip as usize;
-                num_storage[0] = offset as usize + len;
-                num_storage[1] = offset as usize + len;
+                let len1 = num_bytes[0] * node.num_elements as usize;
+                let len2 = num_bytes[1] * node.num_elements as usize;
+                let len_ip = num_bytes[2] * node.num_elements_ip as usize;
+                num_storage[0] = offset1 as usize + len1;
+                num_storage[1] = offset2 as usize + len2;
                 num_storage[2] = offset_ip as usize + len_ip;
             }
             if num_storage[3] == 0 && node.is_chance() {
                 let offset = unsafe { node.storage1.offset_from(self.storage_chance.as_ptr()) };
-                let len = num_bytes * node.num_elements as usize;
+                let len = num_bytes[2] * node.num_elements as usize;
                 num_storage[3] = offset as usize + len;
             }
         }
@@ -100,10 +100,10 @@ impl PostFlopGame {
     }
 }
 
-static VERSION_STR: &str = "2023-03-19";
+static VERSION_STR: &str = "2026-10-18";
 
 thread_local! {
-    static PTR_BASE: Cell<[*const u8; 2]> = Cell::new([ptr::null(); 2]);
+    static PTR_BASE: Cell<[*const u8; 3]> = Cell::new([ptr::null(); 3]);
     static CHANCE_BASE: Cell<*const u8> = Cell::new(ptr::null());
     static PTR_BASE_MUT: Cell<[*mut u8; 3]> = Cell::new([ptr::null_mut(); 3]);
     static CHANCE_BASE_MUT: Cell<*mut u8> = Cell::new(ptr::null_mut());
@@ -126,10 +126,11 @@ impl Encode for PostFlopGame {
         self.tree_config.encode(encoder)?;
         self.added_lines.encode(encoder)?;
         self.removed_lines.encode(encoder)?;
+        self.removed_game_lines.encode(encoder)?;
         self.action_root.encode(encoder)?;
         self.target_storage_mode.encode(encoder)?;
         self.num_nodes.encode(encoder)?;
-        self.is_compression_enabled.encode(encoder)?;
+        self.compression.encode(encoder)?;
         self.num_storage.encode(encoder)?;
         self.num_storage_ip.encode(encoder)?;
         self.num_storage_chance.encode(encoder)?;
@@ -150,12 +151,21 @@ impl Encode for PostFlopGame {
         locking_strategy.retain(|&i, _| i < num_nodes);
         locking_strategy.encode(encoder)?;
 
+        // aggregate-frequency constraints (need to filter)
+        let mut aggregate_constraints = self.aggregate_constraints.clone();
+        aggregate_constraints.retain(|&i, _| i < num_nodes);
+        aggregate_constraints.encode(encoder)?;
+
         // store base pointers
         PTR_BASE.with(|c| {
             if self.state >= State::MemoryAllocated {
-                c.set([self.storage1.as_ptr(), self.storage_ip.as_ptr()]);
+                c.set([
+                    self.storage1.as_ptr(),
+                    self.storage2.as_ptr(),
+                    self.storage_ip.as_ptr(),
+                ]);
             } else {
-                c.set([ptr::null(); 2]);
+                c.set([ptr::null(); 3]);
             }
         });
 
@@ -191,10 +201,11 @@ impl Decode for PostFlopGame {
             tree_config: Decode::decode(decoder)?,
             added_lines: Decode::decode(decoder)?,
             removed_lines: Decode::decode(decoder)?,
+            removed_game_lines: Decode::decode(decoder)?,
             action_root: Decode::decode(decoder)?,
             storage_mode: Decode::decode(decoder)?,
             num_nodes: Decode::decode(decoder)?,
-            is_compression_enabled: Decode::decode(decoder)?,
+            compression: Decode::decode(decoder)?,
             num_storage: Decode::decode(decoder)?,
             num_storage_ip: Decode::decode(decoder)?,
             num_storage_chance: Decode::decode(decoder)?,
@@ -204,15 +215,16 @@ impl Decode for PostFlopGame {
             storage_ip: Decode::decode(decoder)?,
             storage_chance: Decode::decode(decoder)?,
             locking_strategy: Decode::decode(decoder)?,
+            aggregate_constraints: Decode::decode(decoder)?,
             ..Default::default()
         };
 
         game.target_storage_mode = game.storage_mode;
         if game.storage_mode == BoardState::River && game.state >= State::MemoryAllocated {
-            let num_bytes = if game.is_compression_enabled { 2 } else { 4 };
-            game.storage2 = vec![0; (num_bytes * game.num_storage) as usize];
-            game.storage_ip = vec![0; (num_bytes * game.num_storage_ip) as usize];
-            game.storage_chance = vec![0; (num_bytes * game.num_storage_chance) as usize];
+            let storage_sizes = game.storage_sizes(&game.compression);
+            game.storage2 = vec![0; storage_sizes[1] as usize].into();
+            game.storage_ip = vec![0; storage_sizes[2] as usize].into();
+            game.storage_chance = vec![0; storage_sizes[3] as usize].into();
         }
 
         // store base pointers
@@ -283,7 +295,8 @@ impl Encode for PostFlopNode {
                 let bases = PTR_BASE.with(|c| c.get());
                 unsafe {
                     self.storage1.offset_from(bases[0]).encode(encoder)?;
-                    self.storage3.offset_from(bases[1]).encode(encoder)?;
+                    self.storage2.offset_from(bases[1]).encode(encoder)?;
+                    self.storage3.offset_from(bases[2]).encode(encoder)?;
                 }
             }
         }
@@ -323,10 +336,11 @@ impl Decode for PostFlopNode {
         } else {
             let bases = PTR_BASE_MUT.with(|c| c.get());
             if !bases[0].is_null() {
-                let offset = isize::decode(decoder)?;
+                let offset1 = isize::decode(decoder)?;
+                let offset2 = isize::decode(decoder)?;
                 let offset_ip = isize::decode(decoder)?;
-                node.storage1 = unsafe { bases[0].offset(offset) };
-                node.storage2 = unsafe { bases[1].offset(offset) };
+                node.storage1 = unsafe { bases[0].offset(offset1) };
+                node.storage2 = unsafe { bases[1].offset(offset2) };
                 node.storage3 = unsafe { bases[2].offset(offset_ip) };
             }
         }
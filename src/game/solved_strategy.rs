@@ -0,0 +1,463 @@
+use super::*;
+use crate::interface::*;
+use crate::utility::*;
+
+/// A node of [`SolvedStrategy`].
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "bincode", derive(Decode, Encode))]
+struct SolvedNode {
+    prev_action: Action,
+    player: u8,
+    turn: Card,
+    river: Card,
+    amount: i32,
+    children_offset: u32,
+    num_children: u16,
+    strategy_offset: u64,
+}
+
+/// A compact, read-only representation of the average strategy of a solved [`PostFlopGame`].
+///
+/// This struct only holds the tree structure, the private hands, and the normalized strategy of
+/// each decision node quantized to 8 bits, so it is much smaller than the original game and does
+/// not allocate any solver storage. It can be obtained by [`PostFlopGame::export_strategy`] and
+/// saved/loaded by [`save_data_to_file`]/[`load_data_from_file`] (requires the `bincode` feature).
+///
+/// The navigation methods behave the same as those of [`PostFlopGame`].
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// let card_config = CardConfig {
+///     range: ["TT+,AQs+".parse().unwrap(), "99+,AJs+".parse().unwrap()],
+///     flop: flop_from_str("Td9d6h").unwrap(),
+///     turn: card_from_str("Qc").unwrap(),
+///     river: card_from_str("2s").unwrap(),
+///     ..Default::default()
+/// };
+///
+/// let tree_config = TreeConfig {
+///     initial_state: BoardState::River,
+///     starting_pot: 100,
+///     effective_stack: 100,
+///     river_bet_sizes: [("50%", "").try_into().unwrap(), ("50%", "").try_into().unwrap()],
+///     ..Default::default()
+/// };
+///
+/// let action_tree = ActionTree::new(tree_config).unwrap();
+/// let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+/// game.allocate_memory(false);
+/// solve(&mut game, 100, 0.0, false);
+///
+/// let mut strategy = game.export_strategy().unwrap();
+/// game.play(1);
+/// strategy.play(1);
+///
+/// let actions = strategy.available_actions();
+/// assert_eq!(actions, game.available_actions());
+/// assert_eq!(strategy.strategy().len(), actions.len() * strategy.num_private_hands(1));
+///
+/// # #[cfg(feature = "bincode")]
+/// # {
+/// let mut buf = Vec::new();
+/// save_data_into_std_write(&strategy, "memo", &mut buf, None).unwrap();
+/// let (loaded, _): (SolvedStrategy, _) =
+///     load_data_from_std_read(&mut buf.as_slice(), None).unwrap();
+/// assert_eq!(loaded.memory_usage(), strategy.memory_usage());
+/// # }
+/// ```
+///
+/// [`save_data_to_file`]: crate::save_data_to_file
+/// [`load_data_from_file`]: crate::load_data_from_file
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "bincode", derive(Decode, Encode))]
+pub struct SolvedStrategy {
+    // configurations
+    flop: [Card; 3],
+    turn: Card,
+    river: Card,
+    tree_config: TreeConfig,
+    private_cards: [Vec<(Card, Card)>; 2],
+
+    // isomorphism information (see `PostFlopGame`)
+    isomorphism_ref_turn: Vec<u8>,
+    isomorphism_card_turn: Vec<Card>,
+    isomorphism_swap_turn: [SwapList; 4],
+    isomorphism_ref_river: Vec<Vec<u8>>,
+    isomorphism_card_river: [Vec<Card>; 4],
+    isomorphism_swap_river: [[SwapList; 4]; 4],
+
+    // tree structure and quantized strategy
+    nodes: Vec<SolvedNode>,
+    strategy: Vec<u8>,
+
+    // navigation state
+    action_history: Vec<usize>,
+    node_history: Vec<usize>,
+    current_turn: Card,
+    current_river: Card,
+    turn_swapped_suit: Option<(u8, u8)>,
+    turn_swap: Option<u8>,
+    river_swap: Option<(u8, u8)>,
+}
+
+impl PostFlopGame {
+    /// Exports the average strategy of all decision nodes as a [`SolvedStrategy`].
+    ///
+    /// The exported strategy reflects the locked strategies. Returns `Err` if the game is not
+    /// solved or the storage of some nodes is not available (i.e., the game was loaded with a
+    /// limited storage mode).
+    pub fn export_strategy(&self) -> Result<SolvedStrategy, String> {
+        if self.state != State::Solved {
+            return Err("Game is not solved".to_string());
+        }
+
+        if self.storage_mode != BoardState::River {
+            return Err("Strategy of some nodes is not available".to_string());
+        }
+
+        let mut nodes = Vec::with_capacity(self.node_arena.len());
+        let mut strategy = Vec::new();
+
+        for node in &self.node_arena {
+            let node = node.lock();
+            let strategy_offset = strategy.len() as u64;
+
+            if !node.is_terminal() && !node.is_chance() {
+                let mut node_strategy = node_strategy(self, &node);
+                apply_locking_strategy(&mut node_strategy, self.locking_strategy(&node));
+                strategy.extend(
+                    node_strategy
+                        .iter()
+                        .map(|&x| (x * u8::MAX as f32).round() as u8),
+                );
+            }
+
+            nodes.push(SolvedNode {
+                prev_action: node.prev_action,
+                player: node.player,
+                turn: node.turn,
+                river: node.river,
+                amount: node.amount,
+                children_offset: node.children_offset,
+                num_children: node.num_children,
+                strategy_offset,
+            });
+        }
+
+        Ok(SolvedStrategy {
+            flop: self.card_config.flop,
+            turn: self.card_config.turn,
+            river: self.card_config.river,
+            tree_config: self.tree_config.clone(),
+            private_cards: self.private_cards.clone(),
+            isomorphism_ref_turn: self.isomorphism_ref_turn.clone(),
+            isomorphism_card_turn: self.isomorphism_card_turn.clone(),
+            isomorphism_swap_turn: self.isomorphism_swap_turn.clone(),
+            isomorphism_ref_river: self.isomorphism_ref_river.clone(),
+            isomorphism_card_river: self.isomorphism_card_river.clone(),
+            isomorphism_swap_river: self.isomorphism_swap_river.clone(),
+            nodes,
+            strategy,
+            current_turn: self.card_config.turn,
+            current_river: self.card_config.river,
+            ..Default::default()
+        })
+    }
+}
+
+impl SolvedStrategy {
+    /// Returns the tree configuration of the original game.
+    #[inline]
+    pub fn tree_config(&self) -> &TreeConfig {
+        &self.tree_config
+    }
+
+    /// Returns the list of private hands of the given player.
+    ///
+    /// The order is the same as [`PostFlopGame::private_cards`].
+    #[inline]
+    pub fn private_cards(&self, player: usize) -> &[(Card, Card)] {
+        &self.private_cards[player]
+    }
+
+    /// Returns the number of private hands of the given player.
+    #[inline]
+    pub fn num_private_hands(&self, player: usize) -> usize {
+        self.private_cards[player].len()
+    }
+
+    /// Returns whether the strategy is exported from a solved game.
+    #[inline]
+    pub(crate) fn is_ready(&self) -> bool {
+        !self.nodes.is_empty()
+    }
+
+    /// Returns the memory usage in bytes.
+    #[inline]
+    pub fn memory_usage(&self) -> u64 {
+        let swap_len = |swap: &SwapList| (swap[0].len() + swap[1].len()) * 4;
+        let iso_len = self.isomorphism_ref_turn.len()
+            + self.isomorphism_card_turn.len()
+            + self
+                .isomorphism_swap_turn
+                .iter()
+                .map(swap_len)
+                .sum::<usize>()
+            + self
+                .isomorphism_ref_river
+                .iter()
+                .map(Vec::len)
+                .sum::<usize>()
+            + self
+                .isomorphism_card_river
+                .iter()
+                .map(Vec::len)
+                .sum::<usize>()
+            + self
+                .isomorphism_swap_river
+                .iter()
+                .flatten()
+                .map(swap_len)
+                .sum::<usize>();
+        let hands_len = (self.private_cards[0].len() + self.private_cards[1].len()) * 2;
+        let nodes_len = self.nodes.len() * std::mem::size_of::<SolvedNode>();
+        (iso_len + hands_len + nodes_len + self.strategy.len()) as u64
+    }
+
+    /// Moves the current node back to the root node.
+    #[inline]
+    pub fn back_to_root(&mut self) {
+        self.action_history.clear();
+        self.node_history.clear();
+        self.current_turn = self.turn;
+        self.current_river = self.river;
+        self.turn_swapped_suit = None;
+        self.turn_swap = None;
+        self.river_swap = None;
+    }
+
+    /// Returns the history of the current node.
+    ///
+    /// The history is a list of action indices, i.e., the arguments of [`play`]. For chance nodes,
+    /// the dealt card is stored.
+    ///
+    /// [`play`]: #method.play
+    #[inline]
+    pub fn history(&self) -> &[usize] {
+        &self.action_history
+    }
+
+    /// Applies the given history from the root node.
+    #[inline]
+    pub fn apply_history(&mut self, history: &[usize]) {
+        self.back_to_root();
+        for &action in history {
+            self.play(action);
+        }
+    }
+
+    /// Returns whether the current node is a terminal node.
+    #[inline]
+    pub fn is_terminal_node(&self) -> bool {
+        let node = self.node();
+        node.player & PLAYER_TERMINAL_FLAG != 0 || node.amount == self.tree_config.effective_stack
+    }
+
+    /// Returns whether the current node is a chance node (i.e., turn/river node).
+    #[inline]
+    pub fn is_chance_node(&self) -> bool {
+        self.node().player & PLAYER_CHANCE_FLAG != 0 && !self.is_terminal_node()
+    }
+
+    /// Returns the available actions for the current node.
+    ///
+    /// As in [`PostFlopGame::available_actions`], isomorphic chances are grouped into one
+    /// representative action.
+    #[inline]
+    pub fn available_actions(&self) -> Vec<Action> {
+        if self.is_terminal_node() {
+            Vec::new()
+        } else {
+            let index = self.current_index();
+            self.children_range(index)
+                .map(|i| self.nodes[i].prev_action)
+                .collect()
+        }
+    }
+
+    /// Returns the current player (0 = OOP, 1 = IP).
+    ///
+    /// If the current node is a terminal node or a chance node, returns an undefined value.
+    #[inline]
+    pub fn current_player(&self) -> usize {
+        self.node().player as usize
+    }
+
+    /// Returns the current board.
+    #[inline]
+    pub fn current_board(&self) -> Vec<u8> {
+        let mut ret = self.flop.to_vec();
+        if self.current_turn != NOT_DEALT {
+            ret.push(self.current_turn);
+        }
+        if self.current_river != NOT_DEALT {
+            ret.push(self.current_river);
+        }
+        ret
+    }
+
+    /// Plays the given action. Playing an action from a terminal node is not allowed.
+    ///
+    /// - If the current node is a chance node, the `action` corresponds to the dealt card.
+    /// - If the current node is not a chance node, plays the `action`-th action of
+    ///   [`available_actions`].
+    ///
+    /// Panics if the current node is a terminal node or the action is invalid.
+    ///
+    /// [`available_actions`]: #method.available_actions
+    pub fn play(&mut self, action: usize) {
+        if self.is_terminal_node() {
+            panic!("Terminal node is not allowed");
+        }
+
+        let index = self.current_index();
+
+        // chance node
+        if self.is_chance_node() {
+            let is_turn = self.current_turn == NOT_DEALT;
+            let actual_card = action as Card;
+
+            // swap the suit if swapping was performed in turn
+            let action_card = match self.turn_swapped_suit {
+                Some((suit1, suit2)) if actual_card & 3 == suit1 => actual_card - suit1 + suit2,
+                Some((suit1, suit2)) if actual_card & 3 == suit2 => actual_card + suit1 - suit2,
+                _ => actual_card,
+            };
+
+            let actions = self.available_actions();
+            let mut action_index = actions
+                .iter()
+                .position(|&a| a == Action::Chance(action_card))
+                .unwrap_or(usize::MAX);
+
+            // find the action index from isomorphic chances
+            if action_index == usize::MAX {
+                let node = &self.nodes[index];
+                let (isomorphism, isomorphic_cards) = if node.turn == NOT_DEALT {
+                    (&self.isomorphism_ref_turn, &self.isomorphism_card_turn)
+                } else {
+                    (
+                        &self.isomorphism_ref_river[node.turn as usize],
+                        &self.isomorphism_card_river[node.turn as usize & 3],
+                    )
+                };
+                for (i, &repr_index) in isomorphism.iter().enumerate() {
+                    if action_card == isomorphic_cards[i] {
+                        action_index = repr_index as usize;
+                        if is_turn {
+                            if let Action::Chance(repr_card) = actions[repr_index as usize] {
+                                self.turn_swapped_suit = Some((action_card & 3, repr_card & 3));
+                            }
+                            self.turn_swap = Some(action_card & 3);
+                        } else {
+                            // see `PostFlopGame::play`
+                            self.river_swap = Some((
+                                self.current_turn & 3,
+                                self.isomorphism_card_river[self.current_turn as usize & 3][i] & 3,
+                            ));
+                        }
+                        break;
+                    }
+                }
+            }
+
+            if action_index == usize::MAX {
+                panic!("Invalid action");
+            }
+
+            self.node_history
+                .push(self.children_range(index).start + action_index);
+            if is_turn {
+                self.current_turn = actual_card;
+            } else {
+                self.current_river = actual_card;
+            }
+        }
+        // player node
+        else {
+            let children = self.children_range(index);
+            if action >= children.len() {
+                panic!("Invalid action");
+            }
+            self.node_history.push(children.start + action);
+        }
+
+        self.action_history.push(action);
+    }
+
+    /// Returns the strategy of the current player.
+    ///
+    /// The return value is a vector of the length of `#(actions) * #(private hands)`, and the
+    /// layout is the same as [`PostFlopGame::strategy`]. The values are quantized to 8 bits and
+    /// normalized so that the probabilities of each hand sum to 1.
+    ///
+    /// Panics if the current node is a terminal node or a chance node.
+    pub fn strategy(&self) -> Vec<f32> {
+        if self.is_terminal_node() {
+            panic!("Terminal node is not allowed");
+        }
+
+        if self.is_chance_node() {
+            panic!("Chance node is not allowed");
+        }
+
+        let node = self.node();
+        let player = self.current_player();
+        let num_actions = node.num_children as usize;
+        let num_hands = self.num_private_hands(player);
+
+        let offset = node.strategy_offset as usize;
+        let quantized = &self.strategy[offset..offset + num_actions * num_hands];
+        let mut ret = normalized_strategy_compressed(quantized, num_actions);
+
+        // apply the swap
+        let turn_swap = self
+            .turn_swap
+            .map(|suit| &self.isomorphism_swap_turn[suit as usize][player]);
+        let river_swap = self.river_swap.map(|(turn_suit, suit)| {
+            &self.isomorphism_swap_river[turn_suit as usize][suit as usize][player]
+        });
+
+        ret.chunks_exact_mut(num_hands).for_each(|chunk| {
+            for swap in [turn_swap, river_swap].into_iter().flatten() {
+                for &(i, j) in swap {
+                    chunk.swap(i as usize, j as usize);
+                }
+            }
+        });
+
+        ret
+    }
+
+    /// Returns the index of the current node.
+    #[inline]
+    fn current_index(&self) -> usize {
+        *self.node_history.last().unwrap_or(&0)
+    }
+
+    /// Returns the current node.
+    #[inline]
+    fn node(&self) -> &SolvedNode {
+        &self.nodes[self.current_index()]
+    }
+
+    /// Returns the range of the indices of the children of the given node.
+    #[inline]
+    fn children_range(&self, index: usize) -> std::ops::Range<usize> {
+        let node = &self.nodes[index];
+        let start = index + node.children_offset as usize;
+        start..start + node.num_children as usize
+    }
+}
@@ -0,0 +1,244 @@
+use crate::action_tree::BoardState;
+use crate::interface::{Precision, StoragePrecision};
+use std::ops::{Deref, DerefMut};
+use std::path::PathBuf;
+
+#[cfg(feature = "mmap")]
+use std::{
+    fs::{self, File, OpenOptions},
+    path::Path,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+#[cfg(feature = "mmap")]
+use memmap2::MmapMut;
+
+#[cfg(feature = "bincode")]
+use bincode::{de::Decoder, error::DecodeError, Decode, Encode};
+
+/// An enum representing where the storage of [`PostFlopGame`] is allocated.
+///
+/// [`PostFlopGame`]: crate::PostFlopGame
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum StorageBackend {
+    /// Allocates the storage on the heap (default).
+    #[default]
+    Memory,
+
+    /// Memory-maps the storage to temporary files created in the specified directory.
+    ///
+    /// This allows solving game trees larger than the physical memory at the cost of throughput.
+    /// The files are removed when the storage is released. Requires the `mmap` feature.
+    Disk(PathBuf),
+}
+
+/// A struct representing the precision of the storage of [`PostFlopGame`] for each street.
+///
+/// `allocate_memory(false)` corresponds to [`Precision::Float32`] for all values, and
+/// `allocate_memory(true)` corresponds to [`Precision::Int16`] for all values. Since the river
+/// nodes usually account for the majority of the storage, using a lower precision only for the
+/// river is an effective way to reduce the memory usage while keeping the accuracy.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// // 16-bit for the flop and turn, 8-bit strategy and regrets for the river
+/// let compression = CompressionConfig {
+///     river: StoragePrecision {
+///         strategy: Precision::Int8,
+///         regrets: Precision::Int8,
+///         cfvalues: Precision::Int16,
+///     },
+///     ..CompressionConfig::uniform(Precision::Int16)
+/// };
+///
+/// assert!(compression.is_enabled());
+/// assert_eq!(compression.precision(BoardState::Flop).strategy, Precision::Int16);
+/// assert_eq!(compression.precision(BoardState::River).strategy, Precision::Int8);
+/// ```
+///
+/// [`PostFlopGame`]: crate::PostFlopGame
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "bincode", derive(Decode, Encode))]
+pub struct CompressionConfig {
+    /// Precision of the flop nodes.
+    pub flop: StoragePrecision,
+
+    /// Precision of the turn nodes.
+    pub turn: StoragePrecision,
+
+    /// Precision of the river nodes.
+    pub river: StoragePrecision,
+}
+
+impl CompressionConfig {
+    /// Creates a new instance that uses the same precision for all streets and all kinds of values.
+    #[inline]
+    pub fn uniform(precision: Precision) -> Self {
+        let precision = StoragePrecision::uniform(precision);
+        Self {
+            flop: precision,
+            turn: precision,
+            river: precision,
+        }
+    }
+
+    /// Returns the precision of the nodes of the specified street.
+    #[inline]
+    pub fn precision(&self, street: BoardState) -> StoragePrecision {
+        match street {
+            BoardState::Flop => self.flop,
+            BoardState::Turn => self.turn,
+            BoardState::River => self.river,
+        }
+    }
+
+    /// Returns whether any values are compressed.
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        *self != Self::uniform(Precision::Float32)
+    }
+}
+
+impl From<bool> for CompressionConfig {
+    /// Converts the `enable_compression` flag of [`PostFlopGame::allocate_memory`].
+    ///
+    /// [`PostFlopGame::allocate_memory`]: crate::PostFlopGame::allocate_memory
+    #[inline]
+    fn from(enable_compression: bool) -> Self {
+        match enable_compression {
+            false => Self::uniform(Precision::Float32),
+            true => Self::uniform(Precision::Int16),
+        }
+    }
+}
+
+/// A byte arena used as a global storage of [`PostFlopGame`].
+///
+/// [`PostFlopGame`]: crate::PostFlopGame
+pub(super) enum Storage {
+    Memory(Vec<u8>),
+    #[cfg(feature = "mmap")]
+    Disk(DiskStorage),
+}
+
+#[cfg(feature = "mmap")]
+pub(super) struct DiskStorage {
+    mmap: Option<MmapMut>,
+    path: PathBuf,
+}
+
+#[cfg(feature = "mmap")]
+static FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+impl Storage {
+    /// Allocates a zero-filled storage of `len` bytes with the specified backend.
+    pub(super) fn new(len: usize, backend: &StorageBackend) -> Result<Self, String> {
+        match backend {
+            StorageBackend::Memory => Ok(Self::Memory(vec![0; len])),
+            #[cfg(feature = "mmap")]
+            StorageBackend::Disk(_) if len == 0 => Ok(Self::Memory(Vec::new())),
+            #[cfg(feature = "mmap")]
+            StorageBackend::Disk(dir) => Ok(Self::Disk(DiskStorage::new(len, dir)?)),
+            #[cfg(not(feature = "mmap"))]
+            StorageBackend::Disk(_) => Err("Disk backend is not supported".to_string()),
+        }
+    }
+
+    /// Returns whether the storage is backed by a file.
+    #[inline]
+    pub(super) fn is_on_disk(&self) -> bool {
+        match self {
+            Self::Memory(_) => false,
+            #[cfg(feature = "mmap")]
+            Self::Disk(_) => true,
+        }
+    }
+}
+
+impl Default for Storage {
+    #[inline]
+    fn default() -> Self {
+        Self::Memory(Vec::new())
+    }
+}
+
+impl From<Vec<u8>> for Storage {
+    #[inline]
+    fn from(vec: Vec<u8>) -> Self {
+        Self::Memory(vec)
+    }
+}
+
+impl Deref for Storage {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Memory(vec) => vec,
+            #[cfg(feature = "mmap")]
+            Self::Disk(disk) => disk.mmap.as_ref().unwrap(),
+        }
+    }
+}
+
+impl DerefMut for Storage {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            Self::Memory(vec) => vec,
+            #[cfg(feature = "mmap")]
+            Self::Disk(disk) => disk.mmap.as_mut().unwrap(),
+        }
+    }
+}
+
+#[cfg(feature = "bincode")]
+impl Decode for Storage {
+    #[inline]
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(Self::Memory(Decode::decode(decoder)?))
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl DiskStorage {
+    fn new(len: usize, dir: &Path) -> Result<Self, String> {
+        let counter = FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let file_name = format!("postflop-solver-{}-{counter}.bin", std::process::id());
+        let path = dir.join(file_name);
+
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .map_err(|e| format!("Failed to create storage file: {}", e))?;
+
+        // construct first so that the file is removed on failure
+        let mut ret = Self { mmap: None, path };
+        ret.mmap = Some(Self::map(&file, len)?);
+        Ok(ret)
+    }
+
+    fn map(file: &File, len: usize) -> Result<MmapMut, String> {
+        file.set_len(len as u64)
+            .map_err(|e| format!("Failed to resize storage file: {}", e))?;
+
+        // the file is zero-filled by `set_len` and is not shared with other processes
+        unsafe { MmapMut::map_mut(file) }
+            .map_err(|e| format!("Failed to memory-map storage file: {}", e))
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl Drop for DiskStorage {
+    #[inline]
+    fn drop(&mut self) {
+        // unmap before removing the file
+        self.mmap = None;
+        let _ = fs::remove_file(&self.path);
+    }
+}
@@ -1,4 +1,6 @@
 use super::*;
+use crate::bet_size::*;
+use crate::interface::*;
 use crate::range::*;
 use crate::solver::*;
 use crate::utility::*;
@@ -749,6 +751,7 @@ fn remove_lines() {
 
     let res = game.remove_lines(&lines);
     assert!(res.is_ok());
+    assert_eq!(game.removed_game_lines(), lines);
 
     game.allocate_memory(false);
 
@@ -817,8 +820,8 @@ fn isomorphism_monotone() {
         weights.iter().zip(ev.iter()).for_each(|(&w, &v)| {
             assert!(!(w > 0.0 && v == 50.0));
         });
-        assert_eq!(game.turn_swap, expected_turn_swap);
-        assert_eq!(game.river_swap, expected_river_swap);
+        assert_eq!(game.interpreter.turn_swap, expected_turn_swap);
+        assert_eq!(game.interpreter.river_swap, expected_river_swap);
     };
 
     check(&[0, 0, 4], None, None);
@@ -1011,6 +1014,612 @@ fn node_locking_isomorphism() {
     );
 }
 
+#[test]
+fn hand_category() {
+    let cases = [
+        ("AhTc", "Td9d6h", HandCategory::TopPair),
+        ("Ah9c", "Td9d6h", HandCategory::SecondPair),
+        ("Ah6c", "Td9d6h", HandCategory::WeakPair),
+        ("QsQc", "Td9d6h", HandCategory::Overpair),
+        ("9s9c", "Td9d6h", HandCategory::Set),
+        ("8s8c", "Td9d6h", HandCategory::WeakPair),
+        ("Ts9c", "Td9d6h", HandCategory::TwoPair),
+        ("Ts2c", "TdTc6h", HandCategory::Trips),
+        ("As2c", "TdTc6h", HandCategory::HighCard),
+        ("8s7c", "Td9d6h", HandCategory::Straight),
+        ("AdKd", "Td9d6d", HandCategory::Flush),
+        ("Ts6c", "TdTc6h", HandCategory::FullHouse),
+        ("TsTh", "TdTc6h", HandCategory::Quads),
+        ("8d7d", "Td9d6d", HandCategory::StraightFlush),
+        ("5s4c", "Ad3c2h", HandCategory::Straight),
+        ("KsQc", "Td9d6hAc", HandCategory::HighCard),
+    ];
+
+    for (hand, board, expected) in cases {
+        let hand = (
+            card_from_str(&hand[0..2]).unwrap(),
+            card_from_str(&hand[2..4]).unwrap(),
+        );
+        let board = (0..board.len() / 2)
+            .map(|i| card_from_str(&board[2 * i..2 * i + 2]).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(HandCategory::classify(hand, &board), expected);
+    }
+}
+
+#[test]
+fn lock_strategy_by_rules() {
+    let card_config = CardConfig {
+        range: [
+            "TT+,99,66,AQs+,T9s,AKo,ATs".parse().unwrap(),
+            "99+,AJs+".parse().unwrap(),
+        ],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("2c").unwrap(),
+        ..Default::default()
+    };
+
+    let tree_config = TreeConfig {
+        initial_state: BoardState::Turn,
+        starting_pot: 60,
+        effective_stack: 970,
+        turn_bet_sizes: [
+            ("50%", "").try_into().unwrap(),
+            ("50%", "").try_into().unwrap(),
+        ],
+        river_bet_sizes: [
+            ("50%", "").try_into().unwrap(),
+            ("50%", "").try_into().unwrap(),
+        ],
+        ..Default::default()
+    };
+
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    game.allocate_memory(false);
+
+    let board = game.current_board();
+    let hands = game.private_cards(0).to_vec();
+    let category = |h: usize| HandCategory::classify(hands[h], &board);
+    let num_hands = hands.len();
+
+    // bet 100% with sets and two pair, 30% with top pair, check everything else
+    let rules = [
+        LockRule {
+            hands: HandSet::Categories(vec![HandCategory::Set, HandCategory::TwoPair]),
+            frequencies: vec![(Action::Bet(30), 1.0)],
+            is_aggregate: false,
+        },
+        LockRule {
+            hands: HandSet::Categories(vec![HandCategory::TopPair]),
+            frequencies: vec![(Action::Bet(30), 0.3), (Action::Check, 0.7)],
+            is_aggregate: false,
+        },
+        LockRule {
+            hands: HandSet::All,
+            frequencies: vec![(Action::Check, 1.0)],
+            is_aggregate: false,
+        },
+    ];
+
+    game.play(0);
+    game.lock_strategy_by_rules(&[], &rules).unwrap();
+    assert_eq!(game.history(), &[0]);
+
+    game.back_to_root();
+    let locking = game.current_locking_strategy().unwrap();
+    for h in 0..num_hands {
+        let expected_bet = match category(h) {
+            HandCategory::Set | HandCategory::TwoPair => 1.0,
+            HandCategory::TopPair => 0.3,
+            _ => 0.0,
+        };
+        assert!((locking[num_hands + h] - expected_bet).abs() < 1e-6);
+        assert!((locking[h] + locking[num_hands + h] - 1.0).abs() < 1e-6);
+    }
+
+    // bet sets, then bet 65% overall
+    let rules = [
+        LockRule {
+            hands: HandSet::Categories(vec![HandCategory::Set]),
+            frequencies: vec![(Action::Bet(30), 1.0)],
+            is_aggregate: false,
+        },
+        LockRule {
+            hands: HandSet::All,
+            frequencies: vec![(Action::Bet(30), 0.65), (Action::Check, 0.35)],
+            is_aggregate: true,
+        },
+    ];
+
+    game.lock_strategy_by_rules(&[], &rules).unwrap();
+    let locking = game.current_locking_strategy().unwrap();
+    let weights = game.initial_weights(0);
+    let total = weights.iter().sum::<f32>();
+    let bet = (0..num_hands)
+        .map(|h| weights[h] * locking[num_hands + h])
+        .sum::<f32>();
+    assert!((bet / total - 0.65).abs() < 1e-5);
+
+    // invalid rules
+    let invalid_rules = [
+        vec![LockRule {
+            hands: HandSet::All,
+            frequencies: vec![(Action::Bet(100), 1.0)],
+            is_aggregate: false,
+        }],
+        vec![LockRule {
+            hands: HandSet::All,
+            frequencies: vec![(Action::Bet(30), 0.5)],
+            is_aggregate: false,
+        }],
+        vec![
+            LockRule {
+                hands: HandSet::All,
+                frequencies: vec![(Action::Bet(30), 1.0)],
+                is_aggregate: false,
+            },
+            LockRule {
+                hands: HandSet::All,
+                frequencies: vec![(Action::Check, 1.0)],
+                is_aggregate: true,
+            },
+        ],
+    ];
+    for rules in &invalid_rules {
+        assert!(game.lock_strategy_by_rules(&[], rules).is_err());
+    }
+
+    // lock a river node
+    let line = [
+        Action::Check,
+        Action::Check,
+        Action::Chance(card_from_str("3s").unwrap()),
+    ];
+    let rules = [LockRule {
+        hands: HandSet::All,
+        frequencies: vec![(Action::Check, 1.0)],
+        is_aggregate: false,
+    }];
+    game.lock_strategy_by_rules(&line, &rules).unwrap();
+    assert!(game.lock_strategy_by_rules(&line[..2], &rules).is_err());
+    assert!(game
+        .lock_strategy_by_rules(&[Action::Fold], &rules)
+        .is_err());
+
+    // the locked strategy is kept after solving
+    solve(&mut game, 100, 0.0, false);
+    let strategy = game.strategy();
+    for h in 0..num_hands {
+        if category(h) == HandCategory::Set {
+            assert!((strategy[num_hands + h] - 1.0).abs() < 1e-6);
+        }
+    }
+}
+
+#[test]
+fn lock_strategy_by_line() {
+    let card_config = CardConfig {
+        range: [
+            "TT+,AQs+,KQs,AKo".parse().unwrap(),
+            "99+,AJs+,KJs+,AQo+".parse().unwrap(),
+        ],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("Qc").unwrap(),
+        ..Default::default()
+    };
+
+    let tree_config = TreeConfig {
+        initial_state: BoardState::Turn,
+        starting_pot: 60,
+        effective_stack: 970,
+        turn_bet_sizes: [
+            ("50%", "").try_into().unwrap(),
+            ("50%", "").try_into().unwrap(),
+        ],
+        river_bet_sizes: [
+            ("50%", "").try_into().unwrap(),
+            ("50%", "").try_into().unwrap(),
+        ],
+        ..Default::default()
+    };
+
+    let action_tree = ActionTree::new(tree_config.clone()).unwrap();
+    let mut game = PostFlopGame::with_config(card_config.clone(), action_tree).unwrap();
+    game.allocate_memory(false);
+
+    // IP always checks back on every river
+    let line = [
+        Action::Check,
+        Action::Check,
+        Action::Chance(NOT_DEALT),
+        Action::Check,
+    ];
+    let num_hands = game.num_private_hands(1);
+    let mut strategy = vec![0.0; 2 * num_hands];
+    strategy[..num_hands].fill(1.0);
+
+    game.apply_history(&[0, 0]);
+    let num_rivers = game.available_actions().len();
+    game.back_to_root();
+
+    assert_eq!(game.lock_strategy_by_line(&line, &strategy), Ok(num_rivers));
+    assert_eq!(game.history(), &[]);
+
+    let locked_lines = game.locked_lines();
+    assert_eq!(locked_lines.len(), num_rivers);
+    assert!(locked_lines
+        .iter()
+        .all(|l| l.len() == 4 && l[3] == Action::Check));
+
+    // invalid lines and strategies
+    assert!(game.lock_strategy_by_line(&line, &strategy[1..]).is_err());
+    assert!(game.lock_strategy_by_line(&line[..3], &strategy).is_err());
+    assert!(game
+        .unlock_strategy_by_line(&[Action::Chance(NOT_DEALT)])
+        .is_err());
+
+    // export and import
+    let lock_set = game.export_locks();
+    assert_eq!(lock_set.entries.len(), num_rivers);
+
+    #[cfg(feature = "bincode")]
+    let lock_set = {
+        let mut buf = Vec::new();
+        crate::save_data_into_std_write(&lock_set, "", &mut buf, None).unwrap();
+        let (loaded, _): (LockSet, _) =
+            crate::load_data_from_std_read(&mut buf.as_slice(), None).unwrap();
+        assert_eq!(loaded, lock_set);
+        loaded
+    };
+
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game2 = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    game2.allocate_memory(false);
+    game2.import_locks(&lock_set).unwrap();
+    assert_eq!(game2.locked_lines(), locked_lines);
+
+    for line in &locked_lines {
+        game.apply_line(line).unwrap();
+        game2.apply_line(line).unwrap();
+        assert_eq!(
+            game.current_locking_strategy(),
+            game2.current_locking_strategy()
+        );
+    }
+
+    // unlock
+    assert_eq!(game.unlock_strategy_by_line(&line), Ok(num_rivers));
+    assert!(game.locked_lines().is_empty());
+}
+
+#[test]
+fn aggregate_constraint() {
+    let card_config = CardConfig {
+        range: [
+            "TT+,99,66,AQs+,T9s,AKo,ATs".parse().unwrap(),
+            "99+,AJs+".parse().unwrap(),
+        ],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("2c").unwrap(),
+        ..Default::default()
+    };
+
+    let tree_config = TreeConfig {
+        initial_state: BoardState::Turn,
+        starting_pot: 60,
+        effective_stack: 970,
+        turn_bet_sizes: [
+            ("50%", "").try_into().unwrap(),
+            ("50%", "").try_into().unwrap(),
+        ],
+        river_bet_sizes: [
+            ("50%", "").try_into().unwrap(),
+            ("50%", "").try_into().unwrap(),
+        ],
+        ..Default::default()
+    };
+
+    let bounds = [[(Action::Bet(30), 0.8, 0.8)], [(Action::Bet(30), 0.0, 0.2)]];
+
+    for (compression, bounds) in [false, true].into_iter().zip(&bounds) {
+        let action_tree = ActionTree::new(tree_config.clone()).unwrap();
+        let mut game = PostFlopGame::with_config(card_config.clone(), action_tree).unwrap();
+        game.allocate_memory(compression);
+
+        // invalid bounds
+        let invalid_bounds = [
+            [(Action::Bet(100), 0.0, 1.0)],
+            [(Action::Bet(30), 0.5, 0.4)],
+            [(Action::Bet(30), 0.0, 1.1)],
+        ];
+        for invalid in &invalid_bounds {
+            assert!(game.set_aggregate_constraint(&[], invalid).is_err());
+        }
+
+        assert_eq!(game.set_aggregate_constraint(&[], bounds), Ok(1));
+        solve(&mut game, 200, 0.0, false);
+
+        let (_, min, max) = bounds[0];
+        let freq = game.current_aggregate_frequencies();
+        assert!((freq.iter().sum::<f32>() - 1.0).abs() < 1e-4);
+        assert!(min as f32 - 1e-3 <= freq[1] && freq[1] <= max as f32 + 1e-3);
+
+        // the solver chooses which hands take each action
+        let strategy = game.strategy();
+        let num_hands = game.num_private_hands(0);
+        let bet = &strategy[num_hands..];
+        let bet_max = bet.iter().cloned().fold(0.0, f32::max);
+        let bet_min = bet.iter().cloned().fold(1.0, f32::min);
+        assert!(bet_max - bet_min > 0.2);
+    }
+}
+
+#[test]
+fn opponent_model() {
+    // texture classification
+    let board = |s: &str| {
+        let flop = flop_from_str(s).unwrap();
+        flop.to_vec()
+    };
+    assert!(BoardTexture::Rainbow.matches(&board("Td9c6h")));
+    assert!(BoardTexture::TwoTone.matches(&board("Td9d6h")));
+    assert!(BoardTexture::Monotone.matches(&board("Td9d6d")));
+    assert!(BoardTexture::Paired.matches(&board("TdTc6h")));
+    assert!(BoardTexture::Connected.matches(&board("Td9d6h")));
+    assert!(BoardTexture::Connected.matches(&board("Ad2c4h")));
+    assert!(BoardTexture::Disconnected.matches(&board("Kd7c2h")));
+    assert!(BoardTexture::AceHigh.matches(&board("Ad7c2h")));
+    assert!(BoardTexture::BroadwayHigh.matches(&board("Td9d6h")));
+    assert!(BoardTexture::LowHigh.matches(&board("9d7c2h")));
+
+    // parse errors
+    let invalid_models = [
+        "X ; * ; B=0.6 ; X=0.4",
+        "Y ; * ; B=0.6",
+        "X ; wet ; B=0.6",
+        "X ; * ; B=1.2",
+        "X ; * ; B=0.6 B=0.3",
+        "X ; * ; *=0.6",
+        "X ; * ; X=0.6 B=0.6",
+        "X ; * ;",
+    ];
+    for model in invalid_models {
+        assert!(model.parse::<OpponentModel>().is_err());
+    }
+
+    let card_config = CardConfig {
+        range: [
+            "TT+,99,66,AQs+,T9s,AKo,ATs".parse().unwrap(),
+            "99+,AJs+".parse().unwrap(),
+        ],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("2c").unwrap(),
+        ..Default::default()
+    };
+
+    let tree_config = TreeConfig {
+        initial_state: BoardState::Turn,
+        starting_pot: 60,
+        effective_stack: 970,
+        turn_bet_sizes: [
+            ("50%", "").try_into().unwrap(),
+            ("50%, 100%", "").try_into().unwrap(),
+        ],
+        river_bet_sizes: [
+            ("50%", "").try_into().unwrap(),
+            ("50%", "").try_into().unwrap(),
+        ],
+        ..Default::default()
+    };
+
+    let model = "
+        # IP stabs 30% in total over both sizes
+        X ; * ; B=30% tol=1%
+        # OOP checks on the river when the board pairs
+        X X | * ; paired ; X=0.9 tol=0.01
+        # no raise is available after an all-in
+        B R ; * ; R=0.1
+    ";
+    let model: OpponentModel = model.parse().unwrap();
+
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    game.allocate_memory(false);
+
+    // 1 turn node + 12 rivers pairing the board
+    assert_eq!(game.apply_opponent_model(&model), Ok(13));
+    assert!(game.history().is_empty());
+
+    solve(&mut game, 200, 0.0, false);
+
+    game.apply_history(&[0]);
+    let actions = game.available_actions();
+    let freq = game.current_aggregate_frequencies();
+    let bet = (0..actions.len())
+        .filter(|&i| matches!(actions[i], Action::Bet(_) | Action::AllIn(_)))
+        .map(|i| freq[i])
+        .sum::<f32>();
+    assert!((bet - 0.3).abs() < 0.01 + 1e-3);
+
+    for card in ["Ts", "2h"] {
+        game.apply_history(&[0, 0, card_from_str(card).unwrap() as usize]);
+        let freq = game.current_aggregate_frequencies();
+        assert!((freq[0] - 0.9).abs() < 0.01 + 1e-3);
+    }
+}
+
+#[test]
+fn play_action_and_line() {
+    let card_config = CardConfig {
+        range: ["TT+,AQs+".parse().unwrap(), "99+,AJs+".parse().unwrap()],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("2c").unwrap(),
+        ..Default::default()
+    };
+
+    let tree_config = TreeConfig {
+        initial_state: BoardState::Turn,
+        starting_pot: 60,
+        effective_stack: 970,
+        turn_bet_sizes: [
+            ("50%", "").try_into().unwrap(),
+            ("50%, 100%", "").try_into().unwrap(),
+        ],
+        river_bet_sizes: [
+            ("50%", "").try_into().unwrap(),
+            ("50%", "").try_into().unwrap(),
+        ],
+        ..Default::default()
+    };
+
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    game.allocate_memory(false);
+
+    // round trip
+    let line = line_from_str("X B60 C | Qs | B90").unwrap();
+    assert_eq!(game.apply_line(&line), Ok(()));
+    assert_eq!(game.current_line(), line);
+    assert_eq!(
+        line_to_string(&game.current_line()),
+        Ok("X B60 C | Qs | B90".to_string())
+    );
+
+    // the same node as the index-based history
+    let history = game.history().to_vec();
+    game.apply_history(&history);
+    assert_eq!(game.current_line(), line);
+
+    // nearest-size mapping
+    game.apply_line(&line_from_str("X").unwrap()).unwrap();
+    assert_eq!(game.nearest_action(Action::Bet(50)), Some(Action::Bet(60)));
+    assert_eq!(game.nearest_action(Action::Bet(40)), Some(Action::Bet(30)));
+    assert_eq!(game.nearest_action(Action::Check), Some(Action::Check));
+    assert_eq!(game.nearest_action(Action::Call), None);
+    let err = game.play_action(Action::Bet(50)).unwrap_err();
+    assert!(err.contains("Bet(60)"));
+
+    // an aggressive action matches an all-in of the same amount
+    if let Some(&Action::AllIn(amount)) = game.available_actions().last() {
+        assert_eq!(game.play_action(Action::Bet(amount)), Ok(()));
+        assert_eq!(
+            game.current_line(),
+            vec![Action::Check, Action::AllIn(amount)]
+        );
+    }
+
+    // the current node is restored on error
+    game.apply_line(&line).unwrap();
+    let invalid_lines = ["X B50", "X X | 2c", "X X X", "X X | Qs | X X X", "F"];
+    for invalid in invalid_lines {
+        let invalid = line_from_str(invalid).unwrap();
+        assert!(game.apply_line(&invalid).is_err());
+        assert_eq!(game.current_line(), line);
+    }
+
+    // parse errors
+    for invalid in ["X B", "X B-10", "X Y", "X Q"] {
+        assert!(line_from_str(invalid).is_err());
+    }
+}
+
+#[test]
+fn translate_action() {
+    let card_config = CardConfig {
+        range: ["TT+,AQs+".parse().unwrap(), "99+,AJs+".parse().unwrap()],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("2c").unwrap(),
+        ..Default::default()
+    };
+
+    let tree_config = TreeConfig {
+        initial_state: BoardState::Turn,
+        starting_pot: 100,
+        effective_stack: 900,
+        turn_bet_sizes: [
+            ("50%, 100%", "").try_into().unwrap(),
+            ("50%", "3x").try_into().unwrap(),
+        ],
+        add_allin_threshold: 10.0,
+        ..Default::default()
+    };
+
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    game.allocate_memory(false);
+    solve(&mut game, 50, 0.0, false);
+
+    // in-tree actions
+    let mapping = ActionMapping::PseudoHarmonic;
+    assert_eq!(
+        game.translate_action(Action::Bet(50), mapping),
+        Ok(vec![(Action::Bet(50), 1.0)])
+    );
+    assert_eq!(
+        game.translate_action(Action::Check, mapping),
+        Ok(vec![(Action::Check, 1.0)])
+    );
+    assert!(game.translate_action(Action::Call, mapping).is_err());
+    assert!(game.translate_action(Action::Bet(0), mapping).is_err());
+
+    // 75% pot between 50% and 100%
+    let expected = (1.0 - 0.75) * (1.0 + 0.5) / ((1.0 - 0.5) * (1.0 + 0.75));
+    let mix = game.translate_action(Action::Bet(75), mapping).unwrap();
+    assert_eq!(mix[0].0, Action::Bet(50));
+    assert_eq!(mix[1].0, Action::Bet(100));
+    assert!((mix[0].1 - expected).abs() < 1e-9);
+    assert!((mix[0].1 + mix[1].1 - 1.0).abs() < 1e-9);
+
+    let mix = game.translate_action(Action::Bet(70), ActionMapping::Nearest);
+    assert_eq!(mix, Ok(vec![(Action::Bet(50), 1.0)]));
+
+    let mix = game
+        .translate_action(Action::Bet(75), ActionMapping::Geometric)
+        .unwrap();
+    assert!((mix[0].1 - (1.0f64 / 0.75).ln() / 2f64.ln()).abs() < 1e-9);
+
+    // a small bet is partially mapped to check
+    let mix = game.translate_action(Action::Bet(10), mapping).unwrap();
+    assert_eq!(mix[0].0, Action::Check);
+    assert!(game.translated_strategy(Action::Bet(10), mapping).is_err());
+
+    // a large bet is mapped to the largest size
+    let mix = game.translate_action(Action::Bet(2000), mapping).unwrap();
+    assert_eq!(mix, vec![(Action::AllIn(900), 1.0)]);
+
+    // the blended strategy of the next decision
+    let (actions, strategy) = game.translated_strategy(Action::Bet(75), mapping).unwrap();
+    let mut blended = vec![0.0; strategy.len()];
+    for (action, prob) in [
+        (Action::Bet(50), expected),
+        (Action::Bet(100), 1.0 - expected),
+    ] {
+        game.play_action(action).unwrap();
+        if prob > 0.5 {
+            assert_eq!(actions, game.available_actions());
+        }
+        let s = game.strategy();
+        blended
+            .iter_mut()
+            .zip(&s)
+            .for_each(|(b, &s)| *b += s * prob as f32);
+        game.back_to_root();
+    }
+    assert!(blended
+        .iter()
+        .zip(&strategy)
+        .all(|(x, y)| (x - y).abs() < 1e-5));
+
+    // raise sizes are measured after calling: raise to 150 is 50% and to 250 is 100%
+    game.play_action(Action::Bet(50)).unwrap();
+    let mix = game.translate_action(Action::Raise(250), mapping).unwrap();
+    assert_eq!(mix[0].0, Action::Raise(150));
+    assert_eq!(mix[1].0, Action::AllIn(900));
+    let (a, b, x) = (0.5, (900.0 - 50.0) / 200.0, 1.0);
+    assert!((mix[0].1 - (b - x) * (1.0 + a) / ((b - a) * (1.0 + x))).abs() < 1e-9);
+}
+
 #[test]
 fn set_bunching_effect() {
     let flop = flop_from_str("Td9d6h").unwrap();
@@ -1141,6 +1750,1546 @@ fn set_bunching_effect_always_win() {
     assert!((ev_ip - 0.0).abs() < 1e-4);
 }
 
+#[test]
+fn depth_limit_all_check_all_range() {
+    let card_config = CardConfig {
+        range: [Range::ones(); 2],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        ..Default::default()
+    };
+
+    let tree_config = TreeConfig {
+        starting_pot: 60,
+        effective_stack: 970,
+        depth_limit: Some(DepthLimit::Street(BoardState::Flop)),
+        ..Default::default()
+    };
+
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    assert_eq!(game.num_nodes, [3, 0, 0]);
+
+    game.allocate_memory(false);
+    finalize(&mut game);
+
+    game.cache_normalized_weights();
+    let weights_oop = game.normalized_weights(0);
+    let weights_ip = game.normalized_weights(1);
+    let ev_oop = compute_average(&game.expected_values(0), weights_oop);
+    let ev_ip = compute_average(&game.expected_values(1), weights_ip);
+    assert!((ev_oop - 30.0).abs() < 1e-4);
+    assert!((ev_ip - 30.0).abs() < 1e-4);
+
+    game.play(0);
+    game.play(0);
+    assert!(game.is_terminal_node());
+    assert!(game.is_leaf_node());
+    game.cache_normalized_weights();
+    let weights_oop = game.normalized_weights(0);
+    let weights_ip = game.normalized_weights(1);
+    let equity_oop = compute_average(&game.equity(0), weights_oop);
+    let ev_oop = compute_average(&game.expected_values(0), weights_oop);
+    let ev_ip = compute_average(&game.expected_values(1), weights_ip);
+    assert!((equity_oop - 0.5).abs() < 1e-5);
+    assert!((ev_oop - 30.0).abs() < 1e-4);
+    assert!((ev_ip - 30.0).abs() < 1e-4);
+
+    game.set_leaf_evaluator(EquityRealization { factor: [0.5, 1.0] });
+    game.allocate_memory(false);
+    finalize(&mut game);
+    game.cache_normalized_weights();
+    let weights_oop = game.normalized_weights(0);
+    let ev_oop = compute_average(&game.expected_values(0), weights_oop);
+    assert!((ev_oop - 15.0).abs() < 1e-4);
+}
+
+#[test]
+fn depth_limit_matches_full_tree() {
+    let card_config = CardConfig {
+        range: [
+            "TT+,AQs+,KQs,AKo".parse().unwrap(),
+            "99+,AJs+,KJs+,QJs,AQo+".parse().unwrap(),
+        ],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("Qc").unwrap(),
+        ..Default::default()
+    };
+
+    // no bets on the river: the river is equivalent to a showdown
+    let tree_config = TreeConfig {
+        initial_state: BoardState::Turn,
+        starting_pot: 60,
+        effective_stack: 970,
+        turn_bet_sizes: [
+            ("50%", "60%").try_into().unwrap(),
+            ("50%", "60%").try_into().unwrap(),
+        ],
+        ..Default::default()
+    };
+
+    let mut results = Vec::new();
+    for depth_limit in [None, Some(DepthLimit::Street(BoardState::Turn))] {
+        let tree_config = TreeConfig {
+            depth_limit,
+            ..tree_config.clone()
+        };
+
+        let action_tree = ActionTree::new(tree_config).unwrap();
+        let mut game = PostFlopGame::with_config(card_config.clone(), action_tree).unwrap();
+        game.allocate_memory(false);
+
+        let exploitability = solve(&mut game, 1000, 0.01, false);
+        assert!(exploitability <= 0.01);
+
+        game.cache_normalized_weights();
+        let weights_oop = game.normalized_weights(0);
+        let weights_ip = game.normalized_weights(1);
+        let ev_oop = compute_average(&game.expected_values(0), weights_oop);
+        let ev_ip = compute_average(&game.expected_values(1), weights_ip);
+        results.push((ev_oop, ev_ip));
+    }
+
+    assert!((results[0].0 - results[1].0).abs() < 0.05);
+    assert!((results[0].1 - results[1].1).abs() < 0.05);
+}
+
+#[test]
+#[cfg(feature = "mmap")]
+fn disk_backend() {
+    let card_config = CardConfig {
+        range: [
+            "TT+,AQs+,KQs,AKo".parse().unwrap(),
+            "99+,AJs+,KJs+,QJs,AQo+".parse().unwrap(),
+        ],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("Qc").unwrap(),
+        ..Default::default()
+    };
+
+    let tree_config = TreeConfig {
+        initial_state: BoardState::Turn,
+        starting_pot: 60,
+        effective_stack: 970,
+        turn_bet_sizes: [
+            ("50%", "60%").try_into().unwrap(),
+            ("50%", "60%").try_into().unwrap(),
+        ],
+        river_bet_sizes: [
+            ("50%", "60%").try_into().unwrap(),
+            ("50%", "60%").try_into().unwrap(),
+        ],
+        ..Default::default()
+    };
+
+    let backends = [
+        StorageBackend::Memory,
+        StorageBackend::Disk(std::env::temp_dir()),
+    ];
+
+    let mut results = Vec::new();
+    for backend in &backends {
+        let action_tree = ActionTree::new(tree_config.clone()).unwrap();
+        let mut game = PostFlopGame::with_config(card_config.clone(), action_tree).unwrap();
+        game.allocate_memory_with_backend(true, backend).unwrap();
+
+        let (resident, on_disk) = game.allocated_memory_usage();
+        match backend {
+            StorageBackend::Memory => {
+                assert_eq!(resident, game.memory_usage().1);
+                assert_eq!(on_disk, 0);
+            }
+            StorageBackend::Disk(_) => {
+                assert_eq!(resident, game.misc_memory_usage);
+                assert_eq!(resident + on_disk, game.memory_usage().1);
+            }
+        }
+
+        results.push(solve(&mut game, 100, 0.0, false));
+    }
+
+    assert_eq!(results[0], results[1]);
+
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    let backend = StorageBackend::Disk("/nonexistent-directory".into());
+    assert!(game.allocate_memory_with_backend(false, &backend).is_err());
+    assert_eq!(game.is_memory_allocated(), None);
+}
+
+#[test]
+fn storage_precision() {
+    let card_config = CardConfig {
+        range: [
+            "TT+,AQs+,KQs,AKo".parse().unwrap(),
+            "99+,AJs+,KJs+,QJs,AQo+".parse().unwrap(),
+        ],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("Qc").unwrap(),
+        ..Default::default()
+    };
+
+    let tree_config = TreeConfig {
+        initial_state: BoardState::Turn,
+        starting_pot: 60,
+        effective_stack: 970,
+        turn_bet_sizes: [
+            ("50%", "60%").try_into().unwrap(),
+            ("50%", "60%").try_into().unwrap(),
+        ],
+        river_bet_sizes: [
+            ("50%", "60%").try_into().unwrap(),
+            ("50%", "60%").try_into().unwrap(),
+        ],
+        ..Default::default()
+    };
+
+    let river_int8 = CompressionConfig {
+        river: StoragePrecision::uniform(Precision::Int8),
+        ..CompressionConfig::uniform(Precision::Int16)
+    };
+
+    let configs = [
+        CompressionConfig::uniform(Precision::Float32),
+        CompressionConfig::uniform(Precision::Int16),
+        river_int8,
+        CompressionConfig::uniform(Precision::Int8),
+    ];
+
+    let mut results = Vec::new();
+    for compression in &configs {
+        let action_tree = ActionTree::new(tree_config.clone()).unwrap();
+        let mut game = PostFlopGame::with_config(card_config.clone(), action_tree).unwrap();
+        game.allocate_memory_with_compression(compression, &StorageBackend::Memory)
+            .unwrap();
+
+        let expected = game.memory_usage_with_compression(compression);
+        assert_eq!(game.allocated_memory_usage(), (expected, 0));
+        assert_eq!(game.compression_config(), Some(compression));
+
+        let exploitability = solve(&mut game, 300, 0.0, false);
+        game.cache_normalized_weights();
+        let weights = game.normalized_weights(0);
+        let ev = compute_average(&game.expected_values(0), weights);
+        results.push((expected, exploitability, ev));
+    }
+
+    // lower precision uses less memory
+    assert!(results.windows(2).all(|w| w[0].0 > w[1].0));
+
+    // exploitability parity within tolerance (pot = 60)
+    let baseline = results[0];
+    assert!(baseline.1 < 0.1);
+    for &(_, exploitability, ev) in &results[1..] {
+        assert!(exploitability < baseline.1 + 0.1);
+        assert!((ev - baseline.2).abs() < 0.1);
+    }
+}
+
+#[test]
+fn export_strategy() {
+    fn compare_recursive(
+        game: &mut PostFlopGame,
+        strategy: &mut SolvedStrategy,
+        history: &[usize],
+    ) {
+        game.apply_history(history);
+        strategy.apply_history(history);
+
+        assert_eq!(game.is_terminal_node(), strategy.is_terminal_node());
+        assert_eq!(game.is_chance_node(), strategy.is_chance_node());
+        assert_eq!(game.current_board(), strategy.current_board());
+
+        if game.is_terminal_node() {
+            return;
+        }
+
+        let mut history = history.to_vec();
+        if game.is_chance_node() {
+            let possible_cards = game.possible_cards();
+            for card in (0..52).filter(|&c| possible_cards & (1 << c) != 0) {
+                history.push(card);
+                compare_recursive(game, strategy, &history);
+                history.pop();
+            }
+        } else {
+            let actions = game.available_actions();
+            assert_eq!(actions, strategy.available_actions());
+            assert_eq!(game.current_player(), strategy.current_player());
+
+            let expected = game.strategy();
+            let actual = strategy.strategy();
+            assert_eq!(expected.len(), actual.len());
+            assert!(expected
+                .iter()
+                .zip(&actual)
+                .all(|(x, y)| (x - y).abs() < 0.01));
+
+            for action in 0..actions.len() {
+                history.push(action);
+                compare_recursive(game, strategy, &history);
+                history.pop();
+            }
+        }
+    }
+
+    // monotone board: the river cards of the other three suits are isomorphic
+    let card_config = CardConfig {
+        range: [
+            "TT+,AQs+,KQs,AKo".parse().unwrap(),
+            "99+,AJs+,KJs+,AQo+".parse().unwrap(),
+        ],
+        flop: flop_from_str("Td9d6d").unwrap(),
+        turn: card_from_str("Qd").unwrap(),
+        ..Default::default()
+    };
+
+    let tree_config = TreeConfig {
+        initial_state: BoardState::Turn,
+        starting_pot: 60,
+        effective_stack: 970,
+        turn_bet_sizes: [
+            ("50%", "").try_into().unwrap(),
+            ("50%", "").try_into().unwrap(),
+        ],
+        river_bet_sizes: [
+            ("50%", "").try_into().unwrap(),
+            ("50%", "").try_into().unwrap(),
+        ],
+        ..Default::default()
+    };
+
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    game.allocate_memory(false);
+    assert!(game.export_strategy().is_err());
+
+    solve(&mut game, 100, 0.0, false);
+    let mut strategy = game.export_strategy().unwrap();
+    assert!(strategy.memory_usage() < game.memory_usage().0 / 4);
+
+    compare_recursive(&mut game, &mut strategy, &[]);
+}
+
+#[test]
+fn best_response() {
+    fn check_recursive(game: &mut PostFlopGame, history: &[usize]) {
+        game.apply_history(history);
+        if game.is_terminal_node() {
+            return;
+        }
+
+        game.cache_normalized_weights();
+        for player in 0..2 {
+            let best_response = game.best_response_values(player);
+            let expected_values = game.expected_values(player);
+            let normalized_weights = game.normalized_weights(player);
+            for i in 0..game.num_private_hands(player) {
+                if normalized_weights[i] > 0.0 {
+                    assert!(best_response[i] >= expected_values[i] - 1e-2);
+                }
+            }
+            assert!(game.best_response_gain(player) >= -1e-3);
+        }
+
+        let mut history = history.to_vec();
+        if game.is_chance_node() {
+            let possible_cards = game.possible_cards();
+            for card in (0..52).filter(|&c| possible_cards & (1 << c) != 0) {
+                history.push(card);
+                check_recursive(game, &history);
+                history.pop();
+            }
+        } else {
+            // the best response is a pure strategy
+            let num_actions = game.available_actions().len();
+            let num_hands = game.num_private_hands(game.current_player());
+            let strategy = game.best_response_strategy();
+            assert!(strategy.iter().all(|&x| x == 0.0 || x == 1.0));
+            for i in 0..num_hands {
+                let sum = (0..num_actions).map(|j| strategy[i + j * num_hands]);
+                assert_eq!(sum.sum::<f32>(), 1.0);
+            }
+
+            for action in 0..num_actions {
+                history.push(action);
+                check_recursive(game, &history);
+                history.pop();
+            }
+        }
+    }
+
+    // monotone board: the river cards of the other three suits are isomorphic
+    let card_config = CardConfig {
+        range: [
+            "TT+,AQs+,KQs,AKo".parse().unwrap(),
+            "99+,AJs+,KJs+,AQo+".parse().unwrap(),
+        ],
+        flop: flop_from_str("Td9d6d").unwrap(),
+        turn: card_from_str("Qd").unwrap(),
+        ..Default::default()
+    };
+
+    let tree_config = TreeConfig {
+        initial_state: BoardState::Turn,
+        starting_pot: 60,
+        effective_stack: 970,
+        turn_bet_sizes: [
+            ("50%", "").try_into().unwrap(),
+            ("50%", "").try_into().unwrap(),
+        ],
+        river_bet_sizes: [
+            ("50%", "").try_into().unwrap(),
+            ("50%", "").try_into().unwrap(),
+        ],
+        ..Default::default()
+    };
+
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    game.allocate_memory(false);
+    solve(&mut game, 20, 0.0, false);
+
+    // the gain at the root node agrees with the exploitability computation
+    let mes_ev = compute_mes_ev(&game);
+    let current_ev = compute_current_ev(&game);
+    game.cache_normalized_weights();
+    for player in 0..2 {
+        let gain = game.best_response_gain(player);
+        assert!(gain > 0.0);
+        assert!((gain - (mes_ev[player] - current_ev[player])).abs() < 1e-3);
+    }
+
+    check_recursive(&mut game, &[]);
+}
+
+#[test]
+fn game_cursor() {
+    type Record = (Vec<usize>, u64, Vec<f32>, Vec<f32>, Vec<f32>);
+
+    fn collect_recursive(game: &mut PostFlopGame, history: &[usize], out: &mut Vec<Record>) {
+        game.apply_history(history);
+        if game.is_terminal_node() {
+            return;
+        }
+
+        game.cache_normalized_weights();
+        let possible_cards = game.possible_cards();
+        let strategy = match game.is_chance_node() {
+            true => Vec::new(),
+            false => game.strategy(),
+        };
+        let equity = game.equity(0);
+        let expected_values = game.expected_values(1);
+        out.push((
+            history.to_vec(),
+            possible_cards,
+            strategy,
+            equity,
+            expected_values,
+        ));
+
+        let mut history = history.to_vec();
+        if game.is_chance_node() {
+            for card in (0..52).filter(|&c| possible_cards & (1 << c) != 0) {
+                history.push(card);
+                collect_recursive(game, &history, out);
+                history.pop();
+            }
+        } else {
+            for action in 0..game.available_actions().len() {
+                history.push(action);
+                collect_recursive(game, &history, out);
+                history.pop();
+            }
+        }
+    }
+
+    // monotone board: the cursor must handle the suit swaps by itself
+    let card_config = CardConfig {
+        range: [
+            "TT+,AQs+,KQs,AKo".parse().unwrap(),
+            "99+,AJs+,KJs+,AQo+".parse().unwrap(),
+        ],
+        flop: flop_from_str("Td9d6d").unwrap(),
+        turn: card_from_str("Qd").unwrap(),
+        ..Default::default()
+    };
+
+    let tree_config = TreeConfig {
+        initial_state: BoardState::Turn,
+        starting_pot: 60,
+        effective_stack: 970,
+        turn_bet_sizes: [
+            ("50%", "").try_into().unwrap(),
+            ("50%", "").try_into().unwrap(),
+        ],
+        river_bet_sizes: [
+            ("50%", "").try_into().unwrap(),
+            ("50%", "").try_into().unwrap(),
+        ],
+        ..Default::default()
+    };
+
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    game.allocate_memory(false);
+    solve(&mut game, 20, 0.0, false);
+
+    let mut records = Vec::new();
+    collect_recursive(&mut game, &[], &mut records);
+    game.apply_history(&[0, 1]);
+
+    let num_threads = 4;
+    std::thread::scope(|s| {
+        for thread in 0..num_threads {
+            let game = &game;
+            let records = &records;
+            s.spawn(move || {
+                let mut cursor = game.cursor();
+                for record in records.iter().skip(thread).step_by(num_threads) {
+                    let (history, possible_cards, strategy, equity, expected_values) = record;
+                    cursor.apply_history(history);
+                    cursor.cache_normalized_weights();
+                    assert_eq!(cursor.possible_cards(), *possible_cards);
+                    if !cursor.is_chance_node() {
+                        assert_eq!(cursor.strategy(), *strategy);
+                    }
+                    assert_eq!(cursor.equity(0), *equity);
+                    assert_eq!(cursor.expected_values(1), *expected_values);
+                }
+            });
+        }
+    });
+
+    // the cursors do not affect the current node of the game
+    assert_eq!(game.history(), &[0, 1]);
+}
+
+#[test]
+fn walk_tree() {
+    // monotone board: the isomorphic river cards are visited only once
+    let card_config = CardConfig {
+        range: [
+            "TT+,AQs+,KQs,AKo".parse().unwrap(),
+            "99+,AJs+,KJs+,AQo+".parse().unwrap(),
+        ],
+        flop: flop_from_str("Td9d6d").unwrap(),
+        turn: card_from_str("Qd").unwrap(),
+        ..Default::default()
+    };
+
+    let tree_config = TreeConfig {
+        initial_state: BoardState::Turn,
+        starting_pot: 60,
+        effective_stack: 970,
+        turn_bet_sizes: [
+            ("50%", "").try_into().unwrap(),
+            ("50%", "").try_into().unwrap(),
+        ],
+        river_bet_sizes: [
+            ("50%", "").try_into().unwrap(),
+            ("50%", "").try_into().unwrap(),
+        ],
+        ..Default::default()
+    };
+
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    game.allocate_memory(false);
+    solve(&mut game, 20, 0.0, false);
+
+    let mut visited = Vec::new();
+    game.walk(&WalkConfig::default(), |node| {
+        let strategy = match node.is_terminal() || node.is_chance() {
+            true => Vec::new(),
+            false => node.strategy(),
+        };
+        visited.push((
+            node.history().to_vec(),
+            node.line().to_vec(),
+            node.board(),
+            node.pot(),
+            node.actions(),
+            strategy,
+            node.equity(0),
+            node.expected_values(0),
+            node.expected_values(1),
+        ));
+        true
+    });
+
+    assert_eq!(visited.len(), game.node_arena.len());
+
+    for (history, line, board, pot, actions, strategy, equity, ev_oop, ev_ip) in &visited {
+        game.apply_history(history);
+        game.cache_normalized_weights();
+        let [oop, ip] = game.total_bet_amount();
+        assert_eq!(game.current_line(), *line);
+        assert_eq!(game.current_board(), *board);
+        assert_eq!(*pot, game.tree_config.starting_pot + oop + ip);
+        assert_eq!(game.available_actions(), *actions);
+        if !game.is_terminal_node() && !game.is_chance_node() {
+            assert_eq!(game.strategy(), *strategy);
+        }
+        assert_eq!(game.equity(0), *equity);
+        assert_eq!(game.expected_values(0), *ev_oop);
+        assert_eq!(game.expected_values(1), *ev_ip);
+    }
+
+    // pruning by depth, street, and the return value of the visitor
+    let config = WalkConfig {
+        max_depth: Some(3),
+        max_street: Some(BoardState::Turn),
+    };
+    let mut count = 0;
+    game.walk(&config, |node| {
+        assert!(node.depth() <= 3);
+        assert_eq!(node.street(), BoardState::Turn);
+        let line = node.line();
+        assert!(!line[..line.len().saturating_sub(1)].contains(&Action::Check));
+        count += 1;
+        line.last() != Some(&Action::Check)
+    });
+    assert!(count > 1);
+    assert!(count < visited.len());
+}
+
+#[test]
+fn node_diagnostics() {
+    fn check_node(game: &PostFlopGame) {
+        let player = game.current_player();
+        let num_actions = game.available_actions().len();
+        let num_hands = game.num_private_hands(player);
+        let weights = game.weights(player);
+
+        let regrets = game.cumulative_regrets();
+        let cum_strategy = game.cumulative_strategy();
+        let instantaneous = game.instantaneous_strategy();
+        let average = game.strategy();
+        assert_eq!(regrets.len(), num_actions * num_hands);
+        assert_eq!(cum_strategy.len(), num_actions * num_hands);
+        assert_eq!(instantaneous.len(), num_actions * num_hands);
+
+        for hand in (0..num_hands).filter(|&h| weights[h] > 0.0) {
+            let column = |v: &[f32]| (0..num_actions).map(|a| v[a * num_hands + hand]).collect();
+            let regrets: Vec<f32> = column(&regrets);
+            let cum_strategy: Vec<f32> = column(&cum_strategy);
+            let instantaneous: Vec<f32> = column(&instantaneous);
+            let average: Vec<f32> = column(&average);
+
+            // regret matching
+            let positive_sum = regrets.iter().map(|&r| r.max(0.0)).sum::<f32>();
+            for action in 0..num_actions {
+                let expected = match positive_sum > 0.0 {
+                    true => regrets[action].max(0.0) / positive_sum,
+                    false => 1.0 / num_actions as f32,
+                };
+                assert!((instantaneous[action] - expected).abs() < 1e-4);
+            }
+
+            // normalized cumulative strategy is the average strategy
+            let mass = cum_strategy.iter().sum::<f32>();
+            assert!(mass > 0.0);
+            for action in 0..num_actions {
+                assert!((cum_strategy[action] / mass - average[action]).abs() < 1e-4);
+            }
+        }
+    }
+
+    // monotone board: the river node is swapped
+    let card_config = CardConfig {
+        range: [
+            "TT+,AQs+,KQs,AKo".parse().unwrap(),
+            "99+,AJs+,KJs+,AQo+".parse().unwrap(),
+        ],
+        flop: flop_from_str("Td9d6d").unwrap(),
+        turn: card_from_str("Qd").unwrap(),
+        ..Default::default()
+    };
+
+    let tree_config = TreeConfig {
+        initial_state: BoardState::Turn,
+        starting_pot: 60,
+        effective_stack: 970,
+        turn_bet_sizes: [
+            ("50%", "").try_into().unwrap(),
+            ("50%", "").try_into().unwrap(),
+        ],
+        river_bet_sizes: [
+            ("50%", "").try_into().unwrap(),
+            ("50%", "").try_into().unwrap(),
+        ],
+        ..Default::default()
+    };
+
+    for compression in [false, true] {
+        let action_tree = ActionTree::new(tree_config.clone()).unwrap();
+        let mut game = PostFlopGame::with_config(card_config.clone(), action_tree).unwrap();
+        game.allocate_memory(compression);
+        for t in 0..10 {
+            solve_step(&game, t);
+        }
+
+        game.back_to_root();
+        check_node(&game);
+        game.apply_history(&[0, 1]);
+        check_node(&game);
+        game.apply_history(&[0, 0, card_from_str("2s").unwrap() as usize]);
+        assert!(game.interpreter.river_swap.is_some());
+        check_node(&game);
+    }
+}
+
+#[test]
+fn memory_planner_estimate() {
+    let card_configs = [
+        ("Td9d6h", NOT_DEALT, NOT_DEALT, BoardState::Flop),
+        (
+            "Td9d6h",
+            card_from_str("Qc").unwrap(),
+            NOT_DEALT,
+            BoardState::Turn,
+        ),
+        (
+            "Qs7s2s",
+            card_from_str("3s").unwrap(),
+            NOT_DEALT,
+            BoardState::Turn,
+        ),
+    ];
+
+    for (flop, turn, river, initial_state) in card_configs {
+        let card_config = CardConfig {
+            range: [
+                "QQ-22,AKs,AQo".parse().unwrap(),
+                "JJ-44,AJs,KQs".parse().unwrap(),
+            ],
+            flop: flop_from_str(flop).unwrap(),
+            turn,
+            river,
+        };
+
+        let bet_sizes = BetSizeOptions::try_from(("50%, 100%", "2.5x")).unwrap();
+        let tree_config = TreeConfig {
+            initial_state,
+            starting_pot: 60,
+            effective_stack: 300,
+            flop_bet_sizes: [bet_sizes.clone(), bet_sizes.clone()],
+            turn_bet_sizes: [bet_sizes.clone(), bet_sizes.clone()],
+            river_bet_sizes: [bet_sizes.clone(), bet_sizes],
+            turn_donk_sizes: Some(DonkSizeOptions::try_from("50%").unwrap()),
+            add_allin_threshold: 1.5,
+            ..Default::default()
+        };
+
+        let planner = MemoryPlanner::new(card_config.clone()).unwrap();
+        let estimate = planner.estimate(&tree_config).unwrap();
+
+        let action_tree = ActionTree::new(tree_config).unwrap();
+        let game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+        let (uncompressed, compressed) = game.memory_usage();
+
+        assert_eq!(estimate.num_nodes, game.num_nodes);
+        assert_eq!(estimate.uncompressed, uncompressed);
+        assert_eq!(estimate.compressed, compressed);
+    }
+}
+
+#[test]
+fn memory_planner_plan() {
+    let card_config = CardConfig {
+        range: [
+            "QQ-22,AKs,AQo".parse().unwrap(),
+            "JJ-44,AJs,KQs".parse().unwrap(),
+        ],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        ..Default::default()
+    };
+
+    let bet_sizes = BetSizeOptions::try_from(("33%, 75%, 150%", "60%, 2.5x")).unwrap();
+    let tree_config = TreeConfig {
+        starting_pot: 60,
+        effective_stack: 300,
+        flop_bet_sizes: [bet_sizes.clone(), bet_sizes.clone()],
+        turn_bet_sizes: [bet_sizes.clone(), bet_sizes.clone()],
+        river_bet_sizes: [bet_sizes.clone(), bet_sizes],
+        add_allin_threshold: 1.5,
+        ..Default::default()
+    };
+
+    let planner = MemoryPlanner::new(card_config).unwrap();
+    let estimate = planner.estimate(&tree_config).unwrap();
+
+    // already fits
+    let plan = planner
+        .plan(&tree_config, estimate.uncompressed, false)
+        .unwrap();
+    assert!(plan.fits());
+    assert!(plan.suggestions.is_empty());
+
+    // needs some modifications
+    let budget = estimate.compressed / 4;
+    let plan = planner.plan(&tree_config, budget, true).unwrap();
+    assert!(plan.fits());
+    assert!(!plan.suggestions.is_empty());
+
+    let mut prev_usage = estimate.compressed;
+    for suggestion in &plan.suggestions {
+        assert!(suggestion.estimate.compressed < prev_usage);
+        prev_usage = suggestion.estimate.compressed;
+    }
+
+    let last = plan.suggestions.last().unwrap();
+    assert!(last.estimate.compressed <= budget);
+    assert_eq!(planner.estimate(&last.tree_config).unwrap(), last.estimate);
+
+    // impossible budget
+    let plan = planner.plan(&tree_config, 0, true).unwrap();
+    assert!(!plan.fits());
+}
+
+#[test]
+fn memory_planner_candidates_reraise() {
+    let card_config = CardConfig {
+        range: [
+            "QQ-22,AKs,AQo".parse().unwrap(),
+            "JJ-44,AJs,KQs".parse().unwrap(),
+        ],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("Qc").unwrap(),
+        river: card_from_str("2s").unwrap(),
+    };
+
+    let bet_sizes = BetSizeOptions::try_from(("50%", "3x | 2x, 2.5x, 3x | 2x, a")).unwrap();
+    let tree_config = TreeConfig {
+        initial_state: BoardState::River,
+        starting_pot: 60,
+        effective_stack: 3000,
+        river_bet_sizes: [bet_sizes.clone(), bet_sizes],
+        ..Default::default()
+    };
+
+    let planner = MemoryPlanner::new(card_config).unwrap();
+    let estimate = planner.estimate(&tree_config).unwrap();
+    let candidates = planner::candidate_configs(&tree_config);
+
+    let find = |description: &str| {
+        let (_, config) = candidates.iter().find(|(d, _)| d == description).unwrap();
+        planner.estimate(config).unwrap()
+    };
+
+    // every size of the re-raise groups can be removed
+    let num_reraise_sizes = candidates
+        .iter()
+        .filter(|(d, _)| d.contains("raise sizes for raise"))
+        .count();
+    assert_eq!(num_reraise_sizes, 2 * 5);
+
+    let removed = find("Remove 2.5x from river OOP raise sizes for raise 2");
+    assert!(removed.compressed < estimate.compressed);
+
+    // the raises are first capped at the depth of the last group, then one by one
+    let capped = find("Cap river IP max_raises at 3");
+    assert!(capped.compressed < estimate.compressed);
+
+    let mut capped_config = tree_config.clone();
+    capped_config.river_bet_sizes[1].max_raises = Some(1);
+    assert!(planner::candidate_configs(&capped_config)
+        .iter()
+        .any(|(d, _)| d == "Cap river IP max_raises at 0"));
+
+    capped_config.river_bet_sizes[1].max_raises = Some(0);
+    assert!(planner::candidate_configs(&capped_config)
+        .iter()
+        .all(|(d, _)| !d.starts_with("Cap river IP")));
+}
+
+#[test]
+fn memory_planner_plan_board_rules() {
+    let card_config = CardConfig {
+        range: [
+            "QQ-22,AKs,AQo".parse().unwrap(),
+            "JJ-44,AJs,KQs".parse().unwrap(),
+        ],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("Qc").unwrap(),
+        ..Default::default()
+    };
+
+    let bet_sizes = BetSizeOptions::try_from(("50%", "")).unwrap();
+    let rule_sizes = BetSizeOptions::try_from(("25%, 50%, 75%, 125%", "2.5x, 4x")).unwrap();
+    let tree_config = TreeConfig {
+        initial_state: BoardState::Turn,
+        starting_pot: 60,
+        effective_stack: 300,
+        river_bet_sizes: [bet_sizes.clone(), bet_sizes],
+        board_bet_sizes: vec![BoardBetSizes {
+            street: BoardState::River,
+            textures: vec![BoardTexture::Unpaired],
+            bet_sizes: [rule_sizes.clone(), rule_sizes],
+            donk_sizes: Some(DonkSizeOptions::try_from("33%, 66%").unwrap()),
+        }],
+        ..Default::default()
+    };
+
+    let planner = MemoryPlanner::new(card_config).unwrap();
+    let estimate = planner.estimate(&tree_config).unwrap();
+
+    // the sizes of the rule make up most of the tree
+    let budget = estimate.compressed / 4;
+    let plan = planner.plan(&tree_config, budget, true).unwrap();
+    assert!(plan.fits());
+    assert!(plan
+        .suggestions
+        .iter()
+        .any(|s| s.description.contains("board rule 1 (river)")));
+
+    let last = plan.suggestions.last().unwrap();
+    assert_eq!(
+        last.tree_config.river_bet_sizes,
+        tree_config.river_bet_sizes
+    );
+}
+
+#[test]
+fn board_bet_sizes() {
+    // clubs and spades are isomorphic on the river
+    let card_config = CardConfig {
+        range: [
+            "TT+,AQs+,KQs,AKo".parse().unwrap(),
+            "99+,AJs+,KJs+,AQo+".parse().unwrap(),
+        ],
+        flop: flop_from_str("Td9d6d").unwrap(),
+        turn: card_from_str("Qh").unwrap(),
+        ..Default::default()
+    };
+
+    let river_rule = |texture, sizes| BoardBetSizes {
+        street: BoardState::River,
+        textures: vec![texture],
+        bet_sizes: [
+            (sizes, "").try_into().unwrap(),
+            (sizes, "").try_into().unwrap(),
+        ],
+        donk_sizes: None,
+    };
+
+    let tree_config = TreeConfig {
+        initial_state: BoardState::Turn,
+        starting_pot: 60,
+        effective_stack: 300,
+        turn_bet_sizes: [
+            ("50%", "").try_into().unwrap(),
+            ("50%", "").try_into().unwrap(),
+        ],
+        river_bet_sizes: [
+            ("75%", "").try_into().unwrap(),
+            ("75%", "").try_into().unwrap(),
+        ],
+        board_bet_sizes: vec![
+            river_rule(BoardTexture::Paired, "33%"),
+            river_rule(BoardTexture::AceHigh, "150%"),
+        ],
+        ..Default::default()
+    };
+
+    // rules must be after the initial state
+    let mut invalid_config = tree_config.clone();
+    invalid_config.board_bet_sizes[0].street = BoardState::Turn;
+    assert!(ActionTree::new(invalid_config).is_err());
+
+    // the planner counts the card-dependent subtrees
+    let planner = MemoryPlanner::new(card_config.clone()).unwrap();
+    let estimate = planner.estimate(&tree_config).unwrap();
+    let action_tree = ActionTree::new(tree_config.clone()).unwrap();
+    let game = PostFlopGame::with_config(card_config.clone(), action_tree).unwrap();
+    let (uncompressed, compressed) = game.memory_usage();
+    assert_eq!(estimate.num_nodes, game.num_nodes);
+    assert_eq!(estimate.uncompressed, uncompressed);
+    assert_eq!(estimate.compressed, compressed);
+
+    // lines are added to the subtrees in which they are valid
+    let mut action_tree = ActionTree::new(tree_config).unwrap();
+    let raise_line = [
+        Action::Check,
+        Action::Check,
+        Action::Bet(20),
+        Action::Raise(60),
+    ];
+    assert!(action_tree.add_line(&raise_line).is_ok());
+    let invalid_line = [Action::Check, Action::Check, Action::Bet(99), Action::Call];
+    assert!(action_tree.add_line(&invalid_line).is_err());
+    assert!(action_tree.invalid_terminals().is_empty());
+
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    game.allocate_memory(false);
+    solve(&mut game, 10, 0.0, false);
+
+    let river_actions = |game: &mut PostFlopGame, river: &str| {
+        game.apply_history(&[0, 0, card_from_str(river).unwrap() as usize]);
+        game.available_actions()
+    };
+
+    // the first matching rule is used, and isomorphic cards select the same rule
+    for river in ["9c", "9s", "Ts"] {
+        let actions = river_actions(&mut game, river);
+        assert_eq!(actions, vec![Action::Check, Action::Bet(20)]);
+        game.play(1);
+        assert!(game.available_actions().contains(&Action::Raise(60)));
+    }
+    for river in ["Ac", "As"] {
+        let actions = river_actions(&mut game, river);
+        assert_eq!(actions, vec![Action::Check, Action::Bet(90)]);
+    }
+    for river in ["2c", "2s"] {
+        let actions = river_actions(&mut game, river);
+        assert_eq!(actions, vec![Action::Check, Action::Bet(45)]);
+    }
+
+    let ev = {
+        river_actions(&mut game, "2s");
+        game.cache_normalized_weights();
+        game.expected_values(0)
+    };
+    assert!(ev.iter().all(|v| v.is_finite()));
+
+    // turn rules on a flop tree
+    let card_config = CardConfig {
+        range: ["QQ-22,AKs".parse().unwrap(), "JJ-44,AJs".parse().unwrap()],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        ..Default::default()
+    };
+
+    let tree_config = TreeConfig {
+        starting_pot: 60,
+        effective_stack: 300,
+        flop_bet_sizes: [
+            ("50%", "").try_into().unwrap(),
+            ("50%", "").try_into().unwrap(),
+        ],
+        board_bet_sizes: vec![
+            BoardBetSizes {
+                street: BoardState::Turn,
+                textures: vec![BoardTexture::Monotone],
+                bet_sizes: [("150%", "").try_into().unwrap(), Default::default()],
+                donk_sizes: None,
+            },
+            river_rule(BoardTexture::Paired, "33%"),
+        ],
+        ..Default::default()
+    };
+
+    let planner = MemoryPlanner::new(card_config.clone()).unwrap();
+    let estimate = planner.estimate(&tree_config).unwrap();
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    let (uncompressed, _) = game.memory_usage();
+    assert_eq!(estimate.num_nodes, game.num_nodes);
+    assert_eq!(estimate.uncompressed, uncompressed);
+
+    game.allocate_memory(false);
+    game.apply_history(&[0, 0, card_from_str("2d").unwrap() as usize]);
+    assert_eq!(
+        game.available_actions(),
+        vec![Action::Check, Action::Bet(90)]
+    );
+    game.apply_history(&[0, 0, card_from_str("2c").unwrap() as usize]);
+    assert_eq!(game.available_actions(), vec![Action::Check]);
+}
+
+#[test]
+fn raise_depth_sizes() {
+    let tree_config = TreeConfig {
+        initial_state: BoardState::River,
+        starting_pot: 100,
+        effective_stack: 1000,
+        river_bet_sizes: [
+            ("50%", "3x | 2x, 2r").try_into().unwrap(),
+            ("50%", "3x | 2x, 2r").try_into().unwrap(),
+        ],
+        ..Default::default()
+    };
+
+    let mut action_tree = ActionTree::new(tree_config).unwrap();
+    action_tree.play(Action::Bet(50)).unwrap();
+    assert_eq!(
+        action_tree.available_actions(),
+        &[Action::Fold, Action::Call, Action::Raise(150)]
+    );
+
+    // the re-raise uses the second group
+    action_tree.play(Action::Raise(150)).unwrap();
+    assert_eq!(
+        action_tree.available_actions(),
+        &[Action::Fold, Action::Call, Action::Raise(300)]
+    );
+
+    // the third raise is not allowed
+    action_tree.play(Action::Raise(300)).unwrap();
+    assert_eq!(
+        action_tree.available_actions(),
+        &[Action::Fold, Action::Call]
+    );
+
+    // the same limit applies after a check-raise
+    action_tree.back_to_root();
+    action_tree.play(Action::Check).unwrap();
+    action_tree.play(Action::Bet(50)).unwrap();
+    action_tree.play(Action::Raise(150)).unwrap();
+    action_tree.play(Action::Raise(300)).unwrap();
+    assert_eq!(
+        action_tree.available_actions(),
+        &[Action::Fold, Action::Call]
+    );
+}
+
+#[test]
+fn raise_rules() {
+    let tree_config = TreeConfig {
+        initial_state: BoardState::River,
+        starting_pot: 100,
+        effective_stack: 250,
+        river_bet_sizes: [
+            ("50%", "2x").try_into().unwrap(),
+            ("50%", "2x").try_into().unwrap(),
+        ],
+        ..Default::default()
+    };
+
+    // by default, R200 (min: 150) is followed by an incomplete all-in raise (min: 350)
+    let mut action_tree = ActionTree::new(tree_config.clone()).unwrap();
+    action_tree.apply_history(&[Action::Bet(50)]).unwrap();
+    assert_eq!(
+        action_tree.available_actions(),
+        &[Action::Fold, Action::Call, Action::Raise(100)]
+    );
+    action_tree.play(Action::Raise(100)).unwrap();
+    action_tree.play(Action::Raise(200)).unwrap();
+    assert_eq!(
+        action_tree.available_actions(),
+        &[Action::Fold, Action::Call, Action::AllIn(250)]
+    );
+
+    // incomplete all-in raises are not allowed
+    let mut action_tree = ActionTree::new(TreeConfig {
+        incomplete_raise: IncompleteRaise::Disallow,
+        ..tree_config.clone()
+    })
+    .unwrap();
+    action_tree
+        .apply_history(&[Action::Bet(50), Action::Raise(100), Action::Raise(200)])
+        .unwrap();
+    assert_eq!(
+        action_tree.available_actions(),
+        &[Action::Fold, Action::Call]
+    );
+
+    // the bets and raises are capped at two per street
+    let mut action_tree = ActionTree::new(TreeConfig {
+        max_bets_per_street: Some(2),
+        ..tree_config.clone()
+    })
+    .unwrap();
+    action_tree.apply_history(&[Action::Bet(50)]).unwrap();
+    assert_eq!(action_tree.available_actions().len(), 3);
+    action_tree.play(Action::Raise(100)).unwrap();
+    assert_eq!(
+        action_tree.available_actions(),
+        &[Action::Fold, Action::Call]
+    );
+
+    // smaller raises are allowed by the custom minimum raise rule
+    let tree_config = TreeConfig {
+        river_bet_sizes: [
+            ("50%", "1.5x").try_into().unwrap(),
+            ("50%", "1.5x").try_into().unwrap(),
+        ],
+        ..tree_config
+    };
+
+    let mut action_tree = ActionTree::new(tree_config.clone()).unwrap();
+    action_tree.play(Action::Bet(50)).unwrap();
+    assert!(action_tree
+        .available_actions()
+        .contains(&Action::Raise(100)));
+    assert!(action_tree
+        .add_line(&[Action::Bet(50), Action::Raise(80)])
+        .is_err());
+
+    let mut action_tree = ActionTree::new(TreeConfig {
+        min_raise: MinRaise::Ratio(0.5),
+        ..tree_config.clone()
+    })
+    .unwrap();
+    action_tree.play(Action::Bet(50)).unwrap();
+    assert!(action_tree.available_actions().contains(&Action::Raise(75)));
+    assert!(action_tree
+        .add_line(&[Action::Bet(50), Action::Raise(80)])
+        .is_ok());
+    assert!(action_tree
+        .add_line(&[Action::Bet(50), Action::Raise(70)])
+        .is_err());
+
+    for config in [
+        TreeConfig {
+            max_bets_per_street: Some(0),
+            ..tree_config.clone()
+        },
+        TreeConfig {
+            min_raise: MinRaise::Ratio(0.0),
+            ..tree_config.clone()
+        },
+        TreeConfig {
+            min_raise: MinRaise::Ratio(f64::NAN),
+            ..tree_config
+        },
+    ] {
+        assert!(ActionTree::new(config).is_err());
+    }
+}
+
+#[test]
+fn big_blind_and_target_spr_sizes() {
+    let tree_config = TreeConfig {
+        initial_state: BoardState::River,
+        starting_pot: 100,
+        effective_stack: 300,
+        big_blind: 10,
+        river_bet_sizes: [
+            ("2.5bb, 1spr", "3bb, 0spr").try_into().unwrap(),
+            ("5spr", "").try_into().unwrap(),
+        ],
+        ..Default::default()
+    };
+
+    // 1spr: (300 - 67) / (100 + 2 * 67) = 1
+    let mut action_tree = ActionTree::new(tree_config.clone()).unwrap();
+    assert_eq!(
+        action_tree.available_actions(),
+        &[Action::Check, Action::Bet(25), Action::Bet(67)]
+    );
+
+    // 5spr is not reachable from SPR 3, so the minimum size is used
+    action_tree.play(Action::Check).unwrap();
+    assert_eq!(
+        action_tree.available_actions(),
+        &[Action::Check, Action::Bet(1)]
+    );
+
+    // 3bb is added to the previous bet, and 0spr is all-in
+    let mut action_tree = ActionTree::new(TreeConfig {
+        river_bet_sizes: [
+            ("2.5bb", "").try_into().unwrap(),
+            ("", "3bb, 0spr").try_into().unwrap(),
+        ],
+        ..tree_config.clone()
+    })
+    .unwrap();
+    action_tree.play(Action::Bet(25)).unwrap();
+    assert_eq!(
+        action_tree.available_actions(),
+        &[
+            Action::Fold,
+            Action::Call,
+            Action::Raise(55),
+            Action::AllIn(300)
+        ]
+    );
+
+    // big blind must be set to use big blind sizes
+    let tree_config = TreeConfig {
+        big_blind: 0,
+        ..tree_config
+    };
+    assert!(ActionTree::new(tree_config.clone()).is_err());
+    assert!(ActionTree::new(TreeConfig {
+        river_bet_sizes: [
+            ("1spr", "").try_into().unwrap(),
+            ("1spr", "").try_into().unwrap()
+        ],
+        ..tree_config
+    })
+    .is_ok());
+}
+
+#[test]
+fn diff_action_tree() {
+    let card_config = CardConfig {
+        range: ["TT+,AKs".parse().unwrap(), "QQ-88,AQs".parse().unwrap()],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("Qc").unwrap(),
+        river: card_from_str("2s").unwrap(),
+    };
+
+    let tree_config = TreeConfig {
+        initial_state: BoardState::River,
+        starting_pot: 100,
+        effective_stack: 300,
+        river_bet_sizes: [
+            ("50%", "").try_into().unwrap(),
+            ("50%", "").try_into().unwrap(),
+        ],
+        ..Default::default()
+    };
+
+    let old_tree = ActionTree::new(tree_config.clone()).unwrap();
+    let mut new_tree = ActionTree::new(tree_config).unwrap();
+    new_tree
+        .add_line(&[Action::Check, Action::Bet(100)])
+        .unwrap();
+
+    let mut old_game = PostFlopGame::with_config(card_config.clone(), old_tree).unwrap();
+    let new_game = PostFlopGame::with_config(card_config, new_tree).unwrap();
+    old_game.allocate_memory(false);
+
+    let diff = old_game.diff_action_tree(&new_game);
+    assert_eq!(
+        diff.added_lines,
+        vec![vec![Action::Check, Action::Bet(100)]]
+    );
+    assert!(diff.removed_lines.is_empty());
+    assert_eq!(diff.num_action_nodes_delta(), [0, 0, 3]);
+    assert!(new_game.diff_action_tree(&new_game).is_empty());
+}
+
+#[test]
+fn prune_low_frequency_lines() {
+    let card_config = CardConfig {
+        range: ["TT+,AKs,87s".parse().unwrap(), "QQ-88,AQs".parse().unwrap()],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("Qc").unwrap(),
+        ..Default::default()
+    };
+
+    let sizes = BetSizeOptions::try_from(("10%, 50%, 300%", "")).unwrap();
+    let tree_config = TreeConfig {
+        initial_state: BoardState::Turn,
+        starting_pot: 100,
+        effective_stack: 300,
+        turn_bet_sizes: [sizes.clone(), sizes.clone()],
+        river_bet_sizes: [sizes.clone(), sizes],
+        ..Default::default()
+    };
+
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    game.allocate_memory(false);
+
+    for t in 0..50 {
+        solve_step(&game, t);
+    }
+
+    let exploitability = compute_exploitability(&game);
+    let lines = game.low_frequency_lines(0.05);
+    assert!(!lines.is_empty());
+    assert!(lines.iter().any(|l| l.len() == 1));
+    assert!(lines
+        .iter()
+        .any(|l| l.iter().any(|a| matches!(a, Action::Chance(_)))));
+    assert!(lines.iter().all(|l| matches!(
+        l.last(),
+        Some(Action::Bet(_) | Action::Raise(_) | Action::AllIn(_))
+    )));
+    assert!(lines
+        .iter()
+        .all(|l| !lines.iter().any(|m| m != l && l.starts_with(m))));
+    assert!(game.low_frequency_lines(0.0).is_empty());
+
+    // the pruned game is warm-started
+    let mut pruned = game.pruned_game(&lines).unwrap();
+    assert!(pruned.memory_usage().0 < game.memory_usage().0);
+    assert!(compute_exploitability(&pruned) < exploitability * 2.0);
+    for line in &lines {
+        assert!(pruned.apply_line(line).is_err());
+        pruned.back_to_root();
+    }
+
+    let report = game.prune_and_resolve(0.05, 50, 50, 0.0).unwrap();
+    assert_eq!(report.removed_lines, lines);
+    assert_eq!(report.exploitability_before, exploitability);
+    assert!(report.exploitability_after < exploitability * 0.5);
+    assert!(report.exploitability_change() < 0.0);
+    assert!(report.game.is_solved());
+
+    game.lock_current_strategy(&vec![0.0; game.strategy().len()]);
+    assert!(game.pruned_game(&lines).is_err());
+    game.unlock_current_strategy();
+
+    // the regrets are not available after finalization
+    finalize(&mut game);
+    assert!(game.pruned_game(&lines).is_err());
+}
+
+#[test]
+fn prune_after_remove_lines() {
+    let card_config = CardConfig {
+        range: ["TT+,AKs,87s".parse().unwrap(), "QQ-88,AQs".parse().unwrap()],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("Qc").unwrap(),
+        ..Default::default()
+    };
+
+    let sizes = BetSizeOptions::try_from(("10%, 50%", "")).unwrap();
+    let tree_config = TreeConfig {
+        initial_state: BoardState::Turn,
+        starting_pot: 100,
+        effective_stack: 300,
+        turn_bet_sizes: [sizes.clone(), sizes.clone()],
+        river_bet_sizes: [sizes.clone(), sizes],
+        ..Default::default()
+    };
+
+    let river = Action::Chance(card_from_str("2s").unwrap());
+    let removed = vec![vec![Action::Bet(50), Action::Call, river, Action::Bet(100)]];
+
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    game.remove_lines(&removed).unwrap();
+    game.allocate_memory(false);
+
+    for t in 0..20 {
+        solve_step(&game, t);
+    }
+
+    // the game-level removal is replayed in the pruned game
+    let lines = vec![vec![Action::Bet(10)]];
+    let mut pruned = game.pruned_game(&lines).unwrap();
+    assert_eq!(pruned.removed_lines(), lines);
+    assert_eq!(pruned.removed_game_lines(), removed);
+    assert!(pruned.apply_line(&removed[0]).is_err());
+    pruned.back_to_root();
+    assert!(pruned.apply_line(&removed[0][..3]).is_ok());
+
+    // the removal after the pruned line is not replayed
+    let lines = vec![vec![Action::Bet(50)]];
+    let pruned = game.pruned_game(&lines).unwrap();
+    assert!(pruned.removed_game_lines().is_empty());
+}
+
+#[test]
+fn warm_start_from_similar_tree() {
+    let card_config = CardConfig {
+        range: ["TT+,AKs,87s".parse().unwrap(), "QQ-88,AQs".parse().unwrap()],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("Qc").unwrap(),
+        ..Default::default()
+    };
+
+    let sizes = BetSizeOptions::try_from(("50%", "")).unwrap();
+    let tree_config = TreeConfig {
+        initial_state: BoardState::Turn,
+        starting_pot: 100,
+        effective_stack: 300,
+        turn_bet_sizes: [sizes.clone(), sizes.clone()],
+        river_bet_sizes: [sizes.clone(), sizes],
+        ..Default::default()
+    };
+
+    let action_tree = ActionTree::new(tree_config.clone()).unwrap();
+    let mut source = PostFlopGame::with_config(card_config.clone(), action_tree).unwrap();
+    source.allocate_memory(false);
+    for t in 0..50 {
+        solve_step(&source, t);
+    }
+
+    // one more size and a slightly different range
+    let new_card_config = CardConfig {
+        range: ["TT+,AKs,87s,76s".parse().unwrap(), card_config.range[1]],
+        ..card_config.clone()
+    };
+    let sizes = BetSizeOptions::try_from(("50%, 75%", "")).unwrap();
+    let new_tree_config = TreeConfig {
+        turn_bet_sizes: [sizes.clone(), sizes.clone()],
+        river_bet_sizes: [sizes.clone(), sizes],
+        ..tree_config
+    };
+
+    let new_game = || {
+        let action_tree = ActionTree::new(new_tree_config.clone()).unwrap();
+        let mut game = PostFlopGame::with_config(new_card_config.clone(), action_tree).unwrap();
+        game.allocate_memory(true);
+        game
+    };
+
+    let mut cold = new_game();
+    let mut warm = new_game();
+    let cold_exploitability = compute_exploitability(&cold);
+    assert!(warm.warm_start_from(&source).unwrap() > 0);
+    assert!(compute_exploitability(&warm) < cold_exploitability);
+
+    let cold_exploitability = solve(&mut cold, 50, 0.0, false);
+    let warm_exploitability = solve_from(&mut warm, 50, 50, 0.0, false);
+    assert!(warm_exploitability < cold_exploitability);
+
+    // the boards must be the same
+    let mut other = PostFlopGame::with_config(
+        CardConfig {
+            turn: card_from_str("Qs").unwrap(),
+            ..card_config
+        },
+        ActionTree::new(new_tree_config.clone()).unwrap(),
+    )
+    .unwrap();
+    other.allocate_memory(false);
+    assert!(other.warm_start_from(&source).is_err());
+
+    // the regrets are not available after finalization
+    finalize(&mut source);
+    assert!(new_game().warm_start_from(&source).is_err());
+}
+
+#[test]
+fn warm_start_splits_shared_actions() {
+    let card_config = CardConfig {
+        range: ["TT+,AKs,87s".parse().unwrap(), "QQ-88,AQs".parse().unwrap()],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("Qc").unwrap(),
+        river: card_from_str("2s").unwrap(),
+    };
+
+    let tree_config = TreeConfig {
+        initial_state: BoardState::River,
+        starting_pot: 100,
+        effective_stack: 300,
+        river_bet_sizes: [("50%", "").try_into().unwrap(), Default::default()],
+        ..Default::default()
+    };
+
+    let action_tree = ActionTree::new(tree_config.clone()).unwrap();
+    let mut source = PostFlopGame::with_config(card_config.clone(), action_tree).unwrap();
+    source.allocate_memory(false);
+    for t in 0..20 {
+        solve_step(&source, t);
+    }
+
+    // both B50 and B75 correspond to B50 of the source
+    let action_tree = ActionTree::new(TreeConfig {
+        river_bet_sizes: [("50%, 75%", "").try_into().unwrap(), Default::default()],
+        ..tree_config
+    })
+    .unwrap();
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    game.allocate_memory(false);
+    game.warm_start_from(&source).unwrap();
+
+    assert_eq!(source.available_actions(), [Action::Check, Action::Bet(50)]);
+    assert_eq!(
+        game.available_actions(),
+        [Action::Check, Action::Bet(50), Action::Bet(75)]
+    );
+
+    // the average strategy of the check is preserved, and the bets share the source bet
+    let num_hands = game.private_cards(0).len();
+    let (src_strategy, strategy) = (source.strategy(), game.strategy());
+    for hand in 0..num_hands {
+        let src_check = src_strategy[hand];
+        let src_bet = src_strategy[num_hands + hand];
+        assert!((strategy[hand] - src_check).abs() < 1e-6);
+        assert!((strategy[num_hands + hand] - src_bet / 2.0).abs() < 1e-6);
+        assert!((strategy[2 * num_hands + hand] - src_bet / 2.0).abs() < 1e-6);
+    }
+}
+
 #[test]
 #[ignore]
 fn solve_pio_preset_normal() {
@@ -0,0 +1,188 @@
+use super::*;
+use crate::interface::*;
+
+/// An enum representing an action-translation method, used by
+/// [`PostFlopGame::translate_action`].
+///
+/// Bet sizes are measured as fractions of the pot after calling: a bet (or a raise) that adds `y`
+/// chips in addition to the call into the pot of `p` chips has the size of `x = y / p`. An off-tree
+/// size `x` is mapped between the adjacent tree sizes `A < x < B`, where the check/call action is
+/// regarded as the size of zero. `f(x)` denotes the probability of mapping to `A`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ActionMapping {
+    /// Pseudo-harmonic mapping (Ganzfried and Sandholm, 2013):
+    /// `f(x) = (B - x)(1 + A) / ((B - A)(1 + x))`.
+    #[default]
+    PseudoHarmonic,
+
+    /// Maps to the nearest size (the smaller one in case of a tie).
+    Nearest,
+
+    /// Geometric interpolation: `f(x) = ln(B / x) / ln(B / A)`. If `A` is zero (i.e., check/call),
+    /// linear interpolation `f(x) = (B - x) / B` is used instead.
+    Geometric,
+}
+
+impl PostFlopGame {
+    /// Translates the given action of the current player into a probability mix of the available
+    /// actions.
+    ///
+    /// If `action` is available (including a bet or raise of the same amount as an all-in action),
+    /// returns it with probability one. Otherwise, `action` must be a bet, raise, or all-in action,
+    /// which is mapped by `mapping` between the adjacent sizes of the tree (see [`ActionMapping`]).
+    /// A size larger than any size of the tree is mapped to the largest one. The returned list
+    /// contains the actions with positive probabilities in ascending order of the size.
+    ///
+    /// Returns `Err` if `action` cannot be translated. Panics if the current node is a terminal
+    /// node or a chance node.
+    pub fn translate_action(
+        &self,
+        action: Action,
+        mapping: ActionMapping,
+    ) -> Result<Vec<(Action, f64)>, String> {
+        if self.is_terminal_node() {
+            panic!("Terminal node is not allowed");
+        }
+
+        if self.is_chance_node() {
+            panic!("Chance node is not allowed");
+        }
+
+        let actions = self.available_actions();
+        let amount = match action {
+            Action::Bet(a) | Action::Raise(a) | Action::AllIn(a) => a,
+            _ if actions.contains(&action) => return Ok(vec![(action, 1.0)]),
+            _ => return Err(format!("{action:?} is not available")),
+        };
+
+        // the check/call action is regarded as the size of zero
+        let mut anchors = actions
+            .iter()
+            .filter_map(|&a| match a {
+                Action::Check | Action::Call => Some((a, 0.0)),
+                Action::Bet(b) | Action::Raise(b) | Action::AllIn(b) => {
+                    Some((a, self.pot_fraction(b)))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        anchors.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+        let x = self.pot_fraction(amount);
+        if x <= 0.0 {
+            return Err(format!("Invalid amount: {action:?}"));
+        }
+
+        let Some(upper) = anchors.iter().position(|&(_, size)| size >= x) else {
+            return Ok(vec![(anchors.last().unwrap().0, 1.0)]);
+        };
+
+        let (b_action, b) = anchors[upper];
+        if b == x || upper == 0 {
+            return Ok(vec![(b_action, 1.0)]);
+        }
+
+        let (a_action, a) = anchors[upper - 1];
+        let p = match mapping {
+            ActionMapping::PseudoHarmonic => (b - x) * (1.0 + a) / ((b - a) * (1.0 + x)),
+            ActionMapping::Nearest => (x - a <= b - x) as u8 as f64,
+            ActionMapping::Geometric if a == 0.0 => (b - x) / b,
+            ActionMapping::Geometric => (b / x).ln() / (b / a).ln(),
+        };
+
+        let ret = [(a_action, p), (b_action, 1.0 - p)];
+        Ok(ret.into_iter().filter(|&(_, p)| p > 0.0).collect())
+    }
+
+    /// Returns the strategy of the next decision after the given action of the current player,
+    /// blended over the translated actions.
+    ///
+    /// `action` is translated by [`translate_action`], and the strategies of the resulting nodes
+    /// are averaged with the probabilities of the translation. The return value is a pair of the
+    /// available actions and the strategy, whose layout is the same as the [`strategy`] method.
+    /// The actions are those of the most probable node; the actions of the other nodes are matched
+    /// by their positions.
+    ///
+    /// Returns `Err` if `action` cannot be translated, a resulting node is not a player node, or the
+    /// resulting nodes have different kinds of actions (e.g., when mapped to both check and bet).
+    /// Panics if the memory is not yet allocated or the current node is a terminal node or a chance
+    /// node.
+    ///
+    /// [`translate_action`]: #method.translate_action
+    /// [`strategy`]: #method.strategy
+    pub fn translated_strategy(
+        &self,
+        action: Action,
+        mapping: ActionMapping,
+    ) -> Result<(Vec<Action>, Vec<f32>), String> {
+        if self.state < State::MemoryAllocated {
+            panic!("Memory is not allocated");
+        }
+
+        let mix = self.translate_action(action, mapping)?;
+        let actions = self.available_actions();
+        let node = self.node();
+
+        let mut ret: Option<(Vec<Action>, Vec<f32>)> = None;
+        let mut best_prob = 0.0;
+
+        for &(mapped, prob) in &mix {
+            let index = actions.iter().position(|&a| a == mapped).unwrap();
+            let child = node.play(index);
+            if child.is_terminal() || child.is_chance() {
+                return Err(format!("{mapped:?} does not lead to a player node"));
+            }
+
+            let child_actions = child
+                .children()
+                .iter()
+                .map(|c| c.lock().prev_action)
+                .collect::<Vec<_>>();
+            let strategy = self.strategy_at(&child);
+
+            match &mut ret {
+                None => {
+                    let strategy = strategy.iter().map(|&s| s * prob as f32).collect();
+                    ret = Some((child_actions, strategy));
+                }
+                Some((ret_actions, ret_strategy)) => {
+                    let kind = |&a: &Action| match a {
+                        Action::Bet(_) | Action::Raise(_) | Action::AllIn(_) => None,
+                        _ => Some(a),
+                    };
+                    if !ret_actions
+                        .iter()
+                        .map(kind)
+                        .eq(child_actions.iter().map(kind))
+                    {
+                        return Err("Translated actions lead to different actions".to_string());
+                    }
+                    if prob > best_prob {
+                        *ret_actions = child_actions;
+                    }
+                    ret_strategy
+                        .iter_mut()
+                        .zip(&strategy)
+                        .for_each(|(r, &s)| *r += s * prob as f32);
+                }
+            }
+
+            best_prob = f64::max(best_prob, prob);
+        }
+
+        Ok(ret.unwrap())
+    }
+
+    /// Returns the size of the aggressive action of the current player with the given amount as a
+    /// fraction of the pot after calling.
+    fn pot_fraction(&self, amount: i32) -> f64 {
+        let [oop, ip] = self.total_bet_amount();
+        let to_call = (oop - ip).abs();
+        let pot = self.tree_config.starting_pot + oop + ip + to_call;
+        let prev_amount = match self.node().prev_action {
+            Action::Bet(a) | Action::Raise(a) | Action::AllIn(a) => a,
+            _ => 0,
+        };
+        (amount - prev_amount) as f64 / pot as f64
+    }
+}
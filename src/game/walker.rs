@@ -0,0 +1,351 @@
+use super::*;
+use crate::interface::*;
+use crate::utility::*;
+
+/// Options of [`PostFlopGame::walk`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WalkConfig {
+    /// The maximum depth of the nodes to visit, where the depth is the number of actions
+    /// (including chance actions) from the root node. `None` means no limit.
+    pub max_depth: Option<usize>,
+
+    /// The last street to visit; the nodes after dealing a card of a later street are not visited.
+    /// `None` means no limit.
+    pub max_street: Option<BoardState>,
+}
+
+/// A node visited by [`PostFlopGame::walk`].
+///
+/// The basic information of the node is available in *O*(1) time. The strategy is decoded on
+/// demand, and the weights, the equity, and the expected values are computed on demand by playing
+/// the line from the nearest ancestor whose weights have already been computed, so a visitor that
+/// queries them at every node costs about the same as a single call of [`PostFlopGame::play`] per
+/// node.
+pub struct WalkNode<'a> {
+    game: &'a PostFlopGame,
+    history: Vec<usize>,
+    line: Vec<Action>,
+    node_indices: Vec<usize>,
+    total_bet_amounts: Vec<[i32; 2]>,
+    states: Vec<InterpreterState>,
+}
+
+impl PostFlopGame {
+    /// Visits the nodes of the game tree in depth-first order, starting from the root node.
+    ///
+    /// `visitor` is called for each node, including terminal nodes and chance nodes, and the
+    /// children of the node are visited only if it returns `true`. The children of a chance node
+    /// are the representative cards of the isomorphic chances (i.e., the [`available_actions`] at
+    /// the chance node), so each node of the game tree is visited exactly once. The streets whose
+    /// storage is not kept by the storage mode are not visited.
+    ///
+    /// The current node of the game is not changed.
+    ///
+    /// Panics if the memory is not yet allocated.
+    ///
+    /// # Examples
+    /// ```
+    /// use postflop_solver::*;
+    ///
+    /// let card_config = CardConfig {
+    ///     range: ["TT+,AQs+".parse().unwrap(), "99+,AJs+".parse().unwrap()],
+    ///     flop: flop_from_str("Td9d6h").unwrap(),
+    ///     turn: card_from_str("Qc").unwrap(),
+    ///     river: card_from_str("2s").unwrap(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let tree_config = TreeConfig {
+    ///     initial_state: BoardState::River,
+    ///     starting_pot: 100,
+    ///     effective_stack: 100,
+    ///     river_bet_sizes: [("50%", "").try_into().unwrap(), ("50%", "").try_into().unwrap()],
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let action_tree = ActionTree::new(tree_config).unwrap();
+    /// let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    /// game.allocate_memory(false);
+    /// solve(&mut game, 100, 0.0, false);
+    ///
+    /// let mut lines = Vec::new();
+    /// let config = WalkConfig { max_depth: Some(2), ..Default::default() };
+    /// game.walk(&config, |node| {
+    ///     if !node.is_terminal() {
+    ///         let ev = node.expected_values(node.player());
+    ///         lines.push((line_to_string(node.line()).unwrap(), ev));
+    ///     }
+    ///     true
+    /// });
+    ///
+    /// assert_eq!(lines[0].0, "");
+    /// assert_eq!(lines[1].0, "X");
+    /// ```
+    ///
+    /// [`available_actions`]: #method.available_actions
+    pub fn walk<F: FnMut(&mut WalkNode) -> bool>(&self, config: &WalkConfig, mut visitor: F) {
+        if self.state < State::MemoryAllocated {
+            panic!("Memory is not allocated");
+        }
+
+        let mut node = WalkNode {
+            game: self,
+            history: Vec::new(),
+            line: Vec::new(),
+            node_indices: vec![0],
+            total_bet_amounts: vec![[0, 0]],
+            states: Vec::new(),
+        };
+
+        node.walk_recursive(config, &mut visitor);
+    }
+}
+
+impl<'a> WalkNode<'a> {
+    /// Returns the reference to the underlying game.
+    #[inline]
+    pub fn game(&self) -> &'a PostFlopGame {
+        self.game
+    }
+
+    /// Returns the history of the node, which can be passed to [`PostFlopGame::apply_history`].
+    #[inline]
+    pub fn history(&self) -> &[usize] {
+        &self.history
+    }
+
+    /// Returns the line from the root node to the node (see [`PostFlopGame::current_line`]).
+    #[inline]
+    pub fn line(&self) -> &[Action] {
+        &self.line
+    }
+
+    /// Returns the depth of the node, i.e., the number of actions from the root node.
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Returns whether the node is a terminal node (see [`PostFlopGame::is_terminal_node`]).
+    #[inline]
+    pub fn is_terminal(&self) -> bool {
+        let node = self.node();
+        node.is_terminal() || node.amount == self.game.tree_config.effective_stack
+    }
+
+    /// Returns whether the node is a chance node (see [`PostFlopGame::is_chance_node`]).
+    #[inline]
+    pub fn is_chance(&self) -> bool {
+        self.node().is_chance() && !self.is_terminal()
+    }
+
+    /// Returns the player to act at the node (0 = OOP, 1 = IP).
+    ///
+    /// If the node is a terminal node or a chance node, returns an undefined value.
+    #[inline]
+    pub fn player(&self) -> usize {
+        self.node().player()
+    }
+
+    /// Returns the board of the node in the same format as [`PostFlopGame::current_board`].
+    #[inline]
+    pub fn board(&self) -> Vec<Card> {
+        let node = self.node();
+        let mut ret = self.game.card_config.flop.to_vec();
+        if node.turn != NOT_DEALT {
+            ret.push(node.turn);
+        }
+        if node.river != NOT_DEALT {
+            ret.push(node.river);
+        }
+        ret
+    }
+
+    /// Returns the street of the node.
+    #[inline]
+    pub fn street(&self) -> BoardState {
+        let node = self.node();
+        if node.river != NOT_DEALT {
+            BoardState::River
+        } else if node.turn != NOT_DEALT {
+            BoardState::Turn
+        } else {
+            BoardState::Flop
+        }
+    }
+
+    /// Returns the total bet amount of each player (OOP, IP) at the node.
+    #[inline]
+    pub fn total_bet_amount(&self) -> [i32; 2] {
+        *self.total_bet_amounts.last().unwrap()
+    }
+
+    /// Returns the pot size at the node, including the bets of the current street.
+    #[inline]
+    pub fn pot(&self) -> i32 {
+        let [oop, ip] = self.total_bet_amount();
+        self.game.tree_config.starting_pot + oop + ip
+    }
+
+    /// Returns the available actions at the node (see [`PostFlopGame::available_actions`]).
+    #[inline]
+    pub fn actions(&self) -> Vec<Action> {
+        if self.is_terminal() {
+            Vec::new()
+        } else {
+            self.node()
+                .children()
+                .iter()
+                .map(|c| c.lock().prev_action)
+                .collect()
+        }
+    }
+
+    /// Returns the strategy of the node in the same layout as [`PostFlopGame::strategy`].
+    ///
+    /// Panics if the node is a terminal node or a chance node.
+    pub fn strategy(&self) -> Vec<f32> {
+        if self.is_terminal() {
+            panic!("Terminal node is not allowed");
+        }
+
+        if self.is_chance() {
+            panic!("Chance node is not allowed");
+        }
+
+        // the cards of the line are representative, so no swap is needed
+        let node = self.node();
+        let mut ret = node_strategy(self.game, &node);
+        apply_locking_strategy(&mut ret, self.game.locking_strategy(&node));
+        ret
+    }
+
+    /// Returns the weights of each private hand of the given player (see
+    /// [`PostFlopGame::weights`]).
+    #[inline]
+    pub fn weights(&mut self, player: usize) -> &[f32] {
+        let game = self.game;
+        self.state().weights(game, player)
+    }
+
+    /// Returns the normalized weights of each private hand of the given player (see
+    /// [`PostFlopGame::normalized_weights`]).
+    #[inline]
+    pub fn normalized_weights(&mut self, player: usize) -> &[f32] {
+        let game = self.game;
+        self.state().normalized_weights(game, player)
+    }
+
+    /// Returns the equity of each private hand of the given player (see
+    /// [`PostFlopGame::equity`]).
+    #[inline]
+    pub fn equity(&mut self, player: usize) -> Vec<f32> {
+        let game = self.game;
+        self.state().equity(game, player)
+    }
+
+    /// Returns the expected values of each private hand of the given player (see
+    /// [`PostFlopGame::expected_values`]).
+    ///
+    /// Panics if the game is not solved.
+    #[inline]
+    pub fn expected_values(&mut self, player: usize) -> Vec<f32> {
+        let game = self.game;
+        self.state().expected_values(game, player)
+    }
+
+    /// Returns the expected values of each action of each private hand of the given player (see
+    /// [`PostFlopGame::expected_values_detail`]).
+    ///
+    /// Panics if the game is not solved.
+    #[inline]
+    pub fn expected_values_detail(&mut self, player: usize) -> Vec<f32> {
+        let game = self.game;
+        self.state().expected_values_detail(game, player)
+    }
+
+    /// Returns the reference to the node.
+    #[inline]
+    fn node(&self) -> MutexGuardLike<'a, PostFlopNode> {
+        self.game.node_arena[*self.node_indices.last().unwrap()].lock()
+    }
+
+    /// Returns the interpreter state of the node with the normalized weights cached.
+    fn state(&mut self) -> &InterpreterState {
+        let game = self.game;
+
+        if self.states.is_empty() {
+            let mut state = InterpreterState::new(game);
+            state.back_to_root(game);
+            self.states.push(state);
+        }
+
+        while self.states.len() <= self.history.len() {
+            let mut state = self.states.last().unwrap().clone();
+            state.play(game, self.history[self.states.len() - 1]);
+            self.states.push(state);
+        }
+
+        let state = self.states.last_mut().unwrap();
+        state.cache_normalized_weights(game);
+        state
+    }
+
+    /// Visits the node and its descendants.
+    fn walk_recursive<F: FnMut(&mut WalkNode) -> bool>(
+        &mut self,
+        config: &WalkConfig,
+        visitor: &mut F,
+    ) {
+        // discard the states of the previously visited siblings and their subtrees
+        self.states.truncate(self.history.len());
+
+        if !visitor(self) || self.is_terminal() {
+            return;
+        }
+
+        if config
+            .max_depth
+            .is_some_and(|max_depth| self.history.len() >= max_depth)
+        {
+            return;
+        }
+
+        let node = self.node();
+
+        if node.is_chance() {
+            let next_street = match node.turn {
+                NOT_DEALT => BoardState::Turn,
+                _ => BoardState::River,
+            };
+            let max_street = config.max_street.unwrap_or(BoardState::River);
+            if next_street > max_street.min(self.game.storage_mode) {
+                return;
+            }
+        }
+
+        for (action, child) in node.children().iter().enumerate() {
+            let child = child.lock();
+            let mut total_bet_amount = self.total_bet_amount();
+
+            match child.prev_action {
+                Action::Chance(card) => self.history.push(card as usize),
+                _ => {
+                    update_bet_amount(&mut total_bet_amount, &node, action);
+                    self.history.push(action);
+                }
+            }
+
+            self.line.push(child.prev_action);
+            self.node_indices.push(self.game.node_index(&child));
+            self.total_bet_amounts.push(total_bet_amount);
+
+            self.walk_recursive(config, visitor);
+
+            self.history.pop();
+            self.line.pop();
+            self.node_indices.pop();
+            self.total_bet_amounts.pop();
+        }
+    }
+}
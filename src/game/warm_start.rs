@@ -0,0 +1,209 @@
+use super::*;
+use crate::interface::*;
+use crate::solver::*;
+use std::collections::HashMap;
+
+impl PostFlopGame {
+    /// Initializes the regrets and cumulative strategy from the solution of `source`, which is a
+    /// game with a similar configuration (e.g., with an additional bet size or slightly different
+    /// ranges).
+    ///
+    /// The nodes are matched by following the same line in both games. If an action does not
+    /// exist in `source`, the bet, raise, or all-in action whose amount is the closest is followed
+    /// instead (the smaller one is preferred in case of a tie), and the nodes after an action
+    /// without a counterpart are not initialized. If several actions correspond to the same action
+    /// of `source` (e.g., a bet size added next to an existing one), its regrets and cumulative
+    /// strategy are divided equally among them, so that the average strategy of the other actions
+    /// is the same as that of `source`. The private hands are matched by their cards, and the
+    /// hands that do not exist in `source` are not initialized. Continue solving by [`solve_from`]
+    /// with the number of iterations already performed for `source`.
+    ///
+    /// The boards of both games must be the same. This game must be ready to be solved, and
+    /// `source` must not be finalized because the cumulative regrets are overwritten by
+    /// [`finalize`]; solve `source` by [`solve_step`] instead of [`solve`]. The node locking and
+    /// aggregate-frequency constraints of `source` are not carried over.
+    ///
+    /// Returns the number of the initialized nodes.
+    ///
+    /// # Examples
+    /// ```
+    /// use postflop_solver::*;
+    ///
+    /// let card_config = CardConfig {
+    ///     range: ["TT+,AQs+".parse().unwrap(), "99+,AJs+".parse().unwrap()],
+    ///     flop: flop_from_str("Td9d6h").unwrap(),
+    ///     turn: card_from_str("Qc").unwrap(),
+    ///     river: card_from_str("2s").unwrap(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let tree_config = TreeConfig {
+    ///     initial_state: BoardState::River,
+    ///     starting_pot: 100,
+    ///     effective_stack: 300,
+    ///     river_bet_sizes: [("50%", "").try_into().unwrap(), Default::default()],
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let action_tree = ActionTree::new(tree_config.clone()).unwrap();
+    /// let mut source = PostFlopGame::with_config(card_config.clone(), action_tree).unwrap();
+    /// source.allocate_memory(false);
+    /// for t in 0..50 {
+    ///     solve_step(&source, t);
+    /// }
+    ///
+    /// // add a bet size of 75%
+    /// let action_tree = ActionTree::new(TreeConfig {
+    ///     river_bet_sizes: [("50%, 75%", "").try_into().unwrap(), Default::default()],
+    ///     ..tree_config
+    /// })
+    /// .unwrap();
+    /// let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    /// game.allocate_memory(false);
+    ///
+    /// assert!(game.warm_start_from(&source).unwrap() > 0);
+    /// solve_from(&mut game, 50, 50, 0.0, false);
+    /// ```
+    ///
+    /// [`finalize`]: crate::finalize
+    pub fn warm_start_from(&mut self, source: &PostFlopGame) -> Result<usize, String> {
+        if !self.is_ready() {
+            return Err("Game is not ready".to_string());
+        }
+
+        if source.state == State::Solved {
+            return Err("Source game is already solved".to_string());
+        }
+
+        if !source.is_ready() {
+            return Err("Source game is not ready".to_string());
+        }
+
+        let (config, source_config) = (&self.card_config, &source.card_config);
+        if (config.flop, config.turn, config.river)
+            != (source_config.flop, source_config.turn, source_config.river)
+        {
+            return Err("Boards do not match".to_string());
+        }
+
+        let hand_map = [0, 1].map(|player| {
+            let source_index = source.private_cards[player]
+                .iter()
+                .enumerate()
+                .map(|(index, &hand)| (hand, index))
+                .collect::<HashMap<_, _>>();
+            self.private_cards[player]
+                .iter()
+                .map(|hand| source_index.get(hand).copied())
+                .collect::<Vec<_>>()
+        });
+
+        let mut count = 0;
+        let game = &*self;
+        warm_start_recursive(
+            game,
+            &mut game.root(),
+            source,
+            &source.root(),
+            &hand_map,
+            &mut count,
+        );
+
+        Ok(count)
+    }
+}
+
+/// Copies the regrets and cumulative strategy of the nodes of `src` to the corresponding nodes of
+/// `dst`, and increments `count` for each initialized node.
+fn warm_start_recursive(
+    dst_game: &PostFlopGame,
+    dst: &mut PostFlopNode,
+    src_game: &PostFlopGame,
+    src: &PostFlopNode,
+    hand_map: &[Vec<Option<usize>>; 2],
+    count: &mut usize,
+) {
+    if dst.is_terminal() || src.is_terminal() || dst.is_chance() != src.is_chance() {
+        return;
+    }
+
+    if !dst.is_chance() && dst.player() != src.player() {
+        return;
+    }
+
+    let src_actions = src
+        .children()
+        .iter()
+        .map(|child| child.lock().prev_action)
+        .collect::<Vec<_>>();
+
+    let mapping = dst
+        .children()
+        .iter()
+        .map(|child| matching_action(child.lock().prev_action, &src_actions))
+        .collect::<Vec<_>>();
+
+    if !dst.is_chance() && dst.num_elements > 0 && src.num_elements > 0 {
+        // the number of actions sharing each source action
+        let mut num_shares = vec![0; src_actions.len()];
+        for &j in mapping.iter().flatten() {
+            num_shares[j] += 1;
+        }
+
+        let player = dst.player();
+        let num_hands = dst_game.num_private_hands(player);
+        let src_num_hands = src_game.num_private_hands(player);
+        let src_strategy = decoded_cum_strategy(src_game, src);
+        let src_regrets = decoded_regrets(src_game, src);
+
+        let mut strategy = vec![0.0; dst.num_elements as usize];
+        let mut regrets = vec![0.0; dst.num_elements as usize];
+
+        for (i, &index) in mapping.iter().enumerate() {
+            let Some(j) = index else { continue };
+            let share = 1.0 / num_shares[j] as f32;
+            for (hand, &src_hand) in hand_map[player].iter().enumerate() {
+                if let Some(src_hand) = src_hand {
+                    let src_index = j * src_num_hands + src_hand;
+                    strategy[i * num_hands + hand] = src_strategy[src_index] * share;
+                    regrets[i * num_hands + hand] = src_regrets[src_index] * share;
+                }
+            }
+        }
+
+        encode_cum_strategy(dst_game, dst, &strategy);
+        encode_regrets(dst_game, dst, &regrets);
+        *count += 1;
+    }
+
+    for (i, &index) in mapping.iter().enumerate() {
+        if let Some(j) = index {
+            let (mut dst_child, src_child) = (dst.play(i), src.play(j));
+            warm_start_recursive(
+                dst_game,
+                &mut dst_child,
+                src_game,
+                &src_child,
+                hand_map,
+                count,
+            );
+        }
+    }
+}
+
+/// Returns the index of the action in `actions` corresponding to `action`, i.e., `action` itself
+/// if available, or the bet, raise, or all-in action whose amount is the closest.
+#[inline]
+fn matching_action(action: Action, actions: &[Action]) -> Option<usize> {
+    if let Some(index) = actions.iter().position(|&a| a == action) {
+        return Some(index);
+    }
+
+    let target = bet_amount(action)?;
+    actions
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &a)| bet_amount(a).map(|amount| (i, amount)))
+        .min_by_key(|&(_, amount)| ((amount - target).abs(), amount))
+        .map(|(i, _)| i)
+}
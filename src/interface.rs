@@ -2,6 +2,94 @@ use crate::mutex_like::*;
 use std::mem::MaybeUninit;
 use std::ops::Range;
 
+#[cfg(feature = "bincode")]
+use bincode::{Decode, Encode};
+
+/// An enum representing the precision of the values stored in the game tree.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "bincode", derive(Decode, Encode))]
+pub enum Precision {
+    /// 32-bit floating-point numbers (no compression).
+    #[default]
+    Float32,
+
+    /// 16-bit integers with a single 32-bit floating-point scaling factor per node.
+    Int16,
+
+    /// 8-bit integers with a single 32-bit floating-point scaling factor per node.
+    ///
+    /// The values are stochastically rounded with deterministic dithering so that the accumulated
+    /// values do not get stuck even if each update is smaller than the quantization step.
+    Int8,
+}
+
+/// A struct representing the precision of each kind of the values stored in a game node.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "bincode", derive(Decode, Encode))]
+pub struct StoragePrecision {
+    /// Precision of the cumulative strategy.
+    pub strategy: Precision,
+
+    /// Precision of the cumulative regrets.
+    pub regrets: Precision,
+
+    /// Precision of the counterfactual values (computed after solving).
+    pub cfvalues: Precision,
+}
+
+impl Precision {
+    /// Returns the number of bytes per element.
+    #[inline]
+    pub fn num_bytes(self) -> usize {
+        match self {
+            Precision::Float32 => 4,
+            Precision::Int16 => 2,
+            Precision::Int8 => 1,
+        }
+    }
+}
+
+impl StoragePrecision {
+    /// Creates a new instance that uses the same precision for all kinds of values.
+    #[inline]
+    pub fn uniform(precision: Precision) -> Self {
+        Self {
+            strategy: precision,
+            regrets: precision,
+            cfvalues: precision,
+        }
+    }
+}
+
+/// A struct representing a bound of the aggregate frequency of a group of actions.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "bincode", derive(Decode, Encode))]
+pub struct AggregateBound {
+    /// Indices of the actions in the group.
+    pub actions: Vec<usize>,
+
+    /// Lower bound of the aggregate frequency of the group.
+    pub lower: f32,
+
+    /// Upper bound of the aggregate frequency of the group.
+    pub upper: f32,
+}
+
+/// A struct representing an aggregate-frequency constraint of a node.
+///
+/// The aggregate frequency of a group of actions is defined as the sum of
+/// `weights[j] * strategy[i * #(private hands) + j]` over all private hands `j` and all actions `i`
+/// in the group. The groups of `bounds` must be disjoint.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "bincode", derive(Decode, Encode))]
+pub struct AggregateConstraint {
+    /// Weight of each private hand (normalized so that the sum is 1.0).
+    pub weights: Vec<f32>,
+
+    /// Bounds of the aggregate frequencies.
+    pub bounds: Vec<AggregateBound>,
+}
+
 /// The trait representing a game.
 pub trait Game: Send + Sync {
     /// The type representing a node in game tree.
@@ -72,11 +160,27 @@ pub trait Game: Send + Sync {
         &[]
     }
 
+    /// Returns the aggregate-frequency constraint.
+    #[doc(hidden)]
+    fn aggregate_constraint(&self, _node: &Self::Node) -> Option<&AggregateConstraint> {
+        None
+    }
+
     /// Returns whether the compression is enabled.
     #[doc(hidden)]
     fn is_compression_enabled(&self) -> bool {
         false
     }
+
+    /// Returns the precision of the values stored in the given node.
+    #[doc(hidden)]
+    fn storage_precision(&self, _node: &Self::Node) -> StoragePrecision {
+        if self.is_compression_enabled() {
+            StoragePrecision::uniform(Precision::Int16)
+        } else {
+            StoragePrecision::uniform(Precision::Float32)
+        }
+    }
 }
 
 /// The trait representing a node in game tree.
@@ -227,6 +331,66 @@ pub trait GameNode: Send + Sync {
         unreachable!()
     }
 
+    /// Returns the 8-bit compressed strategy.
+    #[doc(hidden)]
+    fn strategy_u8(&self) -> &[u8] {
+        unreachable!()
+    }
+
+    /// Returns the mutable reference to the 8-bit compressed strategy.
+    #[doc(hidden)]
+    fn strategy_u8_mut(&mut self) -> &mut [u8] {
+        unreachable!()
+    }
+
+    /// Returns the 8-bit compressed cumulative regrets.
+    #[doc(hidden)]
+    fn regrets_i8(&self) -> &[i8] {
+        unreachable!()
+    }
+
+    /// Returns the mutable reference to the 8-bit compressed cumulative regrets.
+    #[doc(hidden)]
+    fn regrets_i8_mut(&mut self) -> &mut [i8] {
+        unreachable!()
+    }
+
+    /// Returns the 8-bit compressed counterfactual values.
+    #[doc(hidden)]
+    fn cfvalues_i8(&self) -> &[i8] {
+        unreachable!()
+    }
+
+    /// Returns the mutable reference to the 8-bit compressed counterfactual values.
+    #[doc(hidden)]
+    fn cfvalues_i8_mut(&mut self) -> &mut [i8] {
+        unreachable!()
+    }
+
+    /// Returns IP's 8-bit compressed counterfactual values.
+    #[doc(hidden)]
+    fn cfvalues_ip_i8(&self) -> &[i8] {
+        unreachable!()
+    }
+
+    /// Returns the mutable reference to IP's 8-bit compressed counterfactual values.
+    #[doc(hidden)]
+    fn cfvalues_ip_i8_mut(&mut self) -> &mut [i8] {
+        unreachable!()
+    }
+
+    /// Returns the 8-bit compressed buffer for counterfactual values.
+    #[doc(hidden)]
+    fn cfvalues_chance_i8(&self) -> &[i8] {
+        unreachable!()
+    }
+
+    /// Returns the mutable reference to the 8-bit compressed buffer for counterfactual values.
+    #[doc(hidden)]
+    fn cfvalues_chance_i8_mut(&mut self) -> &mut [i8] {
+        unreachable!()
+    }
+
     /// Returns the scale of the compressed strategy.
     #[doc(hidden)]
     fn strategy_scale(&self) -> f32 {
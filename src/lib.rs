@@ -22,6 +22,8 @@
 //!   When calculating summations, temporary values use 64-bit floating-point numbers.
 //!   There is also a compression option where each game node stores the values
 //!   by 16-bit integers with a single 32-bit floating-point scaling factor.
+//!   The precision can also be configured for each street and each kind of values
+//!   (strategy, regrets, and counterfactual values), including an 8-bit option.
 //! - **Bunching effect**: At the time of writing, this is the only implementation that can handle the bunching effect.
 //!   It supports up to four folded players (6-max game).
 //!   The implementation correctly counts the number of card combinations and does not rely on heuristics
@@ -41,6 +43,10 @@
 //!   Note that this feature assumes that, at most, only one instance of `PostFlopGame` is available
 //!   when solving in a program.
 //!   Disabled by default.
+//! - `mmap`: Uses [memmap2] crate to memory-map the storage of the game tree to files.
+//!   This feature is required to use `StorageBackend::Disk`, which allows solving game trees
+//!   larger than the physical memory.
+//!   Disabled by default.
 //! - `rayon`: Uses [rayon] crate for parallelization.
 //!   Enabled by default.
 //! - `zstd`: Uses [zstd] crate to compress and decompress the game tree.
@@ -48,6 +54,7 @@
 //!   Disabled by default.
 //!
 //! [bincode]: https://github.com/bincode-org/bincode
+//! [memmap2]: https://github.com/RazrFalcon/memmap2-rs
 //! [rayon]: https://github.com/rayon-rs/rayon
 //! [zstd]: https://github.com/gyscos/zstd-rs
 
@@ -69,9 +76,13 @@ mod hand;
 mod hand_table;
 mod interface;
 mod mutex_like;
+mod pio_script;
 mod range;
 mod sliceop;
 mod solver;
+mod tree_diff;
+mod tree_stats;
+mod tree_text;
 mod utility;
 
 #[cfg(feature = "bincode")]
@@ -84,6 +95,9 @@ pub use card::*;
 pub use game::*;
 pub use interface::*;
 pub use mutex_like::*;
+pub use pio_script::*;
 pub use range::*;
 pub use solver::*;
+pub use tree_diff::*;
+pub use tree_stats::*;
 pub use utility::*;
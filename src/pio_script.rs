@@ -0,0 +1,578 @@
+use crate::action_tree::*;
+use crate::bet_size::*;
+use crate::card::*;
+use crate::range::*;
+
+/// An edit of an action tree by a line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LineEdit {
+    /// Adds the line (see [`ActionTree::add_line`]).
+    Add(Vec<Action>),
+
+    /// Removes the line (see [`ActionTree::remove_line`]).
+    Remove(Vec<Action>),
+}
+
+/// The result of [`import_pio_script`].
+#[derive(Debug, Clone, Default)]
+pub struct PioImport {
+    /// The tree configuration.
+    pub tree_config: TreeConfig,
+
+    /// The board specified by the script (empty if not specified).
+    pub board: Vec<Card>,
+
+    /// The line edits in the order of the script.
+    pub line_edits: Vec<LineEdit>,
+
+    /// The lines that are not converted, in the form `Line <number>: <line>`.
+    pub unsupported: Vec<String>,
+}
+
+/// Imports a PioSOLVER tree-building script or a tree configuration file.
+///
+/// The following commands and keys are converted, and all other non-empty lines are reported in
+/// [`PioImport::unsupported`] instead of being silently ignored:
+///
+/// - `set_board <cards>`: sets the board and the initial state (`#Board#<cards>` is also
+///   accepted).
+/// - `set_pot <oop> <ip> <pot>`: sets the starting pot to the sum of the three amounts. `<oop>`
+///   and `<ip>` must be equal (`#Pot#<pot>` is also accepted).
+/// - `set_eff_stack <stack>`: sets the effective stack (`#EffectiveStacks#<stack>` is also
+///   accepted).
+/// - `set_isomorphism <flop> <turn>`: accepted without effect, since the isomorphism is always
+///   applied.
+/// - `clear_lines`: accepted without effect. The lines of the script are added to the tree built
+///   from the configuration, so the configuration should not contain bet sizes when the script
+///   defines the whole tree by lines.
+/// - `add_line <amounts>` and `remove_line <amounts>`: each amount is the total number of chips the
+///   acting player has put into the pot since the start of the tree, so a check repeats the
+///   previous amount. Converted to [`LineEdit`]s.
+/// - `#<Street>Config[IP].BetSize#<sizes>`, `#<Street>Config[IP].RaiseSize#<sizes>`, and
+///   `#<Street>Config.DonkBetSize#<sizes>`, where `<Street>` is `Flop`, `Turn`, or `River` and the
+///   `IP` suffix specifies the IP player: space- or comma-separated sizes, where plain numbers are
+///   percentages of the pot and the other sizes are in the syntax of [`BetSizeOptions`].
+/// - `#AddAllinOnlyIfLessThanThisTimesThePot#<percent>`: sets the add all-in threshold.
+/// - `#MergeSimilarBets#<bool>` and `#MergeSimilarBetsThreshold#<percent>`: set the merging
+///   threshold.
+/// - `#CapEnabled#<bool>` and `#CapPerStreet#<number>`: set [`TreeConfig::max_bets_per_street`]
+///   to the cap (the first bet counts toward the cap).
+/// - `#<Street>Config[IP].AddAllin#<bool>`: accepted without effect (see
+///   `#AddAllinOnlyIfLessThanThisTimesThePot#`).
+///
+/// Lines starting with `//` or `;` are comments.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// let script = "
+///     set_board Qs7h2c
+///     set_pot 0 0 60
+///     set_eff_stack 970
+///     set_isomorphism 1 0
+///     clear_lines
+///     add_line 0 30 90 90 90 90 90 90
+///     add_line 0 30 30 60 60 60 60
+///     set_accuracy 0.3
+///     build_tree
+/// ";
+///
+/// let import = import_pio_script(script).unwrap();
+/// assert_eq!(import.board.len(), 3);
+/// assert_eq!(import.tree_config.starting_pot, 60);
+/// assert_eq!(import.tree_config.effective_stack, 970);
+/// assert_eq!(import.unsupported, ["Line 9: set_accuracy 0.3", "Line 10: build_tree"]);
+///
+/// let line = line_from_str("X B30 R90 C X X X X").unwrap();
+/// assert_eq!(import.line_edits[0], LineEdit::Add(line));
+///
+/// // the bets of the lines are added to the tree without bet sizes
+/// let action_tree = import.build_action_tree().unwrap();
+/// assert_eq!(action_tree.added_lines().len(), 3); // X B30, X B30 R90, X B30 C B30
+/// ```
+pub fn import_pio_script(script: &str) -> Result<PioImport, String> {
+    let mut ret = PioImport::default();
+    let mut pio_lines = Vec::new();
+    let mut add_allin_threshold = None;
+    let mut merge = (false, None);
+    let mut cap = (false, None);
+
+    for (i, line) in script.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with("//") || line.starts_with(';') {
+            continue;
+        }
+
+        let line_number = i + 1;
+        let with_line_number = |e: String| format!("Line {line_number}: {e}");
+
+        if let Some(key_value) = line.strip_prefix('#') {
+            let Some((key, value)) = key_value.split_once('#') else {
+                return Err(with_line_number(format!(
+                    "Expected '#<key>#<value>': {line}"
+                )));
+            };
+
+            let value = value.trim();
+            let converted = match key {
+                "Board" => {
+                    ret.board = board_from_str(value).map_err(with_line_number)?;
+                    true
+                }
+                "Pot" => {
+                    ret.tree_config.starting_pot = parse_amount(value).map_err(with_line_number)?;
+                    true
+                }
+                "EffectiveStacks" => {
+                    let stack = parse_amount(value).map_err(with_line_number)?;
+                    ret.tree_config.effective_stack = stack;
+                    true
+                }
+                "AddAllinOnlyIfLessThanThisTimesThePot" => {
+                    let percent = parse_percent(value).map_err(with_line_number)?;
+                    add_allin_threshold = Some(percent);
+                    true
+                }
+                "MergeSimilarBets" => {
+                    merge.0 = parse_bool(value).map_err(with_line_number)?;
+                    true
+                }
+                "MergeSimilarBetsThreshold" => {
+                    merge.1 = Some(parse_percent(value).map_err(with_line_number)?);
+                    true
+                }
+                "CapEnabled" => {
+                    cap.0 = parse_bool(value).map_err(with_line_number)?;
+                    true
+                }
+                "CapPerStreet" => {
+                    let value = match value.parse::<u32>() {
+                        Ok(value) if value > 0 => value,
+                        _ => return Err(with_line_number(format!("Invalid cap: {value}"))),
+                    };
+                    cap.1 = Some(value);
+                    true
+                }
+                _ => {
+                    set_street_config(&mut ret.tree_config, key, value).map_err(with_line_number)?
+                }
+            };
+
+            if !converted {
+                ret.unsupported.push(with_line_number(line.to_string()));
+            }
+
+            continue;
+        }
+
+        let mut args = line.split_whitespace();
+        let command = args.next().unwrap();
+        let args = args.collect::<Vec<_>>();
+
+        match command {
+            "set_board" => {
+                ret.board = board_from_str(&args.concat()).map_err(with_line_number)?;
+            }
+            "set_pot" => {
+                let amounts = args
+                    .iter()
+                    .map(|arg| parse_amount(arg))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(with_line_number)?;
+                if amounts.len() != 3 {
+                    return Err(with_line_number(format!("Expected three amounts: {line}")));
+                }
+                if amounts[0] != amounts[1] {
+                    let e = format!("Different amounts of the players are not supported: {line}");
+                    return Err(with_line_number(e));
+                }
+                ret.tree_config.starting_pot = amounts.iter().sum();
+            }
+            "set_eff_stack" => {
+                let stack = match args[..] {
+                    [stack] => parse_amount(stack).map_err(with_line_number)?,
+                    _ => return Err(with_line_number(format!("Expected an amount: {line}"))),
+                };
+                ret.tree_config.effective_stack = stack;
+            }
+            "set_isomorphism" | "clear_lines" => {}
+            "add_line" | "remove_line" => {
+                let amounts = args
+                    .iter()
+                    .map(|arg| parse_amount(arg))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(with_line_number)?;
+                pio_lines.push((line_number, command == "add_line", amounts));
+            }
+            _ => ret.unsupported.push(with_line_number(line.to_string())),
+        }
+    }
+
+    ret.tree_config.initial_state = match ret.board.len() {
+        0 | 3 => BoardState::Flop,
+        4 => BoardState::Turn,
+        5 => BoardState::River,
+        n => return Err(format!("Invalid number of board cards: {n}")),
+    };
+
+    if let Some(threshold) = add_allin_threshold {
+        ret.tree_config.add_allin_threshold = threshold;
+    }
+
+    if let (true, Some(threshold)) = merge {
+        ret.tree_config.merging_threshold = threshold;
+    }
+
+    if let (true, Some(cap)) = cap {
+        ret.tree_config.max_bets_per_street = Some(cap);
+    }
+
+    for (line_number, is_add, amounts) in pio_lines {
+        let line = line_from_amounts(&ret.tree_config, &amounts)
+            .map_err(|e| format!("Line {line_number}: {e}"))?;
+        ret.line_edits.push(match is_add {
+            true => LineEdit::Add(line),
+            false => LineEdit::Remove(line),
+        });
+    }
+
+    Ok(ret)
+}
+
+impl PioImport {
+    /// Builds an [`ActionTree`] from the configuration and applies the line edits.
+    ///
+    /// Since the lines of a PioSOLVER script describe the whole path to a terminal node, the bet
+    /// actions of an added line that do not exist in the tree are added one by one from the root.
+    /// The other actions of the line must exist in the tree.
+    pub fn build_action_tree(&self) -> Result<ActionTree, String> {
+        let mut ret = ActionTree::new(self.tree_config.clone())?;
+
+        for edit in &self.line_edits {
+            match edit {
+                LineEdit::Add(line) => {
+                    for i in 0..line.len() {
+                        ret.apply_history(&line[..i])?;
+                        if ret.is_terminal_node() {
+                            return Err(format!("Line reaches a terminal node: {line:?}"));
+                        }
+                        if !ret.available_actions().contains(&line[i]) {
+                            ret.add_line(&line[..=i])?;
+                        }
+                    }
+                }
+                LineEdit::Remove(line) => ret.remove_line(line)?,
+            }
+        }
+
+        ret.back_to_root();
+        Ok(ret)
+    }
+}
+
+/// Converts a line of PioSOLVER, where each amount is the total number of chips put into the pot
+/// by the acting player, into a list of actions.
+fn line_from_amounts(config: &TreeConfig, amounts: &[i32]) -> Result<Vec<Action>, String> {
+    let mut ret = Vec::with_capacity(amounts.len());
+    let mut street = config.initial_state;
+    let mut committed = [0, 0];
+    let mut street_start = 0;
+    let mut player = 0;
+    let mut is_finished = false;
+
+    for &amount in amounts {
+        if is_finished {
+            return Err(format!("Line continues after a terminal node: {amounts:?}"));
+        }
+
+        let opponent = player ^ 1;
+        let mut is_street_finished = false;
+
+        let action = if amount == committed[player] && amount == committed[opponent] {
+            is_street_finished = player == 1;
+            Action::Check
+        } else if amount == committed[player] {
+            is_finished = true;
+            Action::Fold
+        } else if amount == committed[opponent] {
+            is_street_finished = true;
+            Action::Call
+        } else if amount > committed[opponent] && amount <= config.effective_stack {
+            let street_amount = amount - street_start;
+            if amount == config.effective_stack {
+                Action::AllIn(street_amount)
+            } else if committed[opponent] == street_start {
+                Action::Bet(street_amount)
+            } else {
+                Action::Raise(street_amount)
+            }
+        } else {
+            return Err(format!("Invalid amount: {amount} (line: {amounts:?})"));
+        };
+
+        committed[player] = amount;
+        ret.push(action);
+
+        if is_street_finished {
+            street_start = amount;
+            player = 0;
+            match street {
+                BoardState::Flop => street = BoardState::Turn,
+                BoardState::Turn => street = BoardState::River,
+                BoardState::River => is_finished = true,
+            }
+        } else {
+            player = opponent;
+        }
+    }
+
+    Ok(ret)
+}
+
+/// Sets the bet sizes of a street. Returns `Ok(false)` if the key is not supported.
+fn set_street_config(config: &mut TreeConfig, key: &str, value: &str) -> Result<bool, String> {
+    let Some((street_key, field)) = key.split_once('.') else {
+        return Ok(false);
+    };
+
+    let (street, player) = match street_key {
+        "FlopConfig" => (BoardState::Flop, 0),
+        "TurnConfig" => (BoardState::Turn, 0),
+        "RiverConfig" => (BoardState::River, 0),
+        "FlopConfigIP" => (BoardState::Flop, 1),
+        "TurnConfigIP" => (BoardState::Turn, 1),
+        "RiverConfigIP" => (BoardState::River, 1),
+        _ => return Ok(false),
+    };
+
+    let options = match street {
+        BoardState::Flop => &mut config.flop_bet_sizes[player],
+        BoardState::Turn => &mut config.turn_bet_sizes[player],
+        BoardState::River => &mut config.river_bet_sizes[player],
+    };
+
+    let sizes = value
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|token| !token.is_empty())
+        .map(|token| match token.parse::<f64>() {
+            Ok(_) => format!("{token}%"),
+            Err(_) => token.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+
+    match field {
+        "BetSize" => {
+            options.bet = BetSizeOptions::try_from((sizes.as_str(), ""))?.bet;
+        }
+        "RaiseSize" => {
+            options.raise = BetSizeOptions::try_from(("", sizes.as_str()))?.raise;
+        }
+        "DonkBetSize" if player == 0 && street != BoardState::Flop => {
+            let donk_sizes = Some(DonkSizeOptions::try_from(sizes.as_str())?);
+            match street {
+                BoardState::Turn => config.turn_donk_sizes = donk_sizes,
+                _ => config.river_donk_sizes = donk_sizes,
+            }
+        }
+        "AddAllin" => {
+            parse_bool(value)?;
+        }
+        _ => return Ok(false),
+    }
+
+    Ok(true)
+}
+
+/// Parses a board of three to five cards, optionally separated by spaces.
+fn board_from_str(s: &str) -> Result<Vec<Card>, String> {
+    let mut chars = s.chars().filter(|c| !c.is_whitespace()).peekable();
+    let mut ret = Vec::new();
+
+    while chars.peek().is_some() {
+        let card = card_from_chars(&mut chars)?;
+        if ret.contains(&card) {
+            return Err(format!("Duplicate card in board: {s}"));
+        }
+        ret.push(card);
+    }
+
+    if !(3..=5).contains(&ret.len()) {
+        return Err(format!("Board must have three to five cards: {s}"));
+    }
+
+    Ok(ret)
+}
+
+/// Parses a non-negative integer amount.
+fn parse_amount(s: &str) -> Result<i32, String> {
+    s.parse::<i32>()
+        .ok()
+        .filter(|&amount| amount >= 0)
+        .ok_or_else(|| format!("Invalid amount: {s}"))
+}
+
+/// Parses a percentage (e.g., `12` for `0.12`).
+fn parse_percent(s: &str) -> Result<f64, String> {
+    s.parse::<f64>()
+        .ok()
+        .filter(|&percent| percent >= 0.0)
+        .map(|percent| percent / 100.0)
+        .ok_or_else(|| format!("Invalid percentage: {s}"))
+}
+
+/// Parses `True` or `False`.
+fn parse_bool(s: &str) -> Result<bool, String> {
+    match s.to_lowercase().as_str() {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        _ => Err(format!("Invalid boolean: {s}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_from_amounts() {
+        let config = TreeConfig {
+            initial_state: BoardState::Turn,
+            starting_pot: 100,
+            effective_stack: 500,
+            ..Default::default()
+        };
+
+        let tests = [
+            (vec![0, 0, 50, 50], "X X B50 C"),
+            (vec![50, 150, 500, 500], "B50 R150 A500 C"),
+            (vec![50, 0], "B50 F"),
+            (vec![0, 70, 210, 210], "X B70 R210 C"),
+            (vec![40, 40, 40, 120, 40], "B40 C X B80 F"),
+            (vec![500, 500], "A500 C"),
+            (vec![], ""),
+        ];
+
+        for (amounts, expected) in tests {
+            let line = line_from_amounts(&config, &amounts).unwrap();
+            assert_eq!(line_to_string(&line).unwrap(), expected);
+        }
+
+        let error_tests = [
+            vec![0, 0, 0, 0, 0],
+            vec![50, 20],
+            vec![600],
+            vec![0, 30, 0, 30],
+        ];
+
+        for amounts in error_tests {
+            assert!(line_from_amounts(&config, &amounts).is_err());
+        }
+    }
+
+    #[test]
+    fn test_import_config_file() {
+        let config_file = "
+            #Type#NoLimit
+            #Board#Kh 9d 6c 2s
+            #Pot#60
+            #EffectiveStacks#970
+            #AllinThreshold#67
+            #AddAllinOnlyIfLessThanThisTimesThePot#250
+            #MergeSimilarBets#True
+            #MergeSimilarBetsThreshold#12
+            #CapEnabled#True
+            #CapPerStreet#4
+            #TurnConfig.BetSize#33 75
+            #TurnConfig.RaiseSize#2.5x
+            #TurnConfig.AddAllin#True
+            #TurnConfigIP.BetSize#50, e
+            #RiverConfig.DonkBetSize#25
+            #RiverConfigIP.RaiseSize#60
+        ";
+
+        let import = import_pio_script(config_file).unwrap();
+        let config = &import.tree_config;
+        assert_eq!(import.board.len(), 4);
+        assert_eq!(config.initial_state, BoardState::Turn);
+        assert_eq!(config.starting_pot, 60);
+        assert_eq!(config.effective_stack, 970);
+        assert_eq!(config.add_allin_threshold, 2.5);
+        assert_eq!(config.merging_threshold, 0.12);
+        assert_eq!(config.max_bets_per_street, Some(4));
+        assert_eq!(
+            config.turn_bet_sizes[0],
+            ("33%, 75%", "2.5x").try_into().unwrap()
+        );
+        assert_eq!(
+            config.turn_bet_sizes[1].bet,
+            BetSizeOptions::try_from(("50%, e", "")).unwrap().bet
+        );
+        assert_eq!(config.river_donk_sizes, Some("25%".try_into().unwrap()));
+        assert_eq!(
+            config.river_bet_sizes[1].raise,
+            BetSizeOptions::try_from(("", "60%")).unwrap().raise
+        );
+        assert_eq!(
+            import.unsupported,
+            ["Line 2: #Type#NoLimit", "Line 6: #AllinThreshold#67"]
+        );
+    }
+
+    #[test]
+    fn test_import_script_edits() {
+        let script = "
+            // remove the raise of the configuration
+            set_board Td9d6h Qc 2s
+            set_pot 10 10 80
+            set_eff_stack 400
+            #RiverConfig.BetSize#50
+            #RiverConfigIP.BetSize#50
+            #RiverConfig.RaiseSize#3x
+            #RiverConfigIP.RaiseSize#3x
+            remove_line 50 150
+            add_line 0 100 100
+        ";
+
+        let import = import_pio_script(script).unwrap();
+        assert!(import.unsupported.is_empty());
+        assert_eq!(import.tree_config.starting_pot, 100);
+
+        let mut action_tree = import.build_action_tree().unwrap();
+        assert_eq!(
+            action_tree.removed_lines(),
+            &[line_from_str("B50 R150").unwrap()]
+        );
+        assert_eq!(
+            action_tree.added_lines(),
+            &[line_from_str("X B100").unwrap()]
+        );
+        action_tree.play(Action::Bet(50)).unwrap();
+        assert_eq!(
+            action_tree.available_actions(),
+            &[Action::Fold, Action::Call]
+        );
+
+        let error_tests = [
+            "set_pot 0 10 80",
+            "set_pot 100",
+            "set_eff_stack",
+            "set_board Td9d",
+            "set_board TdTd6h",
+            "#Pot",
+            "#Pot#-1",
+            "#MergeSimilarBets#yes",
+            "#FlopConfig.BetSize#2.5x",
+            "set_pot 0 0 100\nset_eff_stack 200\nadd_line 0 300",
+        ];
+
+        for script in error_tests {
+            assert!(import_pio_script(script).is_err(), "{script}");
+        }
+
+        // valid script, but the removed line does not exist in the tree
+        let import = import_pio_script("set_pot 0 0 100\nset_eff_stack 200\nremove_line 0 37");
+        assert!(import.unwrap().build_action_tree().is_err());
+    }
+}
@@ -40,11 +40,39 @@ impl DiscountParams {
 /// satisfied.
 ///
 /// This method returns the exploitability of the obtained strategy.
+#[inline]
 pub fn solve<T: Game>(
     game: &mut T,
     max_num_iterations: u32,
     target_exploitability: f32,
     print_progress: bool,
+) -> f32 {
+    solve_from(
+        game,
+        0,
+        max_num_iterations,
+        target_exploitability,
+        print_progress,
+    )
+}
+
+/// Performs Discounted CFR algorithm starting from the given iteration, i.e., the discount
+/// parameters of the first iteration are those of the `start_iteration`-th iteration.
+///
+/// This method is intended to continue solving a game whose regrets and cumulative strategy are
+/// already initialized (e.g., by [`PostFlopGame::warm_start_from`]). At most
+/// `max_num_iterations` iterations are performed, and the exploitability of the obtained strategy
+/// is returned. `start_iteration` should usually be the number of iterations already performed:
+/// the cumulative regrets are discarded at the iterations 0 and 1, and the cumulative strategy is
+/// discarded whenever the iteration reaches a power of 4.
+///
+/// [`PostFlopGame::warm_start_from`]: crate::PostFlopGame::warm_start_from
+pub fn solve_from<T: Game>(
+    game: &mut T,
+    start_iteration: u32,
+    max_num_iterations: u32,
+    target_exploitability: f32,
+    print_progress: bool,
 ) -> f32 {
     if game.is_solved() {
         panic!("Game is already solved");
@@ -68,7 +96,7 @@ pub fn solve<T: Game>(
             break;
         }
 
-        let params = DiscountParams::new(t);
+        let params = DiscountParams::new(start_iteration + t);
 
         // alternating updates
         for player in 0..2 {
@@ -237,99 +265,113 @@ fn solve_recursive<T: Game>(
         });
 
         // compute the strategy by regret-maching algorithm
-        let mut strategy = if game.is_compression_enabled() {
-            regret_matching_compressed(node.regrets_compressed(), num_actions)
-        } else {
-            regret_matching(node.regrets(), num_actions)
+        let precision = game.storage_precision(node);
+        let mut strategy = match precision.regrets {
+            Precision::Float32 => regret_matching(node.regrets(), num_actions),
+            Precision::Int16 => regret_matching_compressed(node.regrets_compressed(), num_actions),
+            Precision::Int8 => regret_matching_compressed(node.regrets_i8(), num_actions),
         };
 
         // node-locking
         let locking = game.locking_strategy(node);
         apply_locking_strategy(&mut strategy, locking);
 
+        // aggregate-frequency constraint
+        if let Some(constraint) = game.aggregate_constraint(node) {
+            let regrets = decoded_regrets(game, node);
+            apply_aggregate_constraint(&mut strategy, &regrets, locking, constraint);
+        }
+
         // sum up the counterfactual values
         let mut cfv_actions = cfv_actions.lock();
         unsafe { cfv_actions.set_len(num_actions * num_hands) };
         let result = fma_slices_uninit(result, &strategy, &cfv_actions);
 
-        if game.is_compression_enabled() {
-            // update the cumulative strategy
-            let scale = node.strategy_scale();
-            let decoder = params.gamma_t * scale / u16::MAX as f32;
-            let cum_strategy = node.strategy_compressed_mut();
-
-            strategy.iter_mut().zip(&*cum_strategy).for_each(|(x, y)| {
-                *x += (*y as f32) * decoder;
-            });
-
-            if !locking.is_empty() {
-                strategy.iter_mut().zip(locking).for_each(|(d, s)| {
-                    if s.is_sign_positive() {
-                        *d = 0.0;
-                    }
-                })
+        // update the cumulative strategy
+        let gamma = params.gamma_t;
+        match precision.strategy {
+            Precision::Float32 => {
+                let cum_strategy = node.strategy_mut();
+                cum_strategy.iter_mut().zip(&strategy).for_each(|(x, y)| {
+                    *x = *x * gamma + *y;
+                });
             }
-
-            let new_scale = encode_unsigned_slice(cum_strategy, &strategy);
-            node.set_strategy_scale(new_scale);
-
-            // update the cumulative regret
-            let scale = node.regret_scale();
-            let alpha_decoder = params.alpha_t * scale / i16::MAX as f32;
-            let beta_decoder = params.beta_t * scale / i16::MAX as f32;
-            let cum_regret = node.regrets_compressed_mut();
-
-            cfv_actions.iter_mut().zip(&*cum_regret).for_each(|(x, y)| {
-                *x += *y as f32 * if *y >= 0 { alpha_decoder } else { beta_decoder };
-            });
-
-            cfv_actions.chunks_exact_mut(num_hands).for_each(|row| {
-                sub_slice(row, result);
-            });
-
-            if !locking.is_empty() {
-                cfv_actions.iter_mut().zip(locking).for_each(|(d, s)| {
-                    if s.is_sign_positive() {
-                        *d = 0.0;
-                    }
-                })
+            Precision::Int16 => {
+                let scale = node.strategy_scale();
+                let cum_strategy = node.strategy_compressed_mut();
+                let new_scale =
+                    update_strategy_compressed(cum_strategy, scale, &mut strategy, gamma, locking);
+                node.set_strategy_scale(new_scale);
             }
+            Precision::Int8 => {
+                let scale = node.strategy_scale();
+                let cum_strategy = node.strategy_u8_mut();
+                let new_scale =
+                    update_strategy_compressed(cum_strategy, scale, &mut strategy, gamma, locking);
+                node.set_strategy_scale(new_scale);
+            }
+        }
 
-            let new_scale = encode_signed_slice(cum_regret, &cfv_actions);
-            node.set_regret_scale(new_scale);
-        } else {
-            // update the cumulative strategy
-            let gamma = params.gamma_t;
-            let cum_strategy = node.strategy_mut();
-            cum_strategy.iter_mut().zip(&strategy).for_each(|(x, y)| {
-                *x = *x * gamma + *y;
-            });
-
-            // update the cumulative regret
-            let (alpha, beta) = (params.alpha_t, params.beta_t);
-            let cum_regret = node.regrets_mut();
-            cum_regret.iter_mut().zip(&*cfv_actions).for_each(|(x, y)| {
-                let coef = if x.is_sign_positive() { alpha } else { beta };
-                *x = *x * coef + *y;
-            });
-            cum_regret.chunks_exact_mut(num_hands).for_each(|row| {
-                sub_slice(row, result);
-            });
+        // update the cumulative regret
+        let (alpha, beta) = (params.alpha_t, params.beta_t);
+        match precision.regrets {
+            Precision::Float32 => {
+                let cum_regret = node.regrets_mut();
+                cum_regret.iter_mut().zip(&*cfv_actions).for_each(|(x, y)| {
+                    let coef = if x.is_sign_positive() { alpha } else { beta };
+                    *x = *x * coef + *y;
+                });
+                cum_regret.chunks_exact_mut(num_hands).for_each(|row| {
+                    sub_slice(row, result);
+                });
+            }
+            Precision::Int16 => {
+                let scale = node.regret_scale();
+                let cum_regret = node.regrets_compressed_mut();
+                let new_scale = update_regrets_compressed(
+                    cum_regret,
+                    scale,
+                    &mut cfv_actions,
+                    result,
+                    (alpha, beta),
+                    locking,
+                );
+                node.set_regret_scale(new_scale);
+            }
+            Precision::Int8 => {
+                let scale = node.regret_scale();
+                let cum_regret = node.regrets_i8_mut();
+                let new_scale = update_regrets_compressed(
+                    cum_regret,
+                    scale,
+                    &mut cfv_actions,
+                    result,
+                    (alpha, beta),
+                    locking,
+                );
+                node.set_regret_scale(new_scale);
+            }
         }
     }
     // if the current player is not `player`
     else {
         // compute the strategy by regret-matching algorithm
-        let mut cfreach_actions = if game.is_compression_enabled() {
-            regret_matching_compressed(node.regrets_compressed(), num_actions)
-        } else {
-            regret_matching(node.regrets(), num_actions)
+        let mut cfreach_actions = match game.storage_precision(node).regrets {
+            Precision::Float32 => regret_matching(node.regrets(), num_actions),
+            Precision::Int16 => regret_matching_compressed(node.regrets_compressed(), num_actions),
+            Precision::Int8 => regret_matching_compressed(node.regrets_i8(), num_actions),
         };
 
         // node-locking
         let locking = game.locking_strategy(node);
         apply_locking_strategy(&mut cfreach_actions, locking);
 
+        // aggregate-frequency constraint
+        if let Some(constraint) = game.aggregate_constraint(node) {
+            let regrets = decoded_regrets(game, node);
+            apply_aggregate_constraint(&mut cfreach_actions, &regrets, locking, constraint);
+        }
+
         // update the reach probabilities
         let row_size = cfreach.len();
         cfreach_actions.chunks_exact_mut(row_size).for_each(|row| {
@@ -406,9 +448,12 @@ fn regret_matching(regret: &[f32], num_actions: usize) -> Vec<f32> {
 /// Computes the strategy by regret-matching algorithm.
 #[cfg(feature = "custom-alloc")]
 #[inline]
-fn regret_matching_compressed(regret: &[i16], num_actions: usize) -> Vec<f32, StackAlloc> {
+fn regret_matching_compressed<T: Quantized>(
+    regret: &[T],
+    num_actions: usize,
+) -> Vec<f32, StackAlloc> {
     let mut strategy = Vec::with_capacity_in(regret.len(), StackAlloc);
-    strategy.extend(regret.iter().map(|&r| r.max(0) as f32));
+    strategy.extend(regret.iter().map(|&r| max(r.into(), 0.0)));
 
     let row_size = strategy.len() / num_actions;
     let mut denom = Vec::with_capacity_in(row_size, StackAlloc);
@@ -426,9 +471,9 @@ fn regret_matching_compressed(regret: &[i16], num_actions: usize) -> Vec<f32, St
 /// Computes the strategy by regret-matching algorithm.
 #[cfg(not(feature = "custom-alloc"))]
 #[inline]
-fn regret_matching_compressed(regret: &[i16], num_actions: usize) -> Vec<f32> {
+fn regret_matching_compressed<T: Quantized>(regret: &[T], num_actions: usize) -> Vec<f32> {
     let mut strategy = Vec::with_capacity(regret.len());
-    strategy.extend(regret.iter().map(|&r| r.max(0) as f32));
+    strategy.extend(regret.iter().map(|&r| max(r.into(), 0.0)));
 
     let row_size = strategy.len() / num_actions;
     let mut denom = Vec::with_capacity(row_size);
@@ -442,3 +487,253 @@ fn regret_matching_compressed(regret: &[i16], num_actions: usize) -> Vec<f32> {
 
     strategy
 }
+
+/// Updates the compressed cumulative strategy, and returns the new scale.
+#[inline]
+fn update_strategy_compressed<T: Quantized>(
+    cum_strategy: &mut [T],
+    scale: f32,
+    strategy: &mut [f32],
+    gamma: f32,
+    locking: &[f32],
+) -> f32 {
+    let decoder = gamma * scale / T::MAX;
+
+    strategy.iter_mut().zip(&*cum_strategy).for_each(|(x, y)| {
+        *x += (*y).into() * decoder;
+    });
+
+    if !locking.is_empty() {
+        strategy.iter_mut().zip(locking).for_each(|(d, s)| {
+            if s.is_sign_positive() {
+                *d = 0.0;
+            }
+        })
+    }
+
+    T::encode_slice(cum_strategy, strategy)
+}
+
+/// Updates the compressed cumulative regrets, and returns the new scale.
+#[inline]
+fn update_regrets_compressed<T: Quantized>(
+    cum_regret: &mut [T],
+    scale: f32,
+    cfv_actions: &mut [f32],
+    result: &[f32],
+    (alpha, beta): (f32, f32),
+    locking: &[f32],
+) -> f32 {
+    let alpha_decoder = alpha * scale / T::MAX;
+    let beta_decoder = beta * scale / T::MAX;
+
+    cfv_actions.iter_mut().zip(&*cum_regret).for_each(|(x, y)| {
+        let y = (*y).into();
+        *x += y * if y >= 0.0 {
+            alpha_decoder
+        } else {
+            beta_decoder
+        };
+    });
+
+    cfv_actions.chunks_exact_mut(result.len()).for_each(|row| {
+        sub_slice(row, result);
+    });
+
+    if !locking.is_empty() {
+        cfv_actions.iter_mut().zip(locking).for_each(|(d, s)| {
+            if s.is_sign_positive() {
+                *d = 0.0;
+            }
+        })
+    }
+
+    T::encode_slice(cum_regret, cfv_actions)
+}
+
+/// Decodes the cumulative regrets of the given node.
+#[inline]
+pub(crate) fn decoded_regrets<T: Game>(game: &T, node: &T::Node) -> Vec<f32> {
+    match game.storage_precision(node).regrets {
+        Precision::Float32 => node.regrets().to_vec(),
+        Precision::Int16 => decode_slice(node.regrets_compressed(), node.regret_scale()),
+        Precision::Int8 => decode_slice(node.regrets_i8(), node.regret_scale()),
+    }
+}
+
+/// Decodes the (unnormalized) cumulative strategy of the given node.
+#[inline]
+pub(crate) fn decoded_cum_strategy<T: Game>(game: &T, node: &T::Node) -> Vec<f32> {
+    match game.storage_precision(node).strategy {
+        Precision::Float32 => node.strategy().to_vec(),
+        Precision::Int16 => decode_slice(node.strategy_compressed(), node.strategy_scale()),
+        Precision::Int8 => decode_slice(node.strategy_u8(), node.strategy_scale()),
+    }
+}
+
+/// Encodes the given cumulative regrets into the storage of the given node.
+#[inline]
+pub(crate) fn encode_regrets<T: Game>(game: &T, node: &mut T::Node, regrets: &[f32]) {
+    match game.storage_precision(node).regrets {
+        Precision::Float32 => node.regrets_mut().copy_from_slice(regrets),
+        Precision::Int16 => {
+            let scale = encode_signed_slice(node.regrets_compressed_mut(), regrets);
+            node.set_regret_scale(scale);
+        }
+        Precision::Int8 => {
+            let scale = encode_signed_slice_i8(node.regrets_i8_mut(), regrets);
+            node.set_regret_scale(scale);
+        }
+    }
+}
+
+/// Encodes the given (unnormalized) cumulative strategy into the storage of the given node.
+#[inline]
+pub(crate) fn encode_cum_strategy<T: Game>(game: &T, node: &mut T::Node, strategy: &[f32]) {
+    match game.storage_precision(node).strategy {
+        Precision::Float32 => node.strategy_mut().copy_from_slice(strategy),
+        Precision::Int16 => {
+            let scale = encode_unsigned_slice(node.strategy_compressed_mut(), strategy);
+            node.set_strategy_scale(scale);
+        }
+        Precision::Int8 => {
+            let scale = encode_unsigned_slice_u8(node.strategy_u8_mut(), strategy);
+            node.set_strategy_scale(scale);
+        }
+    }
+}
+
+/// Computes the current strategy of the given node, i.e., the strategy that the next iteration
+/// plays, in the same way as [`solve_recursive`].
+pub(crate) fn current_strategy<T: Game>(game: &T, node: &T::Node) -> Vec<f32> {
+    let regrets = decoded_regrets(game, node);
+    let mut strategy = regret_matching(&regrets, node.num_actions()).to_vec();
+
+    let locking = game.locking_strategy(node);
+    apply_locking_strategy(&mut strategy, locking);
+
+    if let Some(constraint) = game.aggregate_constraint(node) {
+        apply_aggregate_constraint(&mut strategy, &regrets, locking, constraint);
+    }
+
+    strategy
+}
+
+/// Adjusts the strategy so that the aggregate-frequency constraint is satisfied.
+///
+/// This computes a constrained best response in the sense of regret matching: the strategy of the
+/// unlocked hands is recomputed from the regrets, where the regrets of the actions in each group are
+/// biased by `lambda[group]`, and `lambda` is searched by coordinate-wise bisection. Therefore, the
+/// hands with larger regrets of a group are preferentially assigned to that group.
+fn apply_aggregate_constraint(
+    strategy: &mut [f32],
+    regrets: &[f32],
+    locking: &[f32],
+    constraint: &AggregateConstraint,
+) {
+    const EPS: f32 = 1e-4;
+    const NUM_ROUNDS: usize = 16;
+    const NUM_BISECTIONS: usize = 24;
+
+    let weights = &constraint.weights;
+    let bounds = &constraint.bounds;
+    let num_hands = weights.len();
+    let num_actions = strategy.len() / num_hands;
+
+    let aggregate = |strategy: &[f32], bound: &AggregateBound| {
+        let row_sum = |&action: &usize| {
+            let row = row(strategy, action, num_hands);
+            row.iter().zip(weights).map(|(s, w)| s * w).sum::<f32>()
+        };
+        bound.actions.iter().map(row_sum).sum::<f32>()
+    };
+
+    let is_feasible = |strategy: &[f32]| {
+        bounds.iter().all(|bound| {
+            let freq = aggregate(strategy, bound);
+            bound.lower - EPS <= freq && freq <= bound.upper + EPS
+        })
+    };
+
+    if is_feasible(strategy) {
+        return;
+    }
+
+    let is_locked = (0..num_hands)
+        .map(|hand| {
+            !locking.is_empty()
+                && (0..num_actions).any(|a| locking[a * num_hands + hand].is_sign_positive())
+        })
+        .collect::<Vec<_>>();
+
+    // recomputes the strategy of the unlocked hands with the biased regrets
+    let biased = |strategy: &mut [f32], lambda: &[f32]| {
+        let mut bias = vec![0.0; num_actions];
+        for (bound, &l) in bounds.iter().zip(lambda) {
+            bound.actions.iter().for_each(|&action| bias[action] += l);
+        }
+
+        for hand in (0..num_hands).filter(|&h| !is_locked[h]) {
+            let mut denom = 0.0;
+            let mut best = 0;
+            for action in 0..num_actions {
+                let index = action * num_hands + hand;
+                let r = regrets[index] + bias[action];
+                if r > regrets[best * num_hands + hand] + bias[best] {
+                    best = action;
+                }
+                strategy[index] = max(r, 0.0);
+                denom += strategy[index];
+            }
+            for action in 0..num_actions {
+                let index = action * num_hands + hand;
+                strategy[index] = match denom > 0.0 {
+                    true => strategy[index] / denom,
+                    false => (action == best) as u8 as f32,
+                };
+            }
+        }
+    };
+
+    let max_regret = regrets.iter().fold(0.0f32, |m, r| m.max(r.abs()));
+    let width = 4.0 * max_regret + 1.0;
+    let mut lambda = vec![0.0; bounds.len()];
+
+    for _ in 0..NUM_ROUNDS {
+        biased(strategy, &lambda);
+        if is_feasible(strategy) {
+            return;
+        }
+
+        for (group, bound) in bounds.iter().enumerate() {
+            let (lower, upper) = (bound.lower, bound.upper);
+            let freq = aggregate(strategy, bound);
+            if lower <= freq && freq <= upper {
+                continue;
+            }
+
+            // the aggregate frequency is monotonically non-decreasing with respect to `lambda`
+            let increase = freq < lower;
+            let (mut lo, mut hi) = match increase {
+                true => (lambda[group], lambda[group] + width),
+                false => (lambda[group] - width, lambda[group]),
+            };
+
+            for _ in 0..NUM_BISECTIONS {
+                lambda[group] = 0.5 * (lo + hi);
+                biased(strategy, &lambda);
+                let freq = aggregate(strategy, bound);
+                match (increase, freq < lower, freq > upper) {
+                    (true, true, _) => lo = lambda[group],
+                    (true, false, _) => hi = lambda[group],
+                    (false, _, true) => hi = lambda[group],
+                    (false, _, false) => lo = lambda[group],
+                }
+            }
+
+            lambda[group] = if increase { hi } else { lo };
+        }
+    }
+
+    biased(strategy, &lambda);
+}
@@ -0,0 +1,300 @@
+use crate::action_tree::*;
+use crate::game::PostFlopGame;
+
+/// A struct representing the differences between two action trees.
+///
+/// The trees are compared node by node, following the same line in both trees. At the chance
+/// nodes, the default subtrees are compared with each other, and so are the subtrees of the same
+/// board bet size rule (see [`TreeConfig::board_bet_sizes`]). The lines in the default subtrees do
+/// not contain chance actions as in [`ActionTree::play`], whereas the lines in the subtree of the
+/// `i`-th rule (1-based) contain `Action::Chance(i)` at the chance node. A rule subtree that exists
+/// only in one tree is reported as an added or removed line ending with the chance action.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ActionTreeDiff {
+    /// Lines that exist only in the new tree. Only the shortest such lines are listed, i.e., the
+    /// lines after them are not included.
+    pub added_lines: Vec<Vec<Action>>,
+
+    /// Lines that exist only in the old tree. Only the shortest such lines are listed.
+    pub removed_lines: Vec<Vec<Action>>,
+
+    /// Nodes that exist in both trees but have different available actions (e.g., changed bet
+    /// sizes).
+    pub changed_nodes: Vec<ChangedNode>,
+
+    /// The number of action nodes of [flop, turn, river] in the old tree.
+    pub old_num_action_nodes: [u64; 3],
+
+    /// The number of action nodes of [flop, turn, river] in the new tree.
+    pub new_num_action_nodes: [u64; 3],
+}
+
+/// A node whose available actions differ between two action trees.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChangedNode {
+    /// The line leading to the node.
+    pub line: Vec<Action>,
+
+    /// The available actions in the old tree.
+    pub old_actions: Vec<Action>,
+
+    /// The available actions in the new tree.
+    pub new_actions: Vec<Action>,
+}
+
+impl ActionTreeDiff {
+    /// Returns whether the two trees have the same structure.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.changed_nodes.is_empty()
+    }
+
+    /// Returns the difference of the number of action nodes of [flop, turn, river] (new - old).
+    #[inline]
+    pub fn num_action_nodes_delta(&self) -> [i64; 3] {
+        let old = &self.old_num_action_nodes;
+        let new = &self.new_num_action_nodes;
+        [
+            new[0] as i64 - old[0] as i64,
+            new[1] as i64 - old[1] as i64,
+            new[2] as i64 - old[2] as i64,
+        ]
+    }
+
+    /// Compares two action trees rooted at `old` and `new`.
+    fn new(old: &ActionTreeNode, new: &ActionTreeNode) -> Self {
+        let mut ret = Self {
+            old_num_action_nodes: count_num_action_nodes(old),
+            new_num_action_nodes: count_num_action_nodes(new),
+            ..Default::default()
+        };
+        ret.diff_recursive(old, new, &mut Vec::new());
+        ret
+    }
+
+    fn diff_recursive(
+        &mut self,
+        old: &ActionTreeNode,
+        new: &ActionTreeNode,
+        line: &mut Vec<Action>,
+    ) {
+        let is_chance = old.is_chance() && new.is_chance();
+        let (old_actions, new_actions) = if is_chance {
+            (old.actions.as_slice(), new.actions.as_slice())
+        } else {
+            (player_actions(old), player_actions(new))
+        };
+
+        if old_actions != new_actions {
+            let lines_only_in = |actions: &[Action], others: &[Action]| {
+                actions
+                    .iter()
+                    .filter(|action| !others.contains(action))
+                    .map(|&action| [line.as_slice(), &[action]].concat())
+                    .collect::<Vec<_>>()
+            };
+
+            self.added_lines
+                .extend(lines_only_in(new_actions, old_actions));
+            self.removed_lines
+                .extend(lines_only_in(old_actions, new_actions));
+            self.changed_nodes.push(ChangedNode {
+                line: line.clone(),
+                old_actions: old_actions.to_vec(),
+                new_actions: new_actions.to_vec(),
+            });
+        }
+
+        for (&action, old_child) in old_actions.iter().zip(old.children.iter()) {
+            if let Some(index) = new_actions.iter().position(|&a| a == action) {
+                // the default subtree is followed without a chance action
+                let is_default = action == Action::Chance(0);
+                if !is_default {
+                    line.push(action);
+                }
+                self.diff_recursive(&old_child.lock(), &new.children[index].lock(), line);
+                if !is_default {
+                    line.pop();
+                }
+            }
+        }
+    }
+}
+
+impl ActionTree {
+    /// Compares the action tree (old) with `other` (new).
+    ///
+    /// # Examples
+    /// ```
+    /// use postflop_solver::*;
+    ///
+    /// let config = TreeConfig {
+    ///     initial_state: BoardState::River,
+    ///     starting_pot: 100,
+    ///     effective_stack: 300,
+    ///     river_bet_sizes: [("50%, 100%", "").try_into().unwrap(), Default::default()],
+    ///     ..Default::default()
+    /// };
+    /// let old = ActionTree::new(config.clone()).unwrap();
+    ///
+    /// let new = ActionTree::new(TreeConfig {
+    ///     river_bet_sizes: [("50%, 75%", "").try_into().unwrap(), Default::default()],
+    ///     ..config
+    /// })
+    /// .unwrap();
+    ///
+    /// let diff = old.diff(&new);
+    /// assert_eq!(diff.added_lines, vec![vec![Action::Bet(75)]]);
+    /// assert_eq!(diff.removed_lines, vec![vec![Action::Bet(100)]]);
+    /// assert_eq!(diff.changed_nodes.len(), 1);
+    /// assert_eq!(diff.num_action_nodes_delta(), [0, 0, 0]);
+    /// ```
+    #[inline]
+    pub fn diff(&self, other: &Self) -> ActionTreeDiff {
+        ActionTreeDiff::new(&self.root(), &other.root())
+    }
+}
+
+impl PostFlopGame {
+    /// Compares the action tree of the game (old) with that of `other` (new).
+    ///
+    /// See [`ActionTreeDiff`] for how the subtrees of the board bet size rules are compared.
+    #[inline]
+    pub fn diff_action_tree(&self, other: &Self) -> ActionTreeDiff {
+        ActionTreeDiff::new(&self.action_root(), &other.action_root())
+    }
+}
+
+/// Returns the available actions of a player node, or an empty slice for other nodes.
+#[inline]
+fn player_actions(node: &ActionTreeNode) -> &[Action] {
+    if node.is_terminal() || node.is_chance() {
+        &[]
+    } else {
+        &node.actions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bet_size::*;
+    use crate::card::BoardTexture;
+
+    #[test]
+    fn test_diff_identical() {
+        let config = TreeConfig {
+            initial_state: BoardState::Turn,
+            starting_pot: 100,
+            effective_stack: 500,
+            turn_bet_sizes: [("50%", "2x").try_into().unwrap(), Default::default()],
+            river_bet_sizes: [("50%", "2x").try_into().unwrap(), Default::default()],
+            ..Default::default()
+        };
+
+        let tree = ActionTree::new(config.clone()).unwrap();
+        let diff = tree.diff(&ActionTree::new(config).unwrap());
+        assert!(diff.is_empty());
+        assert!(diff.added_lines.is_empty() && diff.removed_lines.is_empty());
+        assert_eq!(diff.old_num_action_nodes, diff.new_num_action_nodes);
+        assert_eq!(diff.num_action_nodes_delta(), [0, 0, 0]);
+    }
+
+    #[test]
+    fn test_diff_changes() {
+        let sizes = BetSizeOptions::try_from(("50%", "")).unwrap();
+        let config = TreeConfig {
+            initial_state: BoardState::Turn,
+            starting_pot: 100,
+            effective_stack: 500,
+            turn_bet_sizes: [sizes.clone(), sizes.clone()],
+            river_bet_sizes: [sizes.clone(), sizes],
+            ..Default::default()
+        };
+
+        let old = ActionTree::new(config.clone()).unwrap();
+        let mut new = ActionTree::new(TreeConfig {
+            turn_bet_sizes: [
+                ("50%, a", "").try_into().unwrap(),
+                config.turn_bet_sizes[1].clone(),
+            ],
+            ..config
+        })
+        .unwrap();
+        new.remove_line(&[Action::Check, Action::Check]).unwrap();
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.added_lines, vec![vec![Action::AllIn(500)]]);
+        assert_eq!(diff.removed_lines, vec![vec![Action::Check, Action::Check]]);
+
+        let root = &diff.changed_nodes[0];
+        assert!(root.line.is_empty());
+        assert_eq!(root.old_actions, [Action::Check, Action::Bet(50)]);
+        assert_eq!(
+            root.new_actions,
+            [Action::Check, Action::Bet(50), Action::AllIn(500)]
+        );
+
+        // the nodes after the removed line and the added all-in lines are not compared
+        assert!(diff
+            .changed_nodes
+            .iter()
+            .all(|node| !node.line.starts_with(&[Action::Check, Action::Check])));
+
+        let delta = diff.num_action_nodes_delta();
+        assert!(delta[1] > 0 && delta[2] < 0);
+        assert_eq!(new.diff(&old).num_action_nodes_delta(), delta.map(|d| -d));
+    }
+
+    #[test]
+    fn test_diff_rules() {
+        let config = TreeConfig {
+            initial_state: BoardState::Turn,
+            starting_pot: 100,
+            effective_stack: 500,
+            turn_bet_sizes: [("50%", "").try_into().unwrap(), Default::default()],
+            river_bet_sizes: [("50%", "").try_into().unwrap(), Default::default()],
+            ..Default::default()
+        };
+
+        let rule = |sizes: &str| BoardBetSizes {
+            street: BoardState::River,
+            textures: vec![BoardTexture::Paired],
+            bet_sizes: [(sizes, "").try_into().unwrap(), Default::default()],
+            donk_sizes: None,
+        };
+
+        let old = ActionTree::new(TreeConfig {
+            board_bet_sizes: vec![rule("25%")],
+            ..config.clone()
+        })
+        .unwrap();
+        let new = ActionTree::new(TreeConfig {
+            board_bet_sizes: vec![rule("75%")],
+            ..config.clone()
+        })
+        .unwrap();
+
+        let diff = old.diff(&new);
+        assert!(!diff.is_empty());
+        let line = [Action::Check, Action::Check, Action::Chance(1)];
+        assert!(diff
+            .added_lines
+            .contains(&[&line[..], &[Action::Bet(75)]].concat()));
+        assert!(diff
+            .removed_lines
+            .contains(&[&line[..], &[Action::Bet(25)]].concat()));
+        assert!(diff
+            .changed_nodes
+            .iter()
+            .all(|node| node.line.contains(&line[2])));
+        assert_eq!(diff.num_action_nodes_delta(), [0, 0, 0]);
+
+        // the rule subtrees exist only in the new tree
+        let old = ActionTree::new(config).unwrap();
+        let diff = old.diff(&new);
+        assert!(diff.added_lines.contains(&line.to_vec()));
+        assert!(diff.removed_lines.is_empty());
+        assert!(diff.num_action_nodes_delta()[2] > 0);
+    }
+}
@@ -0,0 +1,451 @@
+use crate::action_tree::*;
+use crate::game::PostFlopGame;
+use std::fmt::Write;
+
+/// A struct representing the statistics of an action tree.
+///
+/// The subtrees of the board bet size rules after the chance nodes are counted in addition to the
+/// default subtrees (see [`TreeConfig::board_bet_sizes`]). The streets are indexed as
+/// [flop, turn, river] and the players as [OOP, IP].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ActionTreeStats {
+    /// The number of player nodes of each street for each player.
+    pub num_player_nodes: [[u64; 3]; 2],
+
+    /// The number of chance nodes of each street (i.e., the street before the card is dealt).
+    pub num_chance_nodes: [u64; 3],
+
+    /// The number of terminal nodes of each street.
+    pub num_terminal_nodes: [u64; 3],
+
+    /// The number of player nodes by depth, i.e., the number of actions from the root.
+    pub depth_distribution: Vec<u64>,
+
+    /// The distinct bet, raise, and all-in amounts of each street in ascending order.
+    pub bet_amounts: [Vec<i32>; 3],
+
+    /// The range (min, max) of the stack-to-pot ratio at the player nodes of each street. See
+    /// [`ActionTree::render`] for the definition. `None` if the street has no player nodes.
+    pub spr_range: [Option<(f64, f64)>; 3],
+}
+
+impl ActionTreeStats {
+    /// Returns the total number of player nodes of each street.
+    #[inline]
+    pub fn num_player_nodes_total(&self) -> [u64; 3] {
+        let [oop, ip] = &self.num_player_nodes;
+        [oop[0] + ip[0], oop[1] + ip[1], oop[2] + ip[2]]
+    }
+
+    /// Returns the number of distinct bet, raise, and all-in amounts of each street.
+    #[inline]
+    pub fn num_distinct_bet_amounts(&self) -> [usize; 3] {
+        [
+            self.bet_amounts[0].len(),
+            self.bet_amounts[1].len(),
+            self.bet_amounts[2].len(),
+        ]
+    }
+
+    /// Returns the maximum depth of the player nodes.
+    #[inline]
+    pub fn max_depth(&self) -> usize {
+        self.depth_distribution.len().saturating_sub(1)
+    }
+
+    /// Computes the statistics of the action tree rooted at `root`.
+    fn new(config: &TreeConfig, root: &ActionTreeNode) -> Self {
+        let mut ret = Self::default();
+        walk_recursive(config, root, NodeInfo::new(config), &mut |node, info| {
+            ret.count_node(node, info);
+        });
+        ret
+    }
+
+    fn count_node(&mut self, node: &ActionTreeNode, info: &NodeInfo) {
+        let street = node.board_state as usize;
+
+        if node.is_terminal() {
+            self.num_terminal_nodes[street] += 1;
+            return;
+        }
+
+        if node.is_chance() {
+            self.num_chance_nodes[street] += 1;
+            return;
+        }
+
+        self.num_player_nodes[node.player as usize][street] += 1;
+
+        if self.depth_distribution.len() <= info.depth {
+            self.depth_distribution.resize(info.depth + 1, 0);
+        }
+        self.depth_distribution[info.depth] += 1;
+
+        for action in &node.actions {
+            if let Action::Bet(amount) | Action::Raise(amount) | Action::AllIn(amount) = *action {
+                if let Err(index) = self.bet_amounts[street].binary_search(&amount) {
+                    self.bet_amounts[street].insert(index, amount);
+                }
+            }
+        }
+
+        let spr = info.spr;
+        self.spr_range[street] = match self.spr_range[street] {
+            Some((min, max)) => Some((min.min(spr), max.max(spr))),
+            None => Some((spr, spr)),
+        };
+    }
+}
+
+/// The information of a node computed while walking the action tree.
+#[derive(Clone, Copy, Default)]
+struct NodeInfo {
+    depth: usize,
+    street_start: i32,
+    committed: [i32; 2],
+    pot: i32,
+    spr: f64,
+}
+
+impl NodeInfo {
+    /// Returns the information of the root node.
+    #[inline]
+    fn new(config: &TreeConfig) -> Self {
+        let mut ret = Self::default();
+        ret.update(config);
+        ret
+    }
+
+    /// Returns the information of the child after `action` is taken by `player`.
+    #[inline]
+    fn create_next(&self, config: &TreeConfig, player: u8, action: Action) -> Self {
+        let mut ret = *self;
+        let player = player as usize;
+
+        match action {
+            Action::Call => ret.committed[player] = ret.committed[player ^ 1],
+            Action::Bet(amount) | Action::Raise(amount) | Action::AllIn(amount) => {
+                ret.committed[player] = ret.street_start + amount;
+            }
+            Action::Chance(_) => ret.street_start = ret.committed[0],
+            _ => {}
+        }
+
+        if !matches!(action, Action::Chance(_)) {
+            ret.depth += 1;
+        }
+
+        ret.update(config);
+        ret
+    }
+
+    /// Updates the pot size and the stack-to-pot ratio.
+    #[inline]
+    fn update(&mut self, config: &TreeConfig) {
+        let max_committed = self.committed[0].max(self.committed[1]);
+        let stack = config.effective_stack - max_committed;
+        self.pot = config.starting_pot + self.committed[0] + self.committed[1];
+        self.spr = stack as f64 / (config.starting_pot + 2 * max_committed) as f64;
+    }
+}
+
+/// Calls `f` for each node of the action tree in depth-first order.
+fn walk_recursive<F: FnMut(&ActionTreeNode, &NodeInfo)>(
+    config: &TreeConfig,
+    node: &ActionTreeNode,
+    info: NodeInfo,
+    f: &mut F,
+) {
+    f(node, &info);
+
+    if node.is_terminal() {
+        // do nothing
+    } else {
+        for (&action, child) in node.actions.iter().zip(node.children.iter()) {
+            let next_info = info.create_next(config, node.player, action);
+            walk_recursive(config, &child.lock(), next_info, f);
+        }
+    }
+}
+
+/// Renders the action tree rooted at `root` into an indented text.
+fn render(config: &TreeConfig, root: &ActionTreeNode, max_depth: Option<usize>) -> String {
+    let mut ret = String::new();
+    render_recursive(
+        config,
+        root,
+        None,
+        None,
+        NodeInfo::new(config),
+        max_depth,
+        &mut ret,
+    );
+    ret
+}
+
+fn render_recursive(
+    config: &TreeConfig,
+    node: &ActionTreeNode,
+    action: Option<Action>,
+    rule: Option<u8>,
+    info: NodeInfo,
+    max_depth: Option<usize>,
+    out: &mut String,
+) {
+    if node.is_chance() {
+        for (&chance, child) in node.actions.iter().zip(node.children.iter()) {
+            let next_info = info.create_next(config, node.player, chance);
+            let rule = match chance {
+                Action::Chance(0) => None,
+                Action::Chance(i) => Some(i),
+                _ => unreachable!(),
+            };
+            let child = &child.lock();
+            render_recursive(config, child, action, rule, next_info, max_depth, out);
+        }
+        return;
+    }
+
+    let street = match node.board_state {
+        BoardState::Flop => "Flop",
+        BoardState::Turn => "Turn",
+        BoardState::River => "River",
+    };
+
+    let indent = "  ".repeat(info.depth);
+    let mut label = match action {
+        Some(action) => format!("{}: ", line_to_string(&[action]).unwrap()),
+        None => String::new(),
+    };
+    if let Some(rule) = rule {
+        label.insert_str(0, &format!("[rule {rule}] "));
+    }
+
+    let description = if node.is_terminal() {
+        format!("Terminal ({street})")
+    } else {
+        let player = ["OOP", "IP"][node.player as usize];
+        format!("{player} ({street})")
+    };
+
+    let is_truncated = !node.is_terminal() && max_depth == Some(info.depth);
+
+    writeln!(
+        out,
+        "{indent}{label}{description} pot={} spr={:.2}{}",
+        info.pot,
+        info.spr,
+        if is_truncated { " ..." } else { "" }
+    )
+    .unwrap();
+
+    if node.is_terminal() || is_truncated {
+        return;
+    }
+
+    for (&action, child) in node.actions.iter().zip(node.children.iter()) {
+        let next_info = info.create_next(config, node.player, action);
+        render_recursive(
+            config,
+            &child.lock(),
+            Some(action),
+            None,
+            next_info,
+            max_depth,
+            out,
+        );
+    }
+}
+
+impl ActionTree {
+    /// Computes the statistics of the action tree.
+    ///
+    /// # Examples
+    /// ```
+    /// use postflop_solver::*;
+    ///
+    /// let config = TreeConfig {
+    ///     initial_state: BoardState::River,
+    ///     starting_pot: 100,
+    ///     effective_stack: 300,
+    ///     river_bet_sizes: [("50%", "").try_into().unwrap(), Default::default()],
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let stats = ActionTree::new(config).unwrap().stats();
+    /// assert_eq!(stats.num_player_nodes, [[0, 0, 1], [0, 0, 2]]);
+    /// assert_eq!(stats.num_terminal_nodes, [0, 0, 3]);
+    /// assert_eq!(stats.depth_distribution, [1, 2]);
+    /// assert_eq!(stats.bet_amounts[2], [50]);
+    /// assert_eq!(stats.spr_range[2], Some((1.25, 3.0)));
+    /// ```
+    #[inline]
+    pub fn stats(&self) -> ActionTreeStats {
+        ActionTreeStats::new(self.config(), &self.root())
+    }
+
+    /// Renders the action tree into an indented text for inspection.
+    ///
+    /// Each line represents a node and is indented by its depth, i.e., the number of actions from
+    /// the root. The line consists of the action leading to the node, the player (or `Terminal`)
+    /// and the street, the pot size, and the stack-to-pot ratio (`spr`). The stack-to-pot ratio is
+    /// computed as if the pending bet (if any) is called. Chance nodes are not shown; the default
+    /// subtree after a chance node is rendered first, followed by the subtrees of the board bet
+    /// size rules, whose first lines are prefixed by `[rule <i>]` (the i-th rule of
+    /// [`TreeConfig::board_bet_sizes`], 1-based).
+    ///
+    /// If `max_depth` is given, the children of the nodes at that depth are omitted, which is
+    /// indicated by `...`.
+    ///
+    /// # Examples
+    /// ```
+    /// use postflop_solver::*;
+    ///
+    /// let config = TreeConfig {
+    ///     initial_state: BoardState::River,
+    ///     starting_pot: 100,
+    ///     effective_stack: 300,
+    ///     river_bet_sizes: [("50%", "").try_into().unwrap(), Default::default()],
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let text = ActionTree::new(config).unwrap().render(Some(1));
+    /// let expected = "\
+    /// OOP (River) pot=100 spr=3.00
+    ///   X: IP (River) pot=100 spr=3.00 ...
+    ///   B50: IP (River) pot=150 spr=1.25 ...
+    /// ";
+    /// assert_eq!(text, expected);
+    /// ```
+    #[inline]
+    pub fn render(&self, max_depth: Option<usize>) -> String {
+        render(self.config(), &self.root(), max_depth)
+    }
+}
+
+impl PostFlopGame {
+    /// Computes the statistics of the action tree of the game. See [`ActionTree::stats`].
+    #[inline]
+    pub fn action_tree_stats(&self) -> ActionTreeStats {
+        ActionTreeStats::new(self.tree_config(), &self.action_root())
+    }
+
+    /// Renders the action tree of the game into an indented text. See [`ActionTree::render`].
+    #[inline]
+    pub fn render_action_tree(&self, max_depth: Option<usize>) -> String {
+        render(self.tree_config(), &self.action_root(), max_depth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bet_size::*;
+    use crate::card::BoardTexture;
+
+    #[test]
+    fn test_stats() {
+        let sizes = BetSizeOptions::try_from(("50%", "2x")).unwrap();
+        let config = TreeConfig {
+            initial_state: BoardState::Turn,
+            starting_pot: 100,
+            effective_stack: 500,
+            turn_bet_sizes: [sizes.clone(), sizes.clone()],
+            river_bet_sizes: [sizes.clone(), sizes],
+            ..Default::default()
+        };
+
+        let action_tree = ActionTree::new(config).unwrap();
+        let stats = action_tree.stats();
+
+        let num_action_nodes = count_num_action_nodes(&action_tree.root());
+        let num_player_nodes = stats.num_player_nodes_total();
+        for (street, &count) in num_action_nodes.iter().enumerate() {
+            let total = num_player_nodes[street]
+                + stats.num_chance_nodes[street]
+                + stats.num_terminal_nodes[street];
+            assert_eq!(total, count);
+        }
+
+        let total = stats.num_player_nodes_total();
+        assert_eq!(total[0], 0);
+        assert_eq!(
+            stats.depth_distribution.iter().sum::<u64>(),
+            total[1] + total[2]
+        );
+        assert_eq!(stats.depth_distribution[..2], [1, 2]);
+        assert_eq!(stats.num_chance_nodes, [0, 11, 0]);
+        assert_eq!(stats.bet_amounts[1], [50, 100, 200, 400, 500]);
+        assert_eq!(stats.num_distinct_bet_amounts()[1], 5);
+
+        // the initial SPR is the maximum, and all-in nodes have SPR 0
+        assert_eq!(stats.spr_range[1].unwrap().1, 5.0);
+        assert_eq!(stats.spr_range[2].unwrap().0, 0.0);
+    }
+
+    #[test]
+    fn test_render() {
+        let config = TreeConfig {
+            initial_state: BoardState::Turn,
+            starting_pot: 100,
+            effective_stack: 100,
+            turn_bet_sizes: [("a", "").try_into().unwrap(), Default::default()],
+            ..Default::default()
+        };
+
+        let action_tree = ActionTree::new(config).unwrap();
+        let text = action_tree.render(None);
+        let expected = "\
+OOP (Turn) pot=100 spr=1.00
+  X: IP (Turn) pot=100 spr=1.00
+    X: OOP (River) pot=100 spr=1.00
+      X: IP (River) pot=100 spr=1.00
+        X: Terminal (River) pot=100 spr=1.00
+  A100: IP (Turn) pot=200 spr=0.00
+    F: Terminal (Turn) pot=200 spr=0.00
+    C: Terminal (River) pot=300 spr=0.00
+";
+        assert_eq!(text, expected);
+
+        let text = action_tree.render(Some(0));
+        assert_eq!(text, "OOP (Turn) pot=100 spr=1.00 ...\n");
+    }
+
+    #[test]
+    fn test_rule_subtrees() {
+        let config = TreeConfig {
+            initial_state: BoardState::Turn,
+            starting_pot: 100,
+            effective_stack: 100,
+            board_bet_sizes: vec![BoardBetSizes {
+                street: BoardState::River,
+                textures: vec![BoardTexture::Paired],
+                bet_sizes: [("50%", "").try_into().unwrap(), Default::default()],
+                donk_sizes: None,
+            }],
+            ..Default::default()
+        };
+
+        let action_tree = ActionTree::new(config).unwrap();
+        let stats = action_tree.stats();
+        assert_eq!(stats.num_player_nodes, [[0, 1, 2], [0, 1, 3]]);
+        assert_eq!(stats.bet_amounts[2], [50]);
+
+        let text = action_tree.render(None);
+        let expected = "\
+OOP (Turn) pot=100 spr=1.00
+  X: IP (Turn) pot=100 spr=1.00
+    X: OOP (River) pot=100 spr=1.00
+      X: IP (River) pot=100 spr=1.00
+        X: Terminal (River) pot=100 spr=1.00
+    [rule 1] X: OOP (River) pot=100 spr=1.00
+      X: IP (River) pot=100 spr=1.00
+        X: Terminal (River) pot=100 spr=1.00
+      B50: IP (River) pot=150 spr=0.25
+        F: Terminal (River) pot=150 spr=0.25
+        C: Terminal (River) pot=200 spr=0.25
+";
+        assert_eq!(text, expected);
+    }
+}
@@ -0,0 +1,716 @@
+use crate::action_tree::*;
+use crate::bet_size::*;
+use crate::card::BoardTexture;
+use std::fmt::Write;
+
+/// The names of the players used in the keys of the text format.
+const PLAYER_NAMES: [&str; 2] = ["oop", "ip"];
+
+/// The names of the streets used in the text format.
+const STREET_NAMES: [&str; 3] = ["flop", "turn", "river"];
+
+impl ActionTree {
+    /// Exports the action tree into a human-editable text format, which can be imported by
+    /// [`from_text`].
+    ///
+    /// The text consists of lines of the form `<key> = <value>`, and empty lines and the text after
+    /// `#` are ignored:
+    ///
+    /// - The fields of [`TreeConfig`] (e.g., `starting_pot = 200`). The bet sizes are written as
+    ///   `<street>_bet_sizes.<player> = <bet sizes> ; <raise sizes>` in the syntax of
+    ///   [`BetSizeOptions`], where `<player>` is `oop` or `ip`. `depth_limit` is written as
+    ///   `street <street>` or `actions <number>`, and each board bet size rule is written as
+    ///   `board_bet_sizes = <street> ; <textures> ; <oop bet> ; <oop raise> ; <ip bet> ;
+    ///   <ip raise> [; <donk sizes>]`, where `<textures>` is `*` for any board. `min_raise` is
+    ///   written as `full` or `ratio <ratio>`, and `incomplete_raise` as `allow` or `disallow`. The
+    ///   fields set to `None` are omitted.
+    /// - `add_line = <line>` and `remove_line = <line>` for each of the [`added_lines`] and
+    ///   [`removed_lines`], in the format of [`line_to_string`].
+    /// - If `explicit` is `true`, `node = <line> : <actions>` for each player node of the tree in
+    ///   depth-first order, where `<actions>` are the available actions of the node. The nodes in
+    ///   the subtrees of board bet size rules are written as `node = [rule <i>, ...] <line> :
+    ///   <actions>`, where the numbers are the subtrees taken at the chance nodes on the line: `0`
+    ///   for the subtree without rules (as seen by [`play`]) and `i` for the `i`-th rule of
+    ///   [`TreeConfig::board_bet_sizes`] (1-based).
+    ///
+    /// # Examples
+    /// ```
+    /// use postflop_solver::*;
+    ///
+    /// let tree_config = TreeConfig {
+    ///     initial_state: BoardState::River,
+    ///     starting_pot: 100,
+    ///     effective_stack: 300,
+    ///     river_bet_sizes: [("50%", "2x").try_into().unwrap(), ("50%", "2x").try_into().unwrap()],
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let mut action_tree = ActionTree::new(tree_config).unwrap();
+    /// action_tree.add_line(&line_from_str("B100").unwrap()).unwrap();
+    ///
+    /// let text = action_tree.to_text(false);
+    /// assert!(text.contains("river_bet_sizes.oop = 50% ; 2x"));
+    /// assert!(text.contains("add_line = B100"));
+    ///
+    /// let imported = ActionTree::from_text(&text).unwrap();
+    /// assert_eq!(imported.added_lines(), action_tree.added_lines());
+    /// assert_eq!(imported.to_text(true), action_tree.to_text(true));
+    /// ```
+    ///
+    /// [`from_text`]: #method.from_text
+    /// [`added_lines`]: #method.added_lines
+    /// [`removed_lines`]: #method.removed_lines
+    /// [`play`]: #method.play
+    pub fn to_text(&self, explicit: bool) -> String {
+        let config = self.config();
+        let mut ret = String::new();
+
+        ret.push_str("# action tree\n");
+        let mut push = |key: &str, value: String| {
+            // empty fields leave redundant spaces
+            let value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+            writeln!(ret, "{key} = {value}").unwrap();
+        };
+
+        push(
+            "initial_state",
+            STREET_NAMES[config.initial_state as usize].to_string(),
+        );
+        push("starting_pot", config.starting_pot.to_string());
+        push("effective_stack", config.effective_stack.to_string());
+        push("big_blind", config.big_blind.to_string());
+        push("rake_rate", config.rake_rate.to_string());
+        push("rake_cap", config.rake_cap.to_string());
+
+        let street_options = [
+            &config.flop_bet_sizes,
+            &config.turn_bet_sizes,
+            &config.river_bet_sizes,
+        ];
+
+        for (street, options) in street_options.iter().enumerate() {
+            for player in 0..2 {
+                let key = format!(
+                    "{}_bet_sizes.{}",
+                    STREET_NAMES[street], PLAYER_NAMES[player]
+                );
+                push(&key, bet_size_options_to_string(&options[player]));
+            }
+        }
+
+        let donk_options = [&config.turn_donk_sizes, &config.river_donk_sizes];
+        for (street, donk) in donk_options.iter().enumerate() {
+            if let Some(donk) = donk {
+                let key = format!("{}_donk_sizes", STREET_NAMES[street + 1]);
+                push(&key, bet_sizes_to_string(&donk.donk));
+            }
+        }
+
+        push(
+            "add_allin_threshold",
+            config.add_allin_threshold.to_string(),
+        );
+        push(
+            "force_allin_threshold",
+            config.force_allin_threshold.to_string(),
+        );
+        push("merging_threshold", config.merging_threshold.to_string());
+
+        match config.depth_limit {
+            Some(DepthLimit::Street(street)) => {
+                push(
+                    "depth_limit",
+                    format!("street {}", STREET_NAMES[street as usize]),
+                );
+            }
+            Some(DepthLimit::Actions(depth)) => {
+                push("depth_limit", format!("actions {depth}"));
+            }
+            None => {}
+        }
+
+        for rule in &config.board_bet_sizes {
+            push("board_bet_sizes", board_bet_sizes_to_string(rule));
+        }
+
+        if let Some(max_bets) = config.max_bets_per_street {
+            push("max_bets_per_street", max_bets.to_string());
+        }
+
+        match config.min_raise {
+            MinRaise::FullRaise => push("min_raise", "full".to_string()),
+            MinRaise::Ratio(ratio) => push("min_raise", format!("ratio {ratio}")),
+        }
+
+        match config.incomplete_raise {
+            IncompleteRaise::Allow => push("incomplete_raise", "allow".to_string()),
+            IncompleteRaise::Disallow => push("incomplete_raise", "disallow".to_string()),
+        }
+
+        for line in self.added_lines() {
+            push("add_line", line_to_string(line).unwrap());
+        }
+
+        for line in self.removed_lines() {
+            push("remove_line", line_to_string(line).unwrap());
+        }
+
+        if explicit {
+            let (mut rules, mut line) = (Vec::new(), Vec::new());
+            let mut f = |rules: &[u8], line: &[Action], actions: &[Action]| {
+                let prefix = match rules.iter().all(|&rule| rule == 0) {
+                    true => String::new(),
+                    false => format!("[rule {}] ", rules_to_string(rules)),
+                };
+                let (line, actions) = (line_to_string(line), line_to_string(actions));
+                push(
+                    "node",
+                    format!("{prefix}{} : {}", line.unwrap(), actions.unwrap()),
+                );
+            };
+            Self::explicit_nodes_recursive(&self.root(), &mut rules, &mut line, &mut f);
+        }
+
+        ret
+    }
+
+    /// Imports an action tree from the text format exported by [`to_text`].
+    ///
+    /// The tree is built from the configuration, and then the added and removed lines are
+    /// applied. If the text lists the nodes explicitly, the actions of each listed node are
+    /// replaced with the listed ones by adding and removing lines, so that the resulting tree
+    /// records the differences from the configuration as its added and removed lines. The nodes
+    /// that are not listed keep their actions.
+    ///
+    /// Since the added and removed lines apply to all subtrees of board bet size rules, the nodes
+    /// listed with `[rule ...]` cannot be edited individually: they are compared with the
+    /// resulting tree, and `Err` is returned if their actions differ.
+    ///
+    /// [`to_text`]: #method.to_text
+    pub fn from_text(text: &str) -> Result<Self, String> {
+        let mut config = TreeConfig::default();
+        let mut added_lines = Vec::new();
+        let mut removed_lines = Vec::new();
+        let mut nodes = Vec::new();
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            parse_entry(
+                line,
+                &mut config,
+                &mut added_lines,
+                &mut removed_lines,
+                &mut nodes,
+            )
+            .map_err(|e| format!("Line {}: {e}", i + 1))?;
+        }
+
+        // the nodes in the subtrees of board bet size rules are only verified
+        let (nodes, rule_nodes): (Vec<_>, Vec<_>) = nodes
+            .into_iter()
+            .partition(|(rules, _, _)| rules.iter().all(|&rule| rule == 0));
+
+        let mut ret = Self::new(config)?;
+
+        // removed lines may be below added lines, but not vice versa
+        for line in &added_lines {
+            ret.add_line(line)?;
+        }
+
+        for line in &removed_lines {
+            ret.remove_line(line)?;
+        }
+
+        for (_, line, actions) in &nodes {
+            ret.apply_history(line)?;
+            let current_actions = ret.available_actions().to_vec();
+
+            let mut action_line = line.clone();
+            for &action in &current_actions {
+                if !actions.contains(&action) {
+                    action_line.push(action);
+                    ret.remove_line(&action_line)?;
+                    action_line.pop();
+                }
+            }
+
+            for &action in actions {
+                if !current_actions.contains(&action) {
+                    action_line.push(action);
+                    ret.add_line(&action_line)?;
+                    action_line.pop();
+                }
+            }
+        }
+
+        for (rules, line, actions) in &rule_nodes {
+            let current_actions = Self::rule_node_actions(&ret.root(), rules, line)?;
+            if current_actions != *actions {
+                return Err(format!(
+                    "Nodes of board bet size rules cannot be edited: [rule {}] {}",
+                    rules_to_string(rules),
+                    line_to_string(line)?
+                ));
+            }
+        }
+
+        ret.back_to_root();
+        Ok(ret)
+    }
+
+    /// Calls `f` with the subtrees taken at the chance nodes, the line, and the actions of each
+    /// player node in depth-first order.
+    fn explicit_nodes_recursive<F: FnMut(&[u8], &[Action], &[Action])>(
+        node: &ActionTreeNode,
+        rules: &mut Vec<u8>,
+        line: &mut Vec<Action>,
+        f: &mut F,
+    ) {
+        if node.is_terminal() {
+            // do nothing
+        } else if node.is_chance() {
+            for (&action, child) in node.actions.iter().zip(node.children.iter()) {
+                let Action::Chance(rule) = action else {
+                    unreachable!()
+                };
+                rules.push(rule);
+                Self::explicit_nodes_recursive(&child.lock(), rules, line, f);
+                rules.pop();
+            }
+        } else {
+            f(rules, line, &node.actions);
+            for (&action, child) in node.actions.iter().zip(node.children.iter()) {
+                line.push(action);
+                Self::explicit_nodes_recursive(&child.lock(), rules, line, f);
+                line.pop();
+            }
+        }
+    }
+
+    /// Returns the actions of the player node reached by `line`, taking the subtrees `rules` at
+    /// the chance nodes.
+    fn rule_node_actions(
+        node: &ActionTreeNode,
+        rules: &[u8],
+        line: &[Action],
+    ) -> Result<Vec<Action>, String> {
+        let not_found = || {
+            format!(
+                "Node not found: [rule {}] {}",
+                rules_to_string(rules),
+                line_to_string(line).unwrap_or_default()
+            )
+        };
+
+        if node.is_terminal() {
+            Err(not_found())
+        } else if node.is_chance() {
+            let (&rule, rest) = rules.split_first().ok_or_else(not_found)?;
+            let index = node.actions.iter().position(|&a| a == Action::Chance(rule));
+            let child = &node.children[index.ok_or_else(not_found)?];
+            Self::rule_node_actions(&child.lock(), rest, line)
+        } else if let Some((&action, rest)) = line.split_first() {
+            let index = node
+                .actions
+                .binary_search(&action)
+                .map_err(|_| not_found())?;
+            Self::rule_node_actions(&node.children[index].lock(), rules, rest)
+        } else if rules.is_empty() {
+            Ok(node.actions.clone())
+        } else {
+            Err(not_found())
+        }
+    }
+}
+
+/// Parses an entry of the text format.
+fn parse_entry(
+    entry: &str,
+    config: &mut TreeConfig,
+    added_lines: &mut Vec<Vec<Action>>,
+    removed_lines: &mut Vec<Vec<Action>>,
+    nodes: &mut Vec<(Vec<u8>, Vec<Action>, Vec<Action>)>,
+) -> Result<(), String> {
+    let Some((key, value)) = entry.split_once('=') else {
+        return Err(format!("Expected '<key> = <value>': {entry}"));
+    };
+
+    let (key, value) = (key.trim(), value.trim());
+
+    let parse_float = |value: &str| {
+        value
+            .parse::<f64>()
+            .map_err(|_| format!("Invalid number: {value}"))
+    };
+
+    let parse_int = |value: &str| {
+        value
+            .parse::<i32>()
+            .map_err(|_| format!("Invalid integer: {value}"))
+    };
+
+    if let Some((street, player)) = key
+        .strip_suffix("_bet_sizes.oop")
+        .map(|street| (street, 0))
+        .or_else(|| key.strip_suffix("_bet_sizes.ip").map(|street| (street, 1)))
+    {
+        let options = match parse_street(street)? {
+            BoardState::Flop => &mut config.flop_bet_sizes,
+            BoardState::Turn => &mut config.turn_bet_sizes,
+            BoardState::River => &mut config.river_bet_sizes,
+        };
+        options[player] = bet_size_options_from_str(value)?;
+        return Ok(());
+    }
+
+    match key {
+        "initial_state" => config.initial_state = parse_street(value)?,
+        "starting_pot" => config.starting_pot = parse_int(value)?,
+        "effective_stack" => config.effective_stack = parse_int(value)?,
+        "big_blind" => config.big_blind = parse_int(value)?,
+        "rake_rate" => config.rake_rate = parse_float(value)?,
+        "rake_cap" => config.rake_cap = parse_float(value)?,
+        "turn_donk_sizes" => config.turn_donk_sizes = Some(value.try_into()?),
+        "river_donk_sizes" => config.river_donk_sizes = Some(value.try_into()?),
+        "add_allin_threshold" => config.add_allin_threshold = parse_float(value)?,
+        "force_allin_threshold" => config.force_allin_threshold = parse_float(value)?,
+        "merging_threshold" => config.merging_threshold = parse_float(value)?,
+        "depth_limit" => {
+            config.depth_limit = Some(match value.split_once(' ') {
+                Some(("street", street)) => DepthLimit::Street(parse_street(street.trim())?),
+                Some(("actions", depth)) => DepthLimit::Actions(
+                    depth
+                        .trim()
+                        .parse()
+                        .map_err(|_| format!("Invalid depth: {depth}"))?,
+                ),
+                _ => return Err(format!("Invalid depth limit: {value}")),
+            });
+        }
+        "board_bet_sizes" => config
+            .board_bet_sizes
+            .push(board_bet_sizes_from_str(value)?),
+        "max_bets_per_street" => {
+            config.max_bets_per_street = Some(
+                value
+                    .parse()
+                    .map_err(|_| format!("Invalid number of bets: {value}"))?,
+            );
+        }
+        "min_raise" => {
+            config.min_raise = match value.split_once(' ') {
+                None if value == "full" => MinRaise::FullRaise,
+                Some(("ratio", ratio)) => MinRaise::Ratio(parse_float(ratio.trim())?),
+                _ => return Err(format!("Invalid minimum raise: {value}")),
+            };
+        }
+        "incomplete_raise" => {
+            config.incomplete_raise = match value {
+                "allow" => IncompleteRaise::Allow,
+                "disallow" => IncompleteRaise::Disallow,
+                _ => return Err(format!("Invalid incomplete raise: {value}")),
+            };
+        }
+        "add_line" => added_lines.push(line_from_str(value)?),
+        "remove_line" => removed_lines.push(line_from_str(value)?),
+        "node" => {
+            let (rules, value) = match value.strip_prefix("[rule") {
+                Some(rest) => match rest.split_once(']') {
+                    Some((rules, rest)) => (rules_from_str(rules)?, rest),
+                    None => return Err(format!("Expected '[rule <i>, ...]': {value}")),
+                },
+                None => (Vec::new(), value),
+            };
+            let Some((line, actions)) = value.split_once(':') else {
+                return Err(format!("Expected '<line> : <actions>': {value}"));
+            };
+            nodes.push((rules, line_from_str(line)?, line_from_str(actions)?));
+        }
+        _ => return Err(format!("Unknown key: {key}")),
+    }
+
+    Ok(())
+}
+
+/// Parses a street name.
+fn parse_street(s: &str) -> Result<BoardState, String> {
+    match s {
+        "flop" => Ok(BoardState::Flop),
+        "turn" => Ok(BoardState::Turn),
+        "river" => Ok(BoardState::River),
+        _ => Err(format!("Invalid street: {s}")),
+    }
+}
+
+/// Converts the subtrees of board bet size rules into a comma-separated string.
+fn rules_to_string(rules: &[u8]) -> String {
+    rules
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Parses the comma-separated subtrees of board bet size rules.
+fn rules_from_str(s: &str) -> Result<Vec<u8>, String> {
+    s.split(',')
+        .map(|rule| {
+            let rule = rule.trim();
+            rule.parse()
+                .map_err(|_| format!("Invalid rule index: {rule}"))
+        })
+        .collect()
+}
+
+/// Converts bet sizes into a comma-separated string.
+fn bet_sizes_to_string(sizes: &[BetSize]) -> String {
+    sizes
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Converts bet size options into the form `<bet sizes> ; <raise sizes>`.
+fn bet_size_options_to_string(options: &BetSizeOptions) -> String {
+    let mut raise = [&options.raise]
+        .into_iter()
+        .chain(options.reraise.iter())
+        .map(|sizes| bet_sizes_to_string(sizes))
+        .collect::<Vec<_>>()
+        .join(" | ");
+
+    if let Some(max_raises) = options.max_raises {
+        if !raise.is_empty() {
+            raise.push_str(", ");
+        }
+        write!(raise, "{max_raises}r").unwrap();
+    }
+
+    format!("{} ; {raise}", bet_sizes_to_string(&options.bet))
+}
+
+/// Parses bet size options of the form `<bet sizes> ; <raise sizes>`.
+fn bet_size_options_from_str(s: &str) -> Result<BetSizeOptions, String> {
+    match s.split_once(';') {
+        Some((bet, raise)) => (bet.trim(), raise.trim()).try_into(),
+        None => Err(format!("Expected '<bet sizes> ; <raise sizes>': {s}")),
+    }
+}
+
+/// Converts a board bet size rule into a string.
+fn board_bet_sizes_to_string(rule: &BoardBetSizes) -> String {
+    let textures = match rule.textures.is_empty() {
+        true => "*".to_string(),
+        // the names of the variants coincide with the names accepted by `from_str()`
+        false => rule
+            .textures
+            .iter()
+            .map(|texture| format!("{texture:?}").to_lowercase())
+            .collect::<Vec<_>>()
+            .join(", "),
+    };
+
+    let mut ret = format!(
+        "{} ; {textures} ; {} ; {}",
+        STREET_NAMES[rule.street as usize],
+        bet_size_options_to_string(&rule.bet_sizes[0]),
+        bet_size_options_to_string(&rule.bet_sizes[1]),
+    );
+
+    if let Some(donk) = &rule.donk_sizes {
+        write!(ret, " ; {}", bet_sizes_to_string(&donk.donk)).unwrap();
+    }
+
+    ret
+}
+
+/// Parses a board bet size rule.
+fn board_bet_sizes_from_str(s: &str) -> Result<BoardBetSizes, String> {
+    let fields = s.split(';').map(str::trim).collect::<Vec<_>>();
+    if fields.len() != 6 && fields.len() != 7 {
+        return Err(format!(
+            "Expected six or seven fields separated by ';': {s}"
+        ));
+    }
+
+    let textures = fields[1]
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|&token| !token.is_empty() && token != "*")
+        .map(str::parse::<BoardTexture>)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(BoardBetSizes {
+        street: parse_street(fields[0])?,
+        textures,
+        bet_sizes: [
+            (fields[2], fields[3]).try_into()?,
+            (fields[4], fields[5]).try_into()?,
+        ],
+        donk_sizes: fields.get(6).map(|&donk| donk.try_into()).transpose()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree_config() -> TreeConfig {
+        let bet_sizes = BetSizeOptions::try_from(("33%, 75%, e", "3x | 2.2x, a, 3r")).unwrap();
+        TreeConfig {
+            initial_state: BoardState::Turn,
+            starting_pot: 200,
+            effective_stack: 900,
+            rake_rate: 0.05,
+            rake_cap: 30.0,
+            turn_bet_sizes: [bet_sizes.clone(), ("50%", "").try_into().unwrap()],
+            river_bet_sizes: [bet_sizes.clone(), bet_sizes],
+            river_donk_sizes: Some("50%".try_into().unwrap()),
+            add_allin_threshold: 1.5,
+            force_allin_threshold: 0.15,
+            merging_threshold: 0.1,
+            depth_limit: Some(DepthLimit::Actions(8)),
+            board_bet_sizes: vec![BoardBetSizes {
+                street: BoardState::River,
+                textures: vec![BoardTexture::Paired, BoardTexture::TwoTone],
+                bet_sizes: [
+                    ("25%", "").try_into().unwrap(),
+                    ("25%", "2x").try_into().unwrap(),
+                ],
+                donk_sizes: Some("".try_into().unwrap()),
+            }],
+            max_bets_per_street: Some(4),
+            min_raise: MinRaise::Ratio(0.5),
+            incomplete_raise: IncompleteRaise::Disallow,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_text_round_trip() {
+        let mut action_tree = ActionTree::new(tree_config()).unwrap();
+        action_tree
+            .add_line(&line_from_str("B100").unwrap())
+            .unwrap();
+        action_tree
+            .remove_line(&line_from_str("X B100").unwrap())
+            .unwrap();
+
+        for explicit in [false, true] {
+            let text = action_tree.to_text(explicit);
+            let imported = ActionTree::from_text(&text).unwrap();
+            assert_eq!(imported.to_text(explicit), text);
+            assert_eq!(imported.added_lines(), action_tree.added_lines());
+            assert_eq!(imported.removed_lines(), action_tree.removed_lines());
+        }
+
+        let text = action_tree.to_text(false);
+        assert!(text.contains("turn_bet_sizes.ip = 50% ;\n"));
+        assert!(text.contains("river_bet_sizes.oop = 33%, 75%, e ; 3x | 2.2x, a, 3r\n"));
+        assert!(text.contains("depth_limit = actions 8\n"));
+        assert!(text.contains("board_bet_sizes = river ; paired, twotone ; 25% ; ; 25% ; 2x ;\n"));
+        assert!(text.contains("max_bets_per_street = 4\n"));
+        assert!(text.contains("min_raise = ratio 0.5\n"));
+        assert!(text.contains("incomplete_raise = disallow\n"));
+        assert!(!text.contains("turn_donk_sizes"));
+    }
+
+    #[test]
+    fn test_text_explicit_nodes() {
+        let text = "
+            initial_state = river
+            starting_pot = 100
+            effective_stack = 300
+            river_bet_sizes.oop = 50% ; 2x
+            river_bet_sizes.ip = 50% ; 2x
+            node = : X B50       # same as the configuration
+            node = X : X B30     # remove B50 and add B30
+            node = B50 : F C     # remove R100
+        ";
+
+        let mut action_tree = ActionTree::from_text(text).unwrap();
+        assert_eq!(
+            action_tree.added_lines(),
+            &[line_from_str("X B30").unwrap()]
+        );
+        assert_eq!(
+            action_tree.removed_lines(),
+            &[
+                line_from_str("X B50").unwrap(),
+                line_from_str("B50 R100").unwrap()
+            ]
+        );
+
+        action_tree.play(Action::Check).unwrap();
+        assert_eq!(
+            action_tree.available_actions(),
+            &[Action::Check, Action::Bet(30)]
+        );
+        action_tree.play(Action::Bet(30)).unwrap();
+        assert_eq!(
+            action_tree.available_actions(),
+            &[Action::Fold, Action::Call, Action::Raise(60)]
+        );
+    }
+
+    #[test]
+    fn test_text_rule_nodes() {
+        let text = "
+            initial_state = turn
+            starting_pot = 100
+            effective_stack = 300
+            river_bet_sizes.oop = 50% ;
+            river_bet_sizes.ip = 50% ;
+            board_bet_sizes = river ; paired ; 25% ; ; 25% ;
+        ";
+
+        let action_tree = ActionTree::from_text(text).unwrap();
+        let explicit = action_tree.to_text(true);
+        assert!(explicit.contains("node = X X : X B50\n"));
+        assert!(explicit.contains("node = [rule 1] X X : X B25\n"));
+        assert!(explicit.contains("node = [rule 1] X X B25 : F C\n"));
+        assert_eq!(
+            ActionTree::from_text(&explicit).unwrap().to_text(true),
+            explicit
+        );
+
+        // the nodes of the rule subtrees are only verified
+        let edited = explicit.replace("[rule 1] X X : X B25", "[rule 1] X X : X B30");
+        assert!(ActionTree::from_text(&edited).is_err());
+        let missing = format!("{text}\nnode = [rule 2] X X : X B25");
+        assert!(ActionTree::from_text(&missing).is_err());
+    }
+
+    #[test]
+    fn test_text_errors() {
+        let error_tests = [
+            "starting_pot = 100\nunknown = 1",
+            "starting_pot 100",
+            "starting_pot = 1e2",
+            "initial_state = preflop",
+            "starting_pot = 100\neffective_stack = 100\nriver_bet_sizes.oop = 50%",
+            "starting_pot = 100\neffective_stack = 100\ndepth_limit = street",
+            "starting_pot = 100\neffective_stack = 100\nadd_line = B",
+            "starting_pot = 100\neffective_stack = 100\nnode = X B50",
+            "starting_pot = 100\neffective_stack = 100\nnode = [rule x] : X",
+            "starting_pot = 100\neffective_stack = 100\nnode = [rule 1 : X",
+            "starting_pot = 100\neffective_stack = 100\nboard_bet_sizes = river ; * ; ; ",
+            "starting_pot = 100\neffective_stack = 100\nmin_raise = 0.5",
+            "starting_pot = 100\neffective_stack = 100\nmin_raise = ratio 0",
+            "starting_pot = 100\neffective_stack = 100\nmax_bets_per_street = 0",
+            "starting_pot = 100\neffective_stack = 100\nincomplete_raise = yes",
+            "effective_stack = 100",
+        ];
+
+        for text in error_tests {
+            assert!(ActionTree::from_text(text).is_err(), "{text}");
+        }
+
+        let result = ActionTree::from_text("starting_pot = 100\n\nfoo = bar");
+        assert_eq!(result.err().unwrap(), "Line 3: Unknown key: foo");
+    }
+}
@@ -227,6 +227,109 @@ pub(crate) fn encode_unsigned_slice(dst: &mut [u16], slice: &[f32]) -> f32 {
     scale
 }
 
+/// Encodes the `f32` slice to the `i8` slice with dithering, and returns the scale.
+#[inline]
+pub(crate) fn encode_signed_slice_i8(dst: &mut [i8], slice: &[f32]) -> f32 {
+    let scale = slice_absolute_max(slice);
+    let scale_nonzero = if scale == 0.0 { 1.0 } else { scale };
+    let encoder = i8::MAX as f32 / scale_nonzero;
+    let range = -(i8::MAX as f32)..=i8::MAX as f32;
+    dst.iter_mut()
+        .enumerate()
+        .zip(slice)
+        .for_each(|((i, d), s)| {
+            let x = (s * encoder + dither(s.to_bits(), i)).floor();
+            *d = unsafe {
+                x.clamp(*range.start(), *range.end())
+                    .to_int_unchecked::<i32>() as i8
+            };
+        });
+    scale
+}
+
+/// Encodes the `f32` slice to the `u8` slice with dithering, and returns the scale.
+#[inline]
+pub(crate) fn encode_unsigned_slice_u8(dst: &mut [u8], slice: &[f32]) -> f32 {
+    let scale = slice_nonnegative_max(slice);
+    let scale_nonzero = if scale == 0.0 { 1.0 } else { scale };
+    let encoder = u8::MAX as f32 / scale_nonzero;
+    dst.iter_mut()
+        .enumerate()
+        .zip(slice)
+        .for_each(|((i, d), s)| {
+            let x = (s * encoder + dither(s.to_bits(), i)).floor();
+            *d = unsafe { x.min(u8::MAX as f32).to_int_unchecked::<i32>() as u8 };
+        });
+    scale
+}
+
+/// Returns a pseudo-random number in [0, 1) determined by the given value and index.
+///
+/// The result is deterministic, but is uncorrelated enough with the inputs to be used as a
+/// threshold of stochastic rounding.
+#[inline]
+fn dither(bits: u32, index: usize) -> f32 {
+    let mut h = bits ^ (index as u32).wrapping_mul(0x9e3779b9);
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x7feb352d);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x846ca68b);
+    h ^= h >> 16;
+    (h >> 8) as f32 * (1.0 / (1 << 24) as f32)
+}
+
+/// A trait for the integer types used to store the compressed values.
+pub(crate) trait Quantized: Copy + Into<f32> {
+    /// The encoded value corresponding to the scale.
+    const MAX: f32;
+
+    /// Encodes the `f32` slice to `dst`, and returns the scale.
+    fn encode_slice(dst: &mut [Self], slice: &[f32]) -> f32;
+}
+
+impl Quantized for u16 {
+    const MAX: f32 = u16::MAX as f32;
+
+    #[inline]
+    fn encode_slice(dst: &mut [Self], slice: &[f32]) -> f32 {
+        encode_unsigned_slice(dst, slice)
+    }
+}
+
+impl Quantized for i16 {
+    const MAX: f32 = i16::MAX as f32;
+
+    #[inline]
+    fn encode_slice(dst: &mut [Self], slice: &[f32]) -> f32 {
+        encode_signed_slice(dst, slice)
+    }
+}
+
+impl Quantized for u8 {
+    const MAX: f32 = u8::MAX as f32;
+
+    #[inline]
+    fn encode_slice(dst: &mut [Self], slice: &[f32]) -> f32 {
+        encode_unsigned_slice_u8(dst, slice)
+    }
+}
+
+impl Quantized for i8 {
+    const MAX: f32 = i8::MAX as f32;
+
+    #[inline]
+    fn encode_slice(dst: &mut [Self], slice: &[f32]) -> f32 {
+        encode_signed_slice_i8(dst, slice)
+    }
+}
+
+/// Decodes the compressed slice with the given scale.
+#[inline]
+pub(crate) fn decode_slice<T: Quantized>(slice: &[T], scale: f32) -> Vec<f32> {
+    let decoder = scale / T::MAX;
+    slice.iter().map(|&x| x.into() * decoder).collect()
+}
+
 /// Applies the given swap to the given slice.
 #[inline]
 pub(crate) fn apply_swap<T>(slice: &mut [T], swap_list: &[(u16, u16)]) {
@@ -441,11 +544,17 @@ fn compute_cfvalue_recursive<T: Game>(
         // save the counterfactual values
         if save_cfvalues && node.cfvalue_storage_player() == Some(player) {
             let result = unsafe { &*(result as *const _ as *const [f32]) };
-            if game.is_compression_enabled() {
-                let cfv_scale = encode_signed_slice(node.cfvalues_chance_compressed_mut(), result);
-                node.set_cfvalue_chance_scale(cfv_scale);
-            } else {
-                node.cfvalues_chance_mut().copy_from_slice(result);
+            match game.storage_precision(node).cfvalues {
+                Precision::Float32 => node.cfvalues_chance_mut().copy_from_slice(result),
+                Precision::Int16 => {
+                    let cfv_scale =
+                        encode_signed_slice(node.cfvalues_chance_compressed_mut(), result);
+                    node.set_cfvalue_chance_scale(cfv_scale);
+                }
+                Precision::Int8 => {
+                    let cfv_scale = encode_signed_slice_i8(node.cfvalues_chance_i8_mut(), result);
+                    node.set_cfvalue_chance_scale(cfv_scale);
+                }
             }
         }
     }
@@ -465,17 +574,9 @@ fn compute_cfvalue_recursive<T: Game>(
 
         // obtain the strategy
         #[cfg(feature = "custom-alloc")]
-        let mut strategy = if game.is_compression_enabled() {
-            normalized_strategy_compressed_custom_alloc(node.strategy_compressed(), num_actions)
-        } else {
-            normalized_strategy_custom_alloc(node.strategy(), num_actions)
-        };
+        let mut strategy = node_strategy_custom_alloc(game, node);
         #[cfg(not(feature = "custom-alloc"))]
-        let mut strategy = if game.is_compression_enabled() {
-            normalized_strategy_compressed(node.strategy_compressed(), num_actions)
-        } else {
-            normalized_strategy(node.strategy(), num_actions)
-        };
+        let mut strategy = node_strategy(game, node);
 
         // node-locking
         let locking = game.locking_strategy(node);
@@ -488,11 +589,17 @@ fn compute_cfvalue_recursive<T: Game>(
 
         // save the counterfactual values
         if save_cfvalues {
-            if game.is_compression_enabled() {
-                let cfv_scale = encode_signed_slice(node.cfvalues_compressed_mut(), &cfv_actions);
-                node.set_cfvalue_scale(cfv_scale);
-            } else {
-                node.cfvalues_mut().copy_from_slice(&cfv_actions);
+            match game.storage_precision(node).cfvalues {
+                Precision::Float32 => node.cfvalues_mut().copy_from_slice(&cfv_actions),
+                Precision::Int16 => {
+                    let cfv_scale =
+                        encode_signed_slice(node.cfvalues_compressed_mut(), &cfv_actions);
+                    node.set_cfvalue_scale(cfv_scale);
+                }
+                Precision::Int8 => {
+                    let cfv_scale = encode_signed_slice_i8(node.cfvalues_i8_mut(), &cfv_actions);
+                    node.set_cfvalue_scale(cfv_scale);
+                }
             }
         }
     }
@@ -510,17 +617,9 @@ fn compute_cfvalue_recursive<T: Game>(
     } else {
         // obtain the strategy
         #[cfg(feature = "custom-alloc")]
-        let mut cfreach_actions = if game.is_compression_enabled() {
-            normalized_strategy_compressed_custom_alloc(node.strategy_compressed(), num_actions)
-        } else {
-            normalized_strategy_custom_alloc(node.strategy(), num_actions)
-        };
+        let mut cfreach_actions = node_strategy_custom_alloc(game, node);
         #[cfg(not(feature = "custom-alloc"))]
-        let mut cfreach_actions = if game.is_compression_enabled() {
-            normalized_strategy_compressed(node.strategy_compressed(), num_actions)
-        } else {
-            normalized_strategy(node.strategy(), num_actions)
-        };
+        let mut cfreach_actions = node_strategy(game, node);
 
         // node-locking
         let locking = game.locking_strategy(node);
@@ -553,17 +652,22 @@ fn compute_cfvalue_recursive<T: Game>(
     // save the counterfactual values for IP
     if save_cfvalues && node.has_cfvalues_ip() && player == 1 {
         let result = unsafe { &*(result as *const _ as *const [f32]) };
-        if game.is_compression_enabled() {
-            let cfv_scale = encode_signed_slice(node.cfvalues_ip_compressed_mut(), result);
-            node.set_cfvalue_ip_scale(cfv_scale);
-        } else {
-            node.cfvalues_ip_mut().copy_from_slice(result);
+        match game.storage_precision(node).cfvalues {
+            Precision::Float32 => node.cfvalues_ip_mut().copy_from_slice(result),
+            Precision::Int16 => {
+                let cfv_scale = encode_signed_slice(node.cfvalues_ip_compressed_mut(), result);
+                node.set_cfvalue_ip_scale(cfv_scale);
+            }
+            Precision::Int8 => {
+                let cfv_scale = encode_signed_slice_i8(node.cfvalues_ip_i8_mut(), result);
+                node.set_cfvalue_ip_scale(cfv_scale);
+            }
         }
     }
 }
 
 /// The recursive helper function for computing the counterfactual values of best response.
-fn compute_best_cfv_recursive<T: Game>(
+pub(crate) fn compute_best_cfv_recursive<T: Game>(
     result: &mut [MaybeUninit<f32>],
     game: &T,
     node: &T::Node,
@@ -679,17 +783,9 @@ fn compute_best_cfv_recursive<T: Game>(
     else {
         // obtain the strategy
         #[cfg(feature = "custom-alloc")]
-        let mut cfreach_actions = if game.is_compression_enabled() {
-            normalized_strategy_compressed_custom_alloc(node.strategy_compressed(), num_actions)
-        } else {
-            normalized_strategy_custom_alloc(node.strategy(), num_actions)
-        };
+        let mut cfreach_actions = node_strategy_custom_alloc(game, node);
         #[cfg(not(feature = "custom-alloc"))]
-        let mut cfreach_actions = if game.is_compression_enabled() {
-            normalized_strategy_compressed(node.strategy_compressed(), num_actions)
-        } else {
-            normalized_strategy(node.strategy(), num_actions)
-        };
+        let mut cfreach_actions = node_strategy(game, node);
 
         // node-locking
         let locking = game.locking_strategy(node);
@@ -769,15 +865,15 @@ pub(crate) fn normalized_strategy(strategy: &[f32], num_actions: usize) -> Vec<f
 
 #[cfg(feature = "custom-alloc")]
 #[inline]
-pub(crate) fn normalized_strategy_compressed_custom_alloc(
-    strategy: &[u16],
+pub(crate) fn normalized_strategy_compressed_custom_alloc<T: Quantized>(
+    strategy: &[T],
     num_actions: usize,
 ) -> Vec<f32, StackAlloc> {
     let mut normalized = Vec::with_capacity_in(strategy.len(), StackAlloc);
     let uninit = normalized.spare_capacity_mut();
 
     uninit.iter_mut().zip(strategy).for_each(|(n, s)| {
-        n.write(*s as f32);
+        n.write((*s).into());
     });
     unsafe { normalized.set_len(strategy.len()) };
 
@@ -795,12 +891,15 @@ pub(crate) fn normalized_strategy_compressed_custom_alloc(
 }
 
 #[inline]
-pub(crate) fn normalized_strategy_compressed(strategy: &[u16], num_actions: usize) -> Vec<f32> {
+pub(crate) fn normalized_strategy_compressed<T: Quantized>(
+    strategy: &[T],
+    num_actions: usize,
+) -> Vec<f32> {
     let mut normalized = Vec::with_capacity(strategy.len());
     let uninit = normalized.spare_capacity_mut();
 
     uninit.iter_mut().zip(strategy).for_each(|(n, s)| {
-        n.write(*s as f32);
+        n.write((*s).into());
     });
     unsafe { normalized.set_len(strategy.len()) };
 
@@ -817,6 +916,36 @@ pub(crate) fn normalized_strategy_compressed(strategy: &[u16], num_actions: usiz
     normalized
 }
 
+/// Obtains the normalized cumulative strategy of the given node.
+#[cfg(feature = "custom-alloc")]
+#[inline]
+pub(crate) fn node_strategy_custom_alloc<T: Game>(
+    game: &T,
+    node: &T::Node,
+) -> Vec<f32, StackAlloc> {
+    let num_actions = node.num_actions();
+    match game.storage_precision(node).strategy {
+        Precision::Float32 => normalized_strategy_custom_alloc(node.strategy(), num_actions),
+        Precision::Int16 => {
+            normalized_strategy_compressed_custom_alloc(node.strategy_compressed(), num_actions)
+        }
+        Precision::Int8 => {
+            normalized_strategy_compressed_custom_alloc(node.strategy_u8(), num_actions)
+        }
+    }
+}
+
+/// Obtains the normalized cumulative strategy of the given node.
+#[inline]
+pub(crate) fn node_strategy<T: Game>(game: &T, node: &T::Node) -> Vec<f32> {
+    let num_actions = node.num_actions();
+    match game.storage_precision(node).strategy {
+        Precision::Float32 => normalized_strategy(node.strategy(), num_actions),
+        Precision::Int16 => normalized_strategy_compressed(node.strategy_compressed(), num_actions),
+        Precision::Int8 => normalized_strategy_compressed(node.strategy_u8(), num_actions),
+    }
+}
+
 #[inline]
 pub(crate) fn apply_locking_strategy(dst: &mut [f32], locking: &[f32]) {
     if !locking.is_empty() {